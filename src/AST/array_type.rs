@@ -7,12 +7,14 @@ use crate::ast::{Checking, PrintAST, PrintUnparsedAST};
 use crate::ast::expression::ExprType;
 use crate::ast::primitive_types::AstTypes;
 use crate::globals::TAB_SIZE;
-use crate::utils::{generate_tabbed_string, SourcePosition};
+use crate::source_map::Span;
+use crate::utils::generate_tabbed_string;
 
+// int m[3][4] is an ArrayType of 3 whose element type is an ArrayType of 4 ints.
 #[derive(Clone, Debug, PartialEq)]
 pub struct ArrayType {
-    source_position: SourcePosition,
-    pub array_type: Arc<Box<AstTypes>>,
+    pub(crate) span: Span,
+    pub array_type: Arc<AstTypeVariant>,
     pub expression: ExprType,
 }
 
@@ -47,12 +49,12 @@ impl PrintUnparsedAST for ArrayType {
 
 impl ArrayType {
     pub fn new(
-        source_position: SourcePosition,
-        array_type: Arc<Box<AstTypes>>,
+        span: Span,
+        array_type: Arc<AstTypeVariant>,
         expression: ExprType,
     ) -> Self {
         Self {
-            source_position,
+            span,
             array_type,
             expression,
         }
@@ -65,6 +67,28 @@ pub enum AstTypeVariant {
     Array(ArrayType),
 }
 
+impl AstTypeVariant {
+    // The primitive type at the bottom of any array dimensions.
+    pub fn base_type(&self) -> &AstTypes {
+        match self {
+            AstTypeVariant::Primitive(primitive_type) => primitive_type,
+            AstTypeVariant::Array(array_type) => array_type.array_type.base_type(),
+        }
+    }
+
+    // Prints the "[size]" suffix of each dimension, outermost first, after a declared name.
+    pub fn unparse_dimensions(&self, depth: i32) {
+        if let AstTypeVariant::Array(array_type) = self {
+            print!("[");
+            if !matches!(array_type.expression, ExprType::EmptyExpr(_)) {
+                array_type.expression.unparse_to_code(depth);
+            }
+            print!("]");
+            array_type.array_type.unparse_dimensions(depth);
+        }
+    }
+}
+
 impl PrintAST for AstTypeVariant {
     fn visit_for_printing(&self, depth: i32) {
         match self {
@@ -10,9 +10,8 @@ use crate::ast::primitive_types::AstTypes;
 use crate::ast::statement::StmtType;
 use crate::ast::{Checking, PrintAST, PrintUnparsedAST};
 use crate::globals::TAB_SIZE;
-use crate::utils::{
-    generate_tabbed_string, print_indent, print_newline_and_indent, SourcePosition,
-};
+use crate::source_map::Span;
+use crate::utils::{generate_tabbed_string, print_indent, print_newline_and_indent};
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum DeclType {
@@ -20,6 +19,23 @@ pub enum DeclType {
     GlobalVarDecl(GlobalVarDecl),
     LocalVarDecl(LocalVarDecl),
     ParaDecl(ParaDecl),
+    IncludeDecl(IncludeDecl),
+    StructDecl(StructDecl),
+    FieldDecl(FieldDecl),
+}
+
+impl DeclType {
+    pub fn get_span(&self) -> &Span {
+        match self {
+            DeclType::FuncDecl(decl) => &decl.span,
+            DeclType::GlobalVarDecl(decl) => &decl.span,
+            DeclType::LocalVarDecl(decl) => &decl.span,
+            DeclType::ParaDecl(decl) => &decl.span,
+            DeclType::IncludeDecl(decl) => &decl.span,
+            DeclType::StructDecl(decl) => &decl.span,
+            DeclType::FieldDecl(decl) => &decl.span,
+        }
+    }
 }
 
 impl PrintAST for DeclType {
@@ -29,6 +45,9 @@ impl PrintAST for DeclType {
             DeclType::GlobalVarDecl(global_var_decl) => global_var_decl.visit_for_printing(depth),
             DeclType::LocalVarDecl(local_var_decl) => local_var_decl.visit_for_printing(depth),
             DeclType::ParaDecl(para_decl) => para_decl.visit_for_printing(depth),
+            DeclType::IncludeDecl(include_decl) => include_decl.visit_for_printing(depth),
+            DeclType::StructDecl(struct_decl) => struct_decl.visit_for_printing(depth),
+            DeclType::FieldDecl(field_decl) => field_decl.visit_for_printing(depth),
         }
     }
 }
@@ -40,17 +59,20 @@ impl PrintUnparsedAST for DeclType {
             DeclType::GlobalVarDecl(global_var_decl) => global_var_decl.unparse_to_code(depth),
             DeclType::LocalVarDecl(local_var_decl) => local_var_decl.unparse_to_code(depth),
             DeclType::ParaDecl(para_decl) => para_decl.unparse_to_code(depth),
+            DeclType::IncludeDecl(include_decl) => include_decl.unparse_to_code(depth),
+            DeclType::StructDecl(struct_decl) => struct_decl.unparse_to_code(depth),
+            DeclType::FieldDecl(field_decl) => field_decl.unparse_to_code(depth),
         }
     }
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct FuncDecl {
-    source_position: SourcePosition,
-    function_type: Box<AstTypeVariant>,
-    ident: Box<Ident>,
-    param_list: Box<ListType>,
-    statements: Box<StmtType>,
+    pub(crate) span: Span,
+    pub(crate) function_type: Box<AstTypeVariant>,
+    pub(crate) ident: Box<Ident>,
+    pub(crate) param_list: Box<ListType>,
+    pub(crate) statements: Box<StmtType>,
 }
 
 impl Checking for FuncDecl {
@@ -64,8 +86,8 @@ impl fmt::Display for FuncDecl {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{{ source_position: {:?}, t: {:?}, i: {:?}, pl: {:?}, s: {:?} }}",
-            self.source_position, self.function_type, self.ident, self.param_list, self.statements
+            "{{ span: {:?}, t: {:?}, i: {:?}, pl: {:?}, s: {:?} }}",
+            self.span, self.function_type, self.ident, self.param_list, self.statements
         )
     }
 }
@@ -83,9 +105,9 @@ impl PrintAST for FuncDecl {
 
 impl PrintUnparsedAST for FuncDecl {
     fn unparse_to_code(&self, depth: i32) {
-        // If line start and char start are 1, print newline.
+        // If the function starts the file, print newline.
         // Else, print indent
-        if self.source_position.line_start == 1 && self.source_position.char_start == 1 {
+        if self.span.lo == 0 {
             print_newline_and_indent(depth);
         } else {
             print_indent(depth);
@@ -95,42 +117,53 @@ impl PrintUnparsedAST for FuncDecl {
         self.ident.unparse_to_code(depth);
         print!("(");
         self.param_list.unparse_to_code(depth);
-        self.statements.unparse_to_code(depth);
+        if self.is_prototype() {
+            println!(";");
+        } else {
+            println!();
+            self.statements.unparse_to_code(depth);
+            println!();
+        }
     }
 }
 
 impl FuncDecl {
     pub fn new(
-        source_position: SourcePosition,
+        span: Span,
         function_type: Box<AstTypeVariant>,
         identifier: Box<Ident>,
         param_list: Box<ListType>,
         statement: Box<StmtType>,
     ) -> Self {
         Self {
-            source_position,
+            span,
             function_type,
             ident: identifier,
             param_list,
             statements: statement,
         }
     }
+
+    // A prototype such as int f(int a); has an EmptyStmt where its body would be.
+    pub fn is_prototype(&self) -> bool {
+        matches!(*self.statements, StmtType::EmptyStmt(_))
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct GlobalVarDecl {
-    source_position: SourcePosition,
-    declaration_type: Box<AstTypeVariant>,
-    ident: Box<Ident>,
-    expr: Box<ExprType>,
+    pub(crate) span: Span,
+    pub(crate) declaration_type: Box<AstTypeVariant>,
+    pub(crate) ident: Box<Ident>,
+    pub(crate) expr: Box<ExprType>,    pub(crate) is_const: bool,
 }
 
 impl fmt::Display for GlobalVarDecl {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{{ source_position: {:?}, t: {:?}, i: {:?}, expr: {:?} }}",
-            self.source_position, self.declaration_type, self.ident, self.expr
+            "{{ span: {:?}, t: {:?}, i: {:?}, expr: {:?} }}",
+            self.span, self.declaration_type, self.ident, self.expr
         )
     }
 }
@@ -145,7 +178,7 @@ impl Checking for GlobalVarDecl {
 impl PrintAST for GlobalVarDecl {
     fn visit_for_printing(&self, depth: i32) {
         let tabbed_string = generate_tabbed_string(std::any::type_name::<Self>(), depth);
-        println!("{}", tabbed_string);
+        print_with_qualifier(&tabbed_string, self.is_const);
         self.declaration_type.visit_for_printing(depth + 1);
         self.ident.visit_for_printing(depth + 1);
         self.expr.visit_for_printing(depth + 1);
@@ -155,51 +188,53 @@ impl PrintAST for GlobalVarDecl {
 impl PrintUnparsedAST for GlobalVarDecl {
     fn unparse_to_code(&self, depth: i32) {
         print_indent(depth);
+        if self.is_const {
+            print!("const ");
+        }
         self.declaration_type.unparse_to_code(depth);
         print!(" ");
         self.ident.unparse_to_code(depth);
-        if matches!(*self.declaration_type, AstTypeVariant::Array(_)) {
-            print!("[");
-            self.expr.unparse_to_code(depth);
-            print!("]");
-        } else {
+        self.declaration_type.unparse_dimensions(depth);
+        if !matches!(*self.expr, ExprType::EmptyExpr(_)) {
             print!(" = ");
             self.expr.unparse_to_code(depth);
         }
-        print!(";");
+        println!(";");
     }
 }
 
 impl GlobalVarDecl {
     pub fn new(
-        source_position: SourcePosition,
+        span: Span,
         declaration_type: Box<AstTypeVariant>,
         ident: Box<Ident>,
         expr: Box<ExprType>,
+        is_const: bool,
     ) -> Self {
         Self {
-            source_position,
+            span,
             declaration_type,
             ident,
             expr,
+            is_const,
         }
     }
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct LocalVarDecl {
-    source_position: SourcePosition,
-    declaration_type: Box<AstTypeVariant>,
-    ident: Box<Ident>,
-    expr: Box<ExprType>,
+    pub(crate) span: Span,
+    pub(crate) declaration_type: Box<AstTypeVariant>,
+    pub(crate) ident: Box<Ident>,
+    pub(crate) expr: Box<ExprType>,    pub(crate) is_const: bool,
 }
 
 impl fmt::Display for LocalVarDecl {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{{ source_position: {:?}, t: {:?}, i: {:?}, expr: {:?} }}",
-            self.source_position, self.declaration_type, self.ident, self.expr
+            "{{ span: {:?}, t: {:?}, i: {:?}, expr: {:?} }}",
+            self.span, self.declaration_type, self.ident, self.expr
         )
     }
 }
@@ -214,7 +249,7 @@ impl Checking for LocalVarDecl {
 impl PrintAST for LocalVarDecl {
     fn visit_for_printing(&self, depth: i32) {
         let tabbed_string = generate_tabbed_string(std::any::type_name::<Self>(), depth);
-        println!("{}", tabbed_string);
+        print_with_qualifier(&tabbed_string, self.is_const);
         self.declaration_type.visit_for_printing(depth + 1);
         self.ident.visit_for_printing(depth + 1);
         self.expr.visit_for_printing(depth + 1);
@@ -224,17 +259,13 @@ impl PrintAST for LocalVarDecl {
 impl PrintUnparsedAST for LocalVarDecl {
     fn unparse_to_code(&self, depth: i32) {
         print_newline_and_indent(depth);
+        if self.is_const {
+            print!("const ");
+        }
         self.declaration_type.unparse_to_code(depth);
         print!(" ");
         self.ident.unparse_to_code(depth);
-        if let AstTypeVariant::Array(curr_array) = &*self.declaration_type {
-            let expr: &ExprType = &curr_array.expression;
-            print!("[");
-            if !matches!(expr, ExprType::EmptyExpr(_)) {
-                curr_array.unparse_to_code(depth);
-            }
-            print!("]");
-        }
+        self.declaration_type.unparse_dimensions(depth);
 
         if !matches!(*self.expr, ExprType::EmptyExpr(_)) {
             print!(" = ");
@@ -246,25 +277,27 @@ impl PrintUnparsedAST for LocalVarDecl {
 
 impl LocalVarDecl {
     pub fn new(
-        source_position: SourcePosition,
+        span: Span,
         declaration_type: Box<AstTypeVariant>,
         ident: Box<Ident>,
         expr: Box<ExprType>,
+        is_const: bool,
     ) -> Self {
         Self {
-            source_position,
+            span,
             declaration_type,
             ident,
             expr,
+            is_const,
         }
     }
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct ParaDecl {
-    source_position: SourcePosition,
-    declaration_type: Box<AstTypeVariant>,
-    ident: Box<Ident>,
+    pub(crate) span: Span,
+    pub(crate) declaration_type: Box<AstTypeVariant>,
+    pub(crate) ident: Box<Ident>,    pub(crate) is_const: bool,
 }
 
 impl Checking for ParaDecl {
@@ -278,8 +311,8 @@ impl fmt::Display for ParaDecl {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{{ source_position: {:?}, t: {:?}, i: {:?} }}",
-            self.source_position, self.declaration_type, self.ident
+            "{{ span: {:?}, t: {:?}, i: {:?} }}",
+            self.span, self.declaration_type, self.ident
         )
     }
 }
@@ -287,7 +320,7 @@ impl fmt::Display for ParaDecl {
 impl PrintAST for ParaDecl {
     fn visit_for_printing(&self, depth: i32) {
         let tabbed_string = generate_tabbed_string(std::any::type_name::<Self>(), depth);
-        println!("{}", tabbed_string);
+        print_with_qualifier(&tabbed_string, self.is_const);
         self.declaration_type.visit_for_printing(depth + 1);
         self.ident.visit_for_printing(depth + 1);
     }
@@ -295,30 +328,196 @@ impl PrintAST for ParaDecl {
 
 impl PrintUnparsedAST for ParaDecl {
     fn unparse_to_code(&self, depth: i32) {
+        if self.is_const {
+            print!("const ");
+        }
         self.declaration_type.unparse_to_code(depth);
         print!(" ");
         self.ident.unparse_to_code(depth);
-        if let AstTypeVariant::Array(curr_array) = &*self.declaration_type {
-            let expr: &ExprType = &curr_array.expression;
-            print!("[");
-            if !matches!(expr, ExprType::EmptyExpr(_)) {
-                curr_array.unparse_to_code(depth);
-            }
-            print!("]");
-        }
+        self.declaration_type.unparse_dimensions(depth);
     }
 }
 
 impl ParaDecl {
     pub fn new(
-        source_position: SourcePosition,
+        span: Span,
         decl_type: Box<AstTypeVariant>,
         ident: Box<Ident>,
+        is_const: bool,
     ) -> Self {
         Self {
-            source_position,
+            span,
             declaration_type: decl_type,
             ident,
+            is_const,
         }
     }
 }
+
+// #include "path" at global scope.  The loader replaces it with the declarations of the file it
+// names, so only a program parsed on its own still contains one.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IncludeDecl {
+    pub(crate) span: Span,
+    pub(crate) path: String,
+}
+
+impl fmt::Display for IncludeDecl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{ span: {:?}, path: {:?} }}", self.span, self.path)
+    }
+}
+
+impl PrintAST for IncludeDecl {
+    fn visit_for_printing(&self, depth: i32) {
+        let tabbed_string = generate_tabbed_string(std::any::type_name::<Self>(), depth);
+        println!("{} ({})", tabbed_string, self.path);
+    }
+}
+
+impl PrintUnparsedAST for IncludeDecl {
+    fn unparse_to_code(&self, depth: i32) {
+        // A directive runs to the end of its line.
+        print_indent(depth);
+        println!("#include \"{}\"", self.path);
+    }
+}
+
+impl IncludeDecl {
+    pub fn new(span: Span, path: String) -> Self {
+        Self { span, path }
+    }
+}
+
+// struct Name { fields };  at global scope.  The fields are a DeclList of FieldDecls.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StructDecl {
+    pub(crate) span: Span,
+    pub(crate) ident: Box<Ident>,
+    pub(crate) field_list: Box<ListType>,
+}
+
+impl fmt::Display for StructDecl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{ span: {:?}, i: {:?}, fl: {:?} }}",
+            self.span, self.ident, self.field_list
+        )
+    }
+}
+
+impl Checking for StructDecl {
+    fn visit_for_semantics_checking(&self) {
+        println!("Visiting StructDecl node.");
+    }
+}
+
+impl PrintAST for StructDecl {
+    fn visit_for_printing(&self, depth: i32) {
+        let tabbed_string = generate_tabbed_string(std::any::type_name::<Self>(), depth);
+        println!("{}", tabbed_string);
+        self.ident.visit_for_printing(depth + 1);
+        self.field_list.visit_for_printing(depth + 1);
+    }
+}
+
+impl PrintUnparsedAST for StructDecl {
+    fn unparse_to_code(&self, depth: i32) {
+        print_indent(depth);
+        print!("struct ");
+        self.ident.unparse_to_code(depth);
+        print!(" {{");
+        self.field_list.unparse_to_code(depth + 1);
+        print_newline_and_indent(depth);
+        println!("}};");
+    }
+}
+
+impl StructDecl {
+    pub fn new(span: Span, ident: Box<Ident>, field_list: Box<ListType>) -> Self {
+        Self {
+            span,
+            ident,
+            field_list,
+        }
+    }
+
+    pub fn fields(&self) -> Vec<&FieldDecl> {
+        let mut fields = Vec::new();
+        let mut current = &*self.field_list;
+        while let ListType::DeclList(list) = current {
+            if let DeclType::FieldDecl(field_decl) = &*list.decl_type {
+                fields.push(field_decl);
+            }
+            current = &list.decl_list;
+        }
+        fields
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldDecl {
+    pub(crate) span: Span,
+    pub(crate) declaration_type: Box<AstTypeVariant>,
+    pub(crate) ident: Box<Ident>,
+}
+
+impl Checking for FieldDecl {
+    fn visit_for_semantics_checking(&self) {
+        println!("Visiting FieldDecl node.");
+    }
+}
+
+impl fmt::Display for FieldDecl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{ span: {:?}, t: {:?}, i: {:?} }}",
+            self.span, self.declaration_type, self.ident
+        )
+    }
+}
+
+impl PrintAST for FieldDecl {
+    fn visit_for_printing(&self, depth: i32) {
+        let tabbed_string = generate_tabbed_string(std::any::type_name::<Self>(), depth);
+        println!("{}", tabbed_string);
+        self.declaration_type.visit_for_printing(depth + 1);
+        self.ident.visit_for_printing(depth + 1);
+    }
+}
+
+impl PrintUnparsedAST for FieldDecl {
+    fn unparse_to_code(&self, depth: i32) {
+        print_newline_and_indent(depth);
+        self.declaration_type.unparse_to_code(depth);
+        print!(" ");
+        self.ident.unparse_to_code(depth);
+        self.declaration_type.unparse_dimensions(depth);
+        print!(";");
+    }
+}
+
+impl FieldDecl {
+    pub fn new(
+        span: Span,
+        declaration_type: Box<AstTypeVariant>,
+        ident: Box<Ident>,
+    ) -> Self {
+        Self {
+            span,
+            declaration_type,
+            ident,
+        }
+    }
+}
+
+// A const declaration prints as "LocalVarDecl (const)", the way an Ident shows its spelling.
+fn print_with_qualifier(tabbed_string: &str, is_const: bool) {
+    if is_const {
+        println!("{} (const)", tabbed_string);
+    } else {
+        println!("{}", tabbed_string);
+    }
+}
@@ -4,13 +4,15 @@ use std::io::{BufWriter, Write};
 
 use crate::ast::ident::Ident;
 use crate::ast::list::{EmptyArrayExprList, ListType};
-use crate::ast::literals::{BooleanLiteral, FloatLiteral, IntLiteral, Operator, StringLiteral};
+use crate::ast::literals::{
+    BooleanLiteral, CharLiteral, FloatLiteral, IntLiteral, Operator, StringLiteral,
+};
+use crate::ast::primitive_types::AstTypes;
 use crate::ast::variable::VarUntyped;
 use crate::ast::{Checking, PrintAST, PrintUnparsedAST};
 use crate::globals::TAB_SIZE;
-use crate::utils::{
-    generate_indent, generate_tabbed_string, print_newline_and_indent, SourcePosition,
-};
+use crate::source_map::Span;
+use crate::utils::{generate_indent, generate_tabbed_string, print_newline_and_indent};
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum ExprType {
@@ -21,10 +23,17 @@ pub enum ExprType {
     BinaryExpr(BinaryExpr),
     BooleanExpr(BooleanExpr),
     CallExpr(CallExpr),
+    CastExpr(CastExpr),
+    CharExpr(CharExpr),
+    CompoundAssignExpr(CompoundAssignExpr),
+    ConditionalExpr(ConditionalExpr),
     EmptyArrayExprList(EmptyArrayExprList),
     EmptyExpr(EmptyExpr),
+    FieldExpr(FieldExpr),
     FloatExpr(FloatExpr),
     IntExpr(IntExpr),
+    ParenExpr(ParenExpr),
+    PostfixExpr(PostfixExpr),
     StringExpr(StringExpr),
     UnaryExpr(UnaryExpr),
     VarExpr(VarExpr),
@@ -33,22 +42,29 @@ pub enum ExprType {
 impl ExprType {
     // https://docs.rs/enum_dispatch/latest/enum_dispatch/
     // See this crate for macros to refactor this.
-    pub fn get_source_position(&self) -> &SourcePosition {
+    pub fn get_span(&self) -> &Span {
         match self {
-            ExprType::Arg(expr) => &expr.source_position,
-            ExprType::ArrayExpr(expr) => &expr.source_position,
-            ExprType::ArrayInitExpr(expr) => &expr.source_position,
-            ExprType::AssignExpr(expr) => &expr.source_position,
-            ExprType::BinaryExpr(expr) => &expr.source_position,
-            ExprType::BooleanExpr(expr) => &expr.source_position,
-            ExprType::CallExpr(expr) => &expr.source_position,
-            ExprType::EmptyArrayExprList(expr) => &expr.source_position,
-            ExprType::FloatExpr(expr) => &expr.source_position,
-            ExprType::IntExpr(expr) => &expr.source_position,
-            ExprType::StringExpr(expr) => &expr.source_position,
-            ExprType::UnaryExpr(expr) => &expr.source_position,
-            ExprType::VarExpr(expr) => &expr.source_position,
-            _ => panic!("Variant does not have a source_position"),
+            ExprType::Arg(expr) => &expr.span,
+            ExprType::ArrayExpr(expr) => &expr.span,
+            ExprType::ArrayInitExpr(expr) => &expr.span,
+            ExprType::AssignExpr(expr) => &expr.span,
+            ExprType::BinaryExpr(expr) => &expr.span,
+            ExprType::BooleanExpr(expr) => &expr.span,
+            ExprType::CallExpr(expr) => &expr.span,
+            ExprType::CastExpr(expr) => &expr.span,
+            ExprType::CharExpr(expr) => &expr.span,
+            ExprType::CompoundAssignExpr(expr) => &expr.span,
+            ExprType::ConditionalExpr(expr) => &expr.span,
+            ExprType::EmptyArrayExprList(expr) => &expr.span,
+            ExprType::EmptyExpr(expr) => &expr.span,
+            ExprType::FieldExpr(expr) => &expr.span,
+            ExprType::FloatExpr(expr) => &expr.span,
+            ExprType::IntExpr(expr) => &expr.span,
+            ExprType::ParenExpr(expr) => &expr.span,
+            ExprType::PostfixExpr(expr) => &expr.span,
+            ExprType::StringExpr(expr) => &expr.span,
+            ExprType::UnaryExpr(expr) => &expr.span,
+            ExprType::VarExpr(expr) => &expr.span,
         }
     }
 }
@@ -63,10 +79,17 @@ impl PrintAST for ExprType {
             ExprType::BinaryExpr(expr) => expr.visit_for_printing(depth),
             ExprType::BooleanExpr(expr) => expr.visit_for_printing(depth),
             ExprType::CallExpr(expr) => expr.visit_for_printing(depth),
+            ExprType::CastExpr(expr) => expr.visit_for_printing(depth),
+            ExprType::CharExpr(expr) => expr.visit_for_printing(depth),
+            ExprType::CompoundAssignExpr(expr) => expr.visit_for_printing(depth),
+            ExprType::ConditionalExpr(expr) => expr.visit_for_printing(depth),
             ExprType::EmptyArrayExprList(expr) => expr.visit_for_printing(depth),
             ExprType::EmptyExpr(expr) => expr.visit_for_printing(depth),
+            ExprType::FieldExpr(expr) => expr.visit_for_printing(depth),
             ExprType::FloatExpr(expr) => expr.visit_for_printing(depth),
             ExprType::IntExpr(expr) => expr.visit_for_printing(depth),
+            ExprType::ParenExpr(expr) => expr.visit_for_printing(depth),
+            ExprType::PostfixExpr(expr) => expr.visit_for_printing(depth),
             ExprType::StringExpr(expr) => expr.visit_for_printing(depth),
             ExprType::UnaryExpr(expr) => expr.visit_for_printing(depth),
             ExprType::VarExpr(expr) => expr.visit_for_printing(depth),
@@ -84,10 +107,17 @@ impl PrintUnparsedAST for ExprType {
             ExprType::BinaryExpr(expr) => expr.unparse_to_code(depth),
             ExprType::BooleanExpr(expr) => expr.unparse_to_code(depth),
             ExprType::CallExpr(expr) => expr.unparse_to_code(depth),
+            ExprType::CastExpr(expr) => expr.unparse_to_code(depth),
+            ExprType::CharExpr(expr) => expr.unparse_to_code(depth),
+            ExprType::CompoundAssignExpr(expr) => expr.unparse_to_code(depth),
+            ExprType::ConditionalExpr(expr) => expr.unparse_to_code(depth),
             ExprType::EmptyArrayExprList(expr) => expr.unparse_to_code(depth),
             ExprType::EmptyExpr(expr) => expr.unparse_to_code(depth),
+            ExprType::FieldExpr(expr) => expr.unparse_to_code(depth),
             ExprType::FloatExpr(expr) => expr.unparse_to_code(depth),
             ExprType::IntExpr(expr) => expr.unparse_to_code(depth),
+            ExprType::ParenExpr(expr) => expr.unparse_to_code(depth),
+            ExprType::PostfixExpr(expr) => expr.unparse_to_code(depth),
             ExprType::StringExpr(expr) => expr.unparse_to_code(depth),
             ExprType::UnaryExpr(expr) => expr.unparse_to_code(depth),
             ExprType::VarExpr(expr) => expr.unparse_to_code(depth),
@@ -97,8 +127,8 @@ impl PrintUnparsedAST for ExprType {
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct Arg {
-    pub source_position: SourcePosition,
-    expr: Box<ExprType>,
+    pub span: Span,
+    pub(crate) expr: Box<ExprType>,
 }
 
 impl Checking for Arg {
@@ -112,8 +142,8 @@ impl std::fmt::Display for Arg {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{{ source_position: {:?}, e: {:?} }}",
-            self.source_position, self.expr
+            "{{ span: {:?}, e: {:?} }}",
+            self.span, self.expr
         )
     }
 }
@@ -133,19 +163,20 @@ impl PrintUnparsedAST for Arg {
 }
 
 impl Arg {
-    pub fn new(source_position: SourcePosition, e: Box<ExprType>) -> Self {
+    pub fn new(span: Span, e: Box<ExprType>) -> Self {
         Self {
-            source_position,
+            span,
             expr: e,
         }
     }
 }
 
+// m[i][j] is an ArrayExpr indexing the ArrayExpr m[i].
 #[derive(Clone, Debug, PartialEq)]
 pub struct ArrayExpr {
-    source_position: SourcePosition,
-    var: VarUntyped,
-    expr: Box<ExprType>,
+    pub(crate) span: Span,
+    pub(crate) array: Box<ExprType>,
+    pub(crate) expr: Box<ExprType>,
 }
 
 impl Checking for ArrayExpr {
@@ -159,8 +190,8 @@ impl std::fmt::Display for ArrayExpr {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{{ source_position: {:?}, v: {:?}, e: {:?} }}",
-            self.source_position, self.var, self.expr
+            "{{ span: {:?}, a: {:?}, e: {:?} }}",
+            self.span, self.array, self.expr
         )
     }
 }
@@ -169,25 +200,25 @@ impl PrintAST for ArrayExpr {
     fn visit_for_printing(&self, depth: i32) {
         let tabbed_string = generate_tabbed_string(std::any::type_name::<Self>(), depth);
         println!("{}", tabbed_string);
-        self.var.visit_for_printing(depth + 1);
+        self.array.visit_for_printing(depth + 1);
         self.expr.visit_for_printing(depth + 1);
     }
 }
 
 impl PrintUnparsedAST for ArrayExpr {
     fn unparse_to_code(&self, depth: i32) {
-        self.var.unparse_to_code(depth);
-        println!("[");
+        self.array.unparse_to_code(depth);
+        print!("[");
         self.expr.unparse_to_code(depth);
-        println!("]");
+        print!("]");
     }
 }
 
 impl ArrayExpr {
-    pub fn new(source_position: SourcePosition, var: VarUntyped, expr: Box<ExprType>) -> Self {
+    pub fn new(span: Span, array: Box<ExprType>, expr: Box<ExprType>) -> Self {
         Self {
-            source_position,
-            var,
+            span,
+            array,
             expr,
         }
     }
@@ -195,9 +226,9 @@ impl ArrayExpr {
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct AssignExpr {
-    source_position: SourcePosition,
-    expression_one: Box<ExprType>,
-    expression_two: Box<ExprType>,
+    pub(crate) span: Span,
+    pub(crate) expression_one: Box<ExprType>,
+    pub(crate) expression_two: Box<ExprType>,
 }
 
 impl Checking for AssignExpr {
@@ -211,8 +242,8 @@ impl std::fmt::Display for AssignExpr {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{{ source_position: {:?}, e1: {:?}, e2: {:?} }}",
-            self.source_position, self.expression_one, self.expression_two
+            "{{ span: {:?}, e1: {:?}, e2: {:?} }}",
+            self.span, self.expression_one, self.expression_two
         )
     }
 }
@@ -228,18 +259,138 @@ impl PrintAST for AssignExpr {
 
 impl PrintUnparsedAST for AssignExpr {
     fn unparse_to_code(&self, depth: i32) {
-        print!("(");
         self.expression_one.unparse_to_code(depth);
         print!(" = ");
         self.expression_two.unparse_to_code(depth);
-        print!(")");
     }
 }
 
 impl AssignExpr {
-    pub fn new(source_position: SourcePosition, e1: Box<ExprType>, e2: Box<ExprType>) -> Self {
+    pub fn new(span: Span, e1: Box<ExprType>, e2: Box<ExprType>) -> Self {
         Self {
-            source_position,
+            span,
+            expression_one: e1,
+            expression_two: e2,
+        }
+    }
+}
+
+// An assignment such as x += 2, which is x = x + 2 with x evaluated once.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CompoundAssignExpr {
+    pub(crate) span: Span,
+    pub(crate) expression_one: Box<ExprType>,
+    pub(crate) operator: Operator,
+    pub(crate) expression_two: Box<ExprType>,
+}
+
+impl Checking for CompoundAssignExpr {
+    fn visit_for_semantics_checking(&self) {
+        println!("Visiting CompoundAssignExpr node.");
+    }
+}
+
+impl std::fmt::Display for CompoundAssignExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{{ span: {:?}, e1: {:?}, o: {:?}, e2: {:?} }}",
+            self.span, self.expression_one, self.operator, self.expression_two
+        )
+    }
+}
+
+impl PrintAST for CompoundAssignExpr {
+    fn visit_for_printing(&self, depth: i32) {
+        let tabbed_string = generate_tabbed_string(std::any::type_name::<Self>(), depth);
+        println!("{}", tabbed_string);
+        self.expression_one.visit_for_printing(depth + 1);
+        self.operator.visit_for_printing(depth + 1);
+        self.expression_two.visit_for_printing(depth + 1);
+    }
+}
+
+impl PrintUnparsedAST for CompoundAssignExpr {
+    fn unparse_to_code(&self, depth: i32) {
+        self.expression_one.unparse_to_code(depth);
+        print!(" ");
+        self.operator.unparse_to_code(depth);
+        print!(" ");
+        self.expression_two.unparse_to_code(depth);
+    }
+}
+
+impl CompoundAssignExpr {
+    pub fn new(
+        span: Span,
+        expression_one: Box<ExprType>,
+        operator: Operator,
+        expression_two: Box<ExprType>,
+    ) -> Self {
+        Self {
+            span,
+            expression_one,
+            operator,
+            expression_two,
+        }
+    }
+
+    // The binary operator applied, e.g. + for +=.
+    pub fn binary_operator(&self) -> &str {
+        self.operator.spelling.trim_end_matches('=')
+    }
+}
+
+// The ternary c ? e1 : e2.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConditionalExpr {
+    pub(crate) span: Span,
+    pub(crate) condition: Box<ExprType>,
+    pub(crate) expression_one: Box<ExprType>,
+    pub(crate) expression_two: Box<ExprType>,
+}
+
+impl Checking for ConditionalExpr {
+    fn visit_for_semantics_checking(&self) {
+        println!("Visiting ConditionalExpr node.");
+    }
+}
+
+impl std::fmt::Display for ConditionalExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{{ span: {:?}, c: {:?}, e1: {:?}, e2: {:?} }}",
+            self.span, self.condition, self.expression_one, self.expression_two
+        )
+    }
+}
+
+impl PrintAST for ConditionalExpr {
+    fn visit_for_printing(&self, depth: i32) {
+        let tabbed_string = generate_tabbed_string(std::any::type_name::<Self>(), depth);
+        println!("{}", tabbed_string);
+        self.condition.visit_for_printing(depth + 1);
+        self.expression_one.visit_for_printing(depth + 1);
+        self.expression_two.visit_for_printing(depth + 1);
+    }
+}
+
+impl PrintUnparsedAST for ConditionalExpr {
+    fn unparse_to_code(&self, depth: i32) {
+        self.condition.unparse_to_code(depth);
+        print!(" ? ");
+        self.expression_one.unparse_to_code(depth);
+        print!(" : ");
+        self.expression_two.unparse_to_code(depth);
+    }
+}
+
+impl ConditionalExpr {
+    pub fn new(span: Span, c: Box<ExprType>, e1: Box<ExprType>, e2: Box<ExprType>) -> Self {
+        Self {
+            span,
+            condition: c,
             expression_one: e1,
             expression_two: e2,
         }
@@ -248,8 +399,8 @@ impl AssignExpr {
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct ArrayInitExpr {
-    source_position: SourcePosition,
-    init_list: Box<ListType>, // Needs definition
+    pub(crate) span: Span,
+    pub(crate) init_list: Box<ListType>, // Needs definition
 }
 
 impl Checking for ArrayInitExpr {
@@ -263,8 +414,8 @@ impl fmt::Display for ArrayInitExpr {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{{ source_position: {:?}, il: {:?} }}",
-            self.source_position, self.init_list
+            "{{ span: {:?}, il: {:?} }}",
+            self.span, self.init_list
         )
     }
 }
@@ -286,9 +437,9 @@ impl PrintUnparsedAST for ArrayInitExpr {
 }
 
 impl ArrayInitExpr {
-    pub fn new(source_position: SourcePosition, init_list: Box<ListType>) -> Self {
+    pub fn new(span: Span, init_list: Box<ListType>) -> Self {
         Self {
-            source_position,
+            span,
             init_list,
         }
     }
@@ -296,10 +447,10 @@ impl ArrayInitExpr {
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct BinaryExpr {
-    source_position: SourcePosition,
-    expression_one: Box<ExprType>,
-    operator: Operator,
-    expression_two: Box<ExprType>,
+    pub(crate) span: Span,
+    pub(crate) expression_one: Box<ExprType>,
+    pub(crate) operator: Operator,
+    pub(crate) expression_two: Box<ExprType>,
 }
 
 impl Checking for BinaryExpr {
@@ -313,8 +464,8 @@ impl fmt::Display for BinaryExpr {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{{ source_position: {:?}, e1: {:?}, o: {:?}, e2: {:?} }}",
-            self.source_position, self.expression_one, self.operator, self.expression_two
+            "{{ span: {:?}, e1: {:?}, o: {:?}, e2: {:?} }}",
+            self.span, self.expression_one, self.operator, self.expression_two
         )
     }
 }
@@ -331,25 +482,23 @@ impl PrintAST for BinaryExpr {
 
 impl PrintUnparsedAST for BinaryExpr {
     fn unparse_to_code(&self, depth: i32) {
-        print!("(");
         self.expression_one.unparse_to_code(depth);
         print!(" ");
         self.operator.unparse_to_code(depth);
         print!(" ");
         self.expression_two.unparse_to_code(depth);
-        print!(")");
     }
 }
 
 impl BinaryExpr {
     pub fn new(
-        source_position: SourcePosition,
+        span: Span,
         expression_one: Box<ExprType>,
         operator: Operator,
         expression_two: Box<ExprType>,
     ) -> Self {
         Self {
-            source_position,
+            span,
             expression_one,
             operator,
             expression_two,
@@ -357,10 +506,159 @@ impl BinaryExpr {
     }
 }
 
+// Member access e.x, where e has a struct type.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldExpr {
+    pub(crate) span: Span,
+    pub(crate) expression: Box<ExprType>,
+    pub(crate) field: Ident,
+}
+
+impl Checking for FieldExpr {
+    fn visit_for_semantics_checking(&self) {
+        println!("Visiting FieldExpr node.");
+    }
+}
+
+impl fmt::Display for FieldExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{ span: {:?}, e: {:?}, f: {:?} }}",
+            self.span, self.expression, self.field
+        )
+    }
+}
+
+impl PrintAST for FieldExpr {
+    fn visit_for_printing(&self, depth: i32) {
+        let tabbed_string = generate_tabbed_string(std::any::type_name::<Self>(), depth);
+        println!("{}", tabbed_string);
+        self.expression.visit_for_printing(depth + 1);
+        self.field.visit_for_printing(depth + 1);
+    }
+}
+
+impl PrintUnparsedAST for FieldExpr {
+    fn unparse_to_code(&self, depth: i32) {
+        self.expression.unparse_to_code(depth);
+        print!(".");
+        self.field.unparse_to_code(depth);
+    }
+}
+
+impl FieldExpr {
+    pub fn new(span: Span, expression: Box<ExprType>, field: Ident) -> Self {
+        Self {
+            span,
+            expression,
+            field,
+        }
+    }
+}
+
+// (int)x converts its operand to the named type.  The source position spans the parentheses and
+// the operand.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CastExpr {
+    pub(crate) span: Span,
+    pub(crate) cast_type: Box<AstTypes>,
+    pub(crate) expression: Box<ExprType>,
+}
+
+impl Checking for CastExpr {
+    fn visit_for_semantics_checking(&self) {
+        println!("Visiting CastExpr node.");
+    }
+}
+
+impl fmt::Display for CastExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{ span: {:?}, t: {:?}, e: {:?} }}",
+            self.span, self.cast_type, self.expression
+        )
+    }
+}
+
+impl PrintAST for CastExpr {
+    fn visit_for_printing(&self, depth: i32) {
+        let tabbed_string = generate_tabbed_string(std::any::type_name::<Self>(), depth);
+        println!("{}", tabbed_string);
+        self.cast_type.visit_for_printing(depth + 1);
+        self.expression.visit_for_printing(depth + 1);
+    }
+}
+
+impl PrintUnparsedAST for CastExpr {
+    fn unparse_to_code(&self, depth: i32) {
+        print!("(");
+        self.cast_type.unparse_to_code(depth);
+        print!(")");
+        self.expression.unparse_to_code(depth);
+    }
+}
+
+impl CastExpr {
+    pub fn new(span: Span, cast_type: Box<AstTypes>, expression: Box<ExprType>) -> Self {
+        Self {
+            span,
+            cast_type,
+            expression,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CharExpr {
+    pub(crate) span: Span,
+    pub(crate) char_literal: CharLiteral,
+}
+
+impl Checking for CharExpr {
+    fn visit_for_semantics_checking(&self) {
+        println!("Visiting CharExpr node.");
+    }
+}
+
+impl fmt::Display for CharExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{ span: {:?}, cl: {:?} }}",
+            self.span, self.char_literal
+        )
+    }
+}
+
+impl PrintAST for CharExpr {
+    fn visit_for_printing(&self, depth: i32) {
+        let tabbed_string = generate_tabbed_string(std::any::type_name::<Self>(), depth);
+        println!("{}", tabbed_string);
+        self.char_literal.visit_for_printing(depth + 1);
+    }
+}
+
+impl PrintUnparsedAST for CharExpr {
+    fn unparse_to_code(&self, depth: i32) {
+        self.char_literal.unparse_to_code(depth);
+    }
+}
+
+impl CharExpr {
+    pub fn new(span: Span, char_literal: CharLiteral) -> Self {
+        Self {
+            span,
+            char_literal,
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct BooleanExpr {
-    source_position: SourcePosition,
-    boolean_literal: BooleanLiteral, // Assuming FloatLiteral struct is defined
+    pub(crate) span: Span,
+    pub(crate) boolean_literal: BooleanLiteral, // Assuming FloatLiteral struct is defined
 }
 
 impl Checking for BooleanExpr {
@@ -374,8 +672,8 @@ impl fmt::Display for BooleanExpr {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{{ source_position: {:?}, fl: {:?} }}",
-            self.source_position, self.boolean_literal
+            "{{ span: {:?}, fl: {:?} }}",
+            self.span, self.boolean_literal
         )
     }
 }
@@ -395,9 +693,9 @@ impl PrintUnparsedAST for BooleanExpr {
 }
 
 impl BooleanExpr {
-    pub fn new(source_position: SourcePosition, int_literal: BooleanLiteral) -> Self {
+    pub fn new(span: Span, int_literal: BooleanLiteral) -> Self {
         Self {
-            source_position,
+            span,
             boolean_literal: int_literal,
         }
     }
@@ -405,9 +703,9 @@ impl BooleanExpr {
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct CallExpr {
-    source_position: SourcePosition,
-    ident: Ident,
-    argument_list: Box<ListType>,
+    pub(crate) span: Span,
+    pub(crate) ident: Ident,
+    pub(crate) argument_list: Box<ListType>,
 }
 
 impl Checking for CallExpr {
@@ -421,8 +719,8 @@ impl std::fmt::Display for CallExpr {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{{ source_position: {:?}, ident: {:?}, argument_list: {:?} }}",
-            self.source_position, self.ident, self.argument_list
+            "{{ span: {:?}, ident: {:?}, argument_list: {:?} }}",
+            self.span, self.ident, self.argument_list
         )
     }
 }
@@ -446,12 +744,12 @@ impl PrintUnparsedAST for CallExpr {
 
 impl CallExpr {
     pub fn new(
-        source_position: SourcePosition,
+        span: Span,
         ident: Ident,
         argument_list: Box<ListType>,
     ) -> Self {
         Self {
-            source_position,
+            span,
             ident,
             argument_list,
         }
@@ -460,7 +758,7 @@ impl CallExpr {
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct EmptyExpr {
-    source_position: SourcePosition,
+    pub(crate) span: Span,
 }
 
 impl Checking for EmptyExpr {
@@ -472,7 +770,7 @@ impl Checking for EmptyExpr {
 
 impl fmt::Display for EmptyExpr {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{{ source_position: {:?} }}", self.source_position)
+        write!(f, "{{ span: {:?} }}", self.span)
     }
 }
 
@@ -488,15 +786,15 @@ impl PrintUnparsedAST for EmptyExpr {
 }
 
 impl EmptyExpr {
-    pub fn new(source_position: SourcePosition) -> Self {
-        Self { source_position }
+    pub fn new(span: Span) -> Self {
+        Self { span }
     }
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct FloatExpr {
-    source_position: SourcePosition,
-    float_literal: FloatLiteral, // Assuming FloatLiteral struct is defined
+    pub(crate) span: Span,
+    pub(crate) float_literal: FloatLiteral, // Assuming FloatLiteral struct is defined
 }
 
 impl Checking for FloatExpr {
@@ -510,8 +808,8 @@ impl fmt::Display for FloatExpr {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{{ source_position: {:?}, fl: {:?} }}",
-            self.source_position, self.float_literal
+            "{{ span: {:?}, fl: {:?} }}",
+            self.span, self.float_literal
         )
     }
 }
@@ -531,9 +829,9 @@ impl PrintUnparsedAST for FloatExpr {
 }
 
 impl FloatExpr {
-    pub fn new(source_position: SourcePosition, float_literal: FloatLiteral) -> Self {
+    pub fn new(span: Span, float_literal: FloatLiteral) -> Self {
         Self {
-            source_position,
+            span,
             float_literal,
         }
     }
@@ -541,8 +839,8 @@ impl FloatExpr {
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct IntExpr {
-    source_position: SourcePosition,
-    int_literal: IntLiteral, // Assuming FloatLiteral struct is defined
+    pub(crate) span: Span,
+    pub(crate) int_literal: IntLiteral, // Assuming FloatLiteral struct is defined
 }
 
 impl Checking for IntExpr {
@@ -556,8 +854,8 @@ impl fmt::Display for IntExpr {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{{ source_position: {:?}, fl: {:?} }}",
-            self.source_position, self.int_literal
+            "{{ span: {:?}, fl: {:?} }}",
+            self.span, self.int_literal
         )
     }
 }
@@ -577,18 +875,68 @@ impl PrintUnparsedAST for IntExpr {
 }
 
 impl IntExpr {
-    pub fn new(source_position: SourcePosition, int_literal: IntLiteral) -> Self {
+    pub fn new(span: Span, int_literal: IntLiteral) -> Self {
         Self {
-            source_position,
+            span,
             int_literal,
         }
     }
 }
 
+// A parenthesised expression, kept so the AST records the grouping written in the source.
+// The source position spans the parentheses themselves.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParenExpr {
+    pub(crate) span: Span,
+    pub(crate) expr: Box<ExprType>,
+}
+
+impl Checking for ParenExpr {
+    fn visit_for_semantics_checking(&self) {
+        println!("Visiting ParenExpr node.");
+        todo!("Implement visitParenExpr function in checker.rs")
+    }
+}
+
+impl fmt::Display for ParenExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{ span: {:?}, e: {:?} }}",
+            self.span, self.expr
+        )
+    }
+}
+
+impl PrintAST for ParenExpr {
+    fn visit_for_printing(&self, depth: i32) {
+        let tabbed_string = generate_tabbed_string(std::any::type_name::<Self>(), depth);
+        println!("{}", tabbed_string);
+        self.expr.visit_for_printing(depth + 1);
+    }
+}
+
+impl PrintUnparsedAST for ParenExpr {
+    fn unparse_to_code(&self, depth: i32) {
+        print!("(");
+        self.expr.unparse_to_code(depth);
+        print!(")");
+    }
+}
+
+impl ParenExpr {
+    pub fn new(span: Span, expr: Box<ExprType>) -> Self {
+        Self {
+            span,
+            expr,
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct StringExpr {
-    source_position: SourcePosition,
-    string_literal: Box<StringLiteral>, // Assuming FloatLiteral struct is defined
+    pub(crate) span: Span,
+    pub(crate) string_literal: Box<StringLiteral>, // Assuming FloatLiteral struct is defined
 }
 
 impl Checking for StringExpr {
@@ -602,8 +950,8 @@ impl fmt::Display for StringExpr {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{{ source_position: {:?}, fl: {:?} }}",
-            self.source_position, self.string_literal
+            "{{ span: {:?}, fl: {:?} }}",
+            self.span, self.string_literal
         )
     }
 }
@@ -623,9 +971,9 @@ impl PrintUnparsedAST for StringExpr {
 }
 
 impl StringExpr {
-    pub fn new(source_position: SourcePosition, string_literal: Box<StringLiteral>) -> Self {
+    pub fn new(span: Span, string_literal: Box<StringLiteral>) -> Self {
         Self {
-            source_position,
+            span,
             string_literal,
         }
     }
@@ -633,9 +981,9 @@ impl StringExpr {
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct UnaryExpr {
-    pub(crate) source_position: SourcePosition,
-    operator: Operator,
-    expression: Box<ExprType>,
+    pub(crate) span: Span,
+    pub(crate) operator: Operator,
+    pub(crate) expression: Box<ExprType>,
 }
 
 impl Checking for UnaryExpr {
@@ -649,8 +997,8 @@ impl std::fmt::Display for UnaryExpr {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{{ source_position: {:?}, o: {:?}, e: {:?} }}",
-            self.source_position, self.operator, self.expression
+            "{{ span: {:?}, o: {:?}, e: {:?} }}",
+            self.span, self.operator, self.expression
         )
     }
 }
@@ -667,24 +1015,81 @@ impl PrintAST for UnaryExpr {
 impl PrintUnparsedAST for UnaryExpr {
     fn unparse_to_code(&self, depth: i32) {
         self.operator.unparse_to_code(depth);
+        // Keeps - -x from unparsing as --x, and + ++x as +++x.
+        if let ExprType::UnaryExpr(operand) = &*self.expression {
+            let sign = &self.operator.spelling[..1];
+            if "+-".contains(sign) && operand.operator.spelling.starts_with(sign) {
+                print!(" ");
+            }
+        }
         self.expression.unparse_to_code(depth);
     }
 }
 
 impl UnaryExpr {
-    pub fn new(source_position: SourcePosition, operator: Operator, expr: Box<ExprType>) -> Self {
+    pub fn new(span: Span, operator: Operator, expr: Box<ExprType>) -> Self {
         Self {
-            source_position,
+            span,
             operator,
             expression: expr,
         }
     }
 }
 
+// x++ or x--.  The prefix forms, ++x and --x, are UnaryExprs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PostfixExpr {
+    pub(crate) span: Span,
+    pub(crate) expression: Box<ExprType>,
+    pub(crate) operator: Operator,
+}
+
+impl Checking for PostfixExpr {
+    fn visit_for_semantics_checking(&self) {
+        println!("Visiting PostfixExpr node.");
+    }
+}
+
+impl std::fmt::Display for PostfixExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{{ span: {:?}, e: {:?}, o: {:?} }}",
+            self.span, self.expression, self.operator
+        )
+    }
+}
+
+impl PrintAST for PostfixExpr {
+    fn visit_for_printing(&self, depth: i32) {
+        let tabbed_string = generate_tabbed_string(std::any::type_name::<Self>(), depth);
+        println!("{}", tabbed_string);
+        self.expression.visit_for_printing(depth + 1);
+        self.operator.visit_for_printing(depth + 1);
+    }
+}
+
+impl PrintUnparsedAST for PostfixExpr {
+    fn unparse_to_code(&self, depth: i32) {
+        self.expression.unparse_to_code(depth);
+        self.operator.unparse_to_code(depth);
+    }
+}
+
+impl PostfixExpr {
+    pub fn new(span: Span, expression: Box<ExprType>, operator: Operator) -> Self {
+        Self {
+            span,
+            expression,
+            operator,
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct VarExpr {
-    source_position: SourcePosition,
-    var: VarUntyped,
+    pub(crate) span: Span,
+    pub(crate) var: VarUntyped,
 }
 
 impl Checking for VarExpr {
@@ -698,8 +1103,8 @@ impl fmt::Display for VarExpr {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{{ source_position: {:?}, v: {:?} }}",
-            self.source_position, self.var
+            "{{ span: {:?}, v: {:?} }}",
+            self.span, self.var
         )
     }
 }
@@ -719,9 +1124,9 @@ impl PrintUnparsedAST for VarExpr {
 }
 
 impl VarExpr {
-    pub fn new(source_position: SourcePosition, var: VarUntyped) -> Self {
+    pub fn new(span: Span, var: VarUntyped) -> Self {
         Self {
-            source_position,
+            span,
             var,
         }
     }
@@ -5,12 +5,13 @@ use std::io::{BufWriter, Write};
 use crate::ast::{Checking, PrintAST, PrintUnparsedAST};
 use crate::ast::decl::DeclType;
 use crate::globals::TAB_SIZE;
-use crate::utils::{generate_tabbed_string, SourcePosition};
+use crate::source_map::Span;
+use crate::utils::generate_tabbed_string;
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct Ident {
     pub spelling: String,
-    pub source_position: SourcePosition,
+    pub span: Span,
     pub decl: Option<DeclType>,
 }
 
@@ -41,10 +42,10 @@ impl PrintUnparsedAST for Ident {
 }
 
 impl Ident {
-    pub fn new(spelling: String, source_position: SourcePosition, decl: Option<DeclType>) -> Self {
+    pub fn new(spelling: String, span: Span, decl: Option<DeclType>) -> Self {
         Self {
             spelling,
-            source_position,
+            span,
             decl,
         }
     }
@@ -52,7 +53,7 @@ impl Ident {
     pub fn copy_with_null_decl(&self) -> Self {
         Self {
             spelling: self.spelling.clone(),
-            source_position: self.source_position,
+            span: self.span,
             decl: None,
         }
     }
@@ -6,17 +6,20 @@ use std::io::{BufWriter, Write};
 use crate::ast::{Checking, PrintAST, PrintUnparsedAST};
 use crate::ast::decl::{DeclType, ParaDecl};
 use crate::ast::expression::{Arg, ExprType};
-use crate::ast::statement::StmtType;
+use crate::ast::statement::{CaseStmt, StmtType};
 use crate::globals::TAB_SIZE;
-use crate::utils::{generate_tabbed_string, SourcePosition};
+use crate::source_map::Span;
+use crate::utils::generate_tabbed_string;
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum ListType {
     ArgList(ArgList),
     ArrayExprList(ArrayExprList),
+    CaseList(CaseList),
     DeclList(DeclList),
     EmptyArgList(EmptyArgList),
     EmptyArrayExprList(EmptyArrayExprList),
+    EmptyCaseList(EmptyCaseList),
     EmptyDeclList(EmptyDeclList),
     EmptyParamList(EmptyParamList),
     EmptyStmtList(EmptyStmtList),
@@ -29,6 +32,8 @@ impl PrintAST for ListType {
         match self {
             ListType::ArgList(arg_list) => arg_list.visit_for_printing(depth),
             ListType::ArrayExprList(array_expr_list) => array_expr_list.visit_for_printing(depth),
+            ListType::CaseList(case_list) => case_list.visit_for_printing(depth),
+            ListType::EmptyCaseList(empty_case_list) => empty_case_list.visit_for_printing(depth),
             ListType::DeclList(decl_list) => decl_list.visit_for_printing(depth),
             ListType::EmptyArrayExprList(empty_array_expr_list) => empty_array_expr_list.visit_for_printing(depth),
             ListType::EmptyArgList(empty_arg_list) => empty_arg_list.visit_for_printing(depth),
@@ -50,6 +55,8 @@ impl PrintUnparsedAST for ListType {
             ListType::EmptyDeclList(empty_decl_list) => empty_decl_list.unparse_to_code(depth),
             ListType::EmptyArrayExprList(empty_array_expr_list) => empty_array_expr_list.unparse_to_code(depth),
             ListType::ArrayExprList(array_expr_list) => array_expr_list.unparse_to_code(depth),
+            ListType::CaseList(case_list) => case_list.unparse_to_code(depth),
+            ListType::EmptyCaseList(empty_case_list) => empty_case_list.unparse_to_code(depth),
             ListType::EmptyStmtList(empty_stmt_list) => empty_stmt_list.unparse_to_code(depth),
             ListType::StmtList(stmt_list) => stmt_list.unparse_to_code(depth),
             ListType::ParamList(param_list) => param_list.unparse_to_code(depth),
@@ -60,9 +67,9 @@ impl PrintUnparsedAST for ListType {
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct ArrayExprList {
-    source_position: SourcePosition,
-    expression: ExprType,
-    expr_list: Box<ListType>,
+    pub(crate) span: Span,
+    pub(crate) expression: ExprType,
+    pub(crate) expr_list: Box<ListType>,
 }
 
 impl Checking for ArrayExprList {
@@ -92,7 +99,7 @@ impl PrintUnparsedAST for ArrayExprList {
     fn unparse_to_code(&self, depth: i32) {
         self.expression.unparse_to_code(depth);
         if !matches!(*self.expr_list, ListType::EmptyArrayExprList(_)) {
-            println!(", ");
+            print!(", ");
         }
 
         self.expr_list.unparse_to_code(depth);
@@ -101,12 +108,12 @@ impl PrintUnparsedAST for ArrayExprList {
 
 impl ArrayExprList {
     pub fn new(
-        source_position: SourcePosition,
+        span: Span,
         expression: ExprType,
         expr_list: Box<ListType>,
     ) -> Self {
         Self {
-            source_position,
+            span,
             expression,
             expr_list,
         }
@@ -115,9 +122,9 @@ impl ArrayExprList {
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct ArgList {
-    source_position: SourcePosition,
-    arg: ExprType,
-    arg_list: Box<ListType>,
+    pub(crate) span: Span,
+    pub(crate) arg: ExprType,
+    pub(crate) arg_list: Box<ListType>,
 }
 
 impl Checking for ArgList {
@@ -148,7 +155,7 @@ impl PrintUnparsedAST for ArgList {
         // Print nothing for the program stage.
         self.arg.unparse_to_code(depth + 1);
         if !matches!(*self.arg_list, ListType::EmptyArgList(_)) {
-            println!(", ");
+            print!(", ");
         }
 
         self.arg_list.unparse_to_code(depth + 1);
@@ -156,20 +163,101 @@ impl PrintUnparsedAST for ArgList {
 }
 
 impl ArgList {
-    pub fn new(source_position: SourcePosition, arg: ExprType, arg_list: Box<ListType>) -> Self {
+    pub fn new(span: Span, arg: ExprType, arg_list: Box<ListType>) -> Self {
         Self {
-            source_position,
+            span,
             arg,
             arg_list,
         }
     }
 }
 
+#[derive(Clone, Debug, PartialEq)]
+pub struct CaseList {
+    pub(crate) span: Span,
+    pub(crate) case_stmt: CaseStmt,
+    pub(crate) case_list: Box<ListType>,
+}
+
+impl Checking for CaseList {
+    fn visit_for_semantics_checking(&self) {
+        println!("Visiting CaseList node.");
+    }
+}
+
+impl fmt::Display for CaseList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CaseList")
+    }
+}
+
+impl PrintAST for CaseList {
+    fn visit_for_printing(&self, depth: i32) {
+        let tabbed_string = generate_tabbed_string(
+            std::any::type_name::<Self>(), depth);
+        println!("{}", tabbed_string);
+        self.case_stmt.visit_for_printing(depth + 1);
+        self.case_list.visit_for_printing(depth + 1);
+    }
+}
+
+impl PrintUnparsedAST for CaseList {
+    fn unparse_to_code(&self, depth: i32) {
+        self.case_stmt.unparse_to_code(depth);
+        self.case_list.unparse_to_code(depth);
+    }
+}
+
+impl CaseList {
+    pub fn new(span: Span, case_stmt: CaseStmt, case_list: Box<ListType>) -> Self {
+        Self {
+            span,
+            case_stmt,
+            case_list,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct EmptyCaseList {
+    pub(crate) span: Span,
+}
+
+impl Checking for EmptyCaseList {
+    fn visit_for_semantics_checking(&self) {
+        println!("Visiting EmptyCaseList node.");
+    }
+}
+
+impl fmt::Display for EmptyCaseList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "EmptyCaseList")
+    }
+}
+
+impl PrintAST for EmptyCaseList {
+    fn visit_for_printing(&self, depth: i32) {
+        let tabbed_string = generate_tabbed_string(
+            std::any::type_name::<Self>(), depth);
+        println!("{}", tabbed_string);
+    }
+}
+
+impl PrintUnparsedAST for EmptyCaseList {
+    fn unparse_to_code(&self, depth: i32) {}
+}
+
+impl EmptyCaseList {
+    pub fn new(span: Span) -> Self {
+        Self { span }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct DeclList {
-    source_position: SourcePosition,
-    decl_type: Box<DeclType>,
-    decl_list: Box<ListType>,
+    pub(crate) span: Span,
+    pub(crate) decl_type: Box<DeclType>,
+    pub(crate) decl_list: Box<ListType>,
 }
 
 impl PrintAST for DeclList {
@@ -200,16 +288,16 @@ impl fmt::Display for DeclList {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{{source_position: {:?}, d: {:?}, dl: {:?} }}",
-            self.source_position, self.decl_type, self.decl_list
+            "{{span: {:?}, d: {:?}, dl: {:?} }}",
+            self.span, self.decl_type, self.decl_list
         )
     }
 }
 
 impl DeclList {
-    pub fn new(source_position: SourcePosition, decl_type: Box<DeclType>, decl_list: Box<ListType>) -> Self {
+    pub fn new(span: Span, decl_type: Box<DeclType>, decl_list: Box<ListType>) -> Self {
         Self {
-            source_position,
+            span,
             decl_type,
             decl_list,
         }
@@ -218,7 +306,7 @@ impl DeclList {
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct EmptyArgList {
-    source_position: SourcePosition,
+    pub(crate) span: Span,
 }
 
 impl Checking for EmptyArgList {
@@ -249,14 +337,14 @@ impl PrintUnparsedAST for EmptyArgList {
 }
 
 impl EmptyArgList {
-    pub fn new(source_position: SourcePosition) -> Self {
-        Self { source_position }
+    pub fn new(span: Span) -> Self {
+        Self { span }
     }
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct EmptyDeclList {
-    source_position: SourcePosition,
+    pub(crate) span: Span,
 }
 
 impl Checking for EmptyDeclList {
@@ -285,14 +373,14 @@ impl PrintUnparsedAST for EmptyDeclList {
 }
 
 impl EmptyDeclList {
-    pub fn new(source_position: SourcePosition) -> Self {
-        Self { source_position }
+    pub fn new(span: Span) -> Self {
+        Self { span }
     }
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct EmptyArrayExprList {
-    pub source_position: SourcePosition,
+    pub span: Span,
 }
 
 impl Checking for EmptyArrayExprList {
@@ -303,7 +391,7 @@ impl Checking for EmptyArrayExprList {
 
 impl std::fmt::Display for EmptyArrayExprList {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{{ source_position: {:?} }}", self.source_position)
+        write!(f, "{{ span: {:?} }}", self.span)
     }
 }
 
@@ -321,16 +409,16 @@ impl PrintUnparsedAST for EmptyArrayExprList {
 }
 
 impl EmptyArrayExprList {
-    pub fn new(source_position: SourcePosition) -> Self {
-        Self { source_position }
+    pub fn new(span: Span) -> Self {
+        Self { span }
     }
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct StmtList {
-    source_position: SourcePosition,
-    stmt: Box<StmtType>,
-    stmt_list: Box<ListType>,
+    pub(crate) span: Span,
+    pub(crate) stmt: Box<StmtType>,
+    pub(crate) stmt_list: Box<ListType>,
 }
 
 impl Checking for StmtList {
@@ -365,12 +453,12 @@ impl PrintUnparsedAST for StmtList {
 
 impl StmtList {
     pub fn new(
-        source_position: SourcePosition,
+        span: Span,
         stmt: Box<StmtType>,
         stmt_list: Box<ListType>,
     ) -> Self {
         Self {
-            source_position,
+            span,
             stmt,
             stmt_list,
         }
@@ -379,7 +467,7 @@ impl StmtList {
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct EmptyStmtList {
-    source_position: SourcePosition,
+    pub(crate) span: Span,
 }
 
 impl PrintAST for EmptyStmtList {
@@ -408,16 +496,16 @@ impl PrintUnparsedAST for EmptyStmtList {
 }
 
 impl EmptyStmtList {
-    pub fn new(source_position: SourcePosition) -> Self {
-        Self { source_position }
+    pub fn new(span: Span) -> Self {
+        Self { span }
     }
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct ParamList {
-    source_position: SourcePosition,
-    param: ParaDecl,
-    param_list: Box<ListType>,
+    pub(crate) span: Span,
+    pub(crate) param: ParaDecl,
+    pub(crate) param_list: Box<ListType>,
 }
 
 impl Checking for ParamList {
@@ -447,7 +535,7 @@ impl PrintUnparsedAST for ParamList {
     fn unparse_to_code(&self, depth: i32) {
         self.param.unparse_to_code(depth);
         if !matches!(*self.param_list, ListType::EmptyParamList(_)) {
-            println!(", ");
+            print!(", ");
         }
         self.param_list.unparse_to_code(depth);
     }
@@ -455,12 +543,12 @@ impl PrintUnparsedAST for ParamList {
 
 impl ParamList {
     pub fn new(
-        source_position: SourcePosition,
+        span: Span,
         param: ParaDecl,
         param_list: Box<ListType>,
     ) -> Self {
         Self {
-            source_position,
+            span,
             param,
             param_list,
         }
@@ -469,7 +557,7 @@ impl ParamList {
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct EmptyParamList {
-    source_position: SourcePosition,
+    pub(crate) span: Span,
 }
 
 impl Checking for EmptyParamList {
@@ -495,12 +583,12 @@ impl PrintAST for EmptyParamList {
 
 impl PrintUnparsedAST for EmptyParamList {
     fn unparse_to_code(&self, depth: i32) {
-        println!(")");
+        print!(")");
     }
 }
 
 impl EmptyParamList {
-    pub fn new(source_position: SourcePosition) -> Self {
-        Self { source_position }
+    pub fn new(span: Span) -> Self {
+        Self { span }
     }
 }
@@ -4,11 +4,15 @@ use std::io::{BufWriter, Write};
 
 use crate::ast::{Checking, PrintAST, PrintUnparsedAST};
 use crate::globals::TAB_SIZE;
-use crate::utils::{generate_indent, generate_tabbed_string, SourcePosition};
+use crate::source_map::Span;
+use crate::utils::escapes::decode_escapes;
+use crate::utils::{
+    float_literal_value, generate_indent, generate_tabbed_string, int_literal_value,
+};
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct Operator {
-    pub source_position: SourcePosition,
+    pub span: Span,
     pub spelling: String,
 }
 
@@ -23,8 +27,8 @@ impl fmt::Display for Operator {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{{ source_position: {:?}, il: {:?} }}",
-            self.source_position, self.spelling
+            "{{ span: {:?}, il: {:?} }}",
+            self.span, self.spelling
         )
     }
 }
@@ -43,9 +47,9 @@ impl PrintUnparsedAST for Operator {
 }
 
 impl Operator {
-    pub fn new(source_position: SourcePosition, spelling: String) -> Self {
+    pub fn new(span: Span, spelling: String) -> Self {
         Self {
-            source_position,
+            span,
             spelling,
         }
     }
@@ -53,7 +57,7 @@ impl Operator {
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct Terminal {
-    pub source_position: SourcePosition,
+    pub span: Span,
     pub spelling: String,
 }
 
@@ -68,8 +72,8 @@ impl fmt::Display for Terminal {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{{ source_position: {:?}, il: {:?} }}",
-            self.source_position, self.spelling
+            "{{ span: {:?}, il: {:?} }}",
+            self.span, self.spelling
         )
     }
 }
@@ -88,9 +92,9 @@ impl PrintUnparsedAST for Terminal {
 }
 
 impl Terminal {
-    pub fn new(source_position: SourcePosition, spelling: String) -> Self {
+    pub fn new(span: Span, spelling: String) -> Self {
         Self {
-            source_position,
+            span,
             spelling,
         }
     }
@@ -98,8 +102,10 @@ impl Terminal {
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct IntLiteral {
-    pub source_position: SourcePosition,
+    pub span: Span,
     pub spelling: String,
+    // The value written, whatever its radix.  None if it does not fit in 64 bits.
+    pub value: Option<u64>,
 }
 
 impl Checking for IntLiteral {
@@ -113,8 +119,8 @@ impl fmt::Display for IntLiteral {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{{ source_position: {:?}, il: {:?} }}",
-            self.source_position, self.spelling
+            "{{ span: {:?}, il: {:?} }}",
+            self.span, self.spelling
         )
     }
 }
@@ -133,18 +139,21 @@ impl PrintUnparsedAST for IntLiteral {
 }
 
 impl IntLiteral {
-    pub fn new(source_position: SourcePosition, spelling: String) -> Self {
+    pub fn new(span: Span, spelling: String) -> Self {
+        let value = int_literal_value(&spelling);
         Self {
-            source_position,
+            span,
             spelling,
+            value,
         }
     }
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct FloatLiteral {
-    pub source_position: SourcePosition,
+    pub span: Span,
     pub spelling: String,
+    pub value: Option<f64>,
 }
 
 impl Checking for FloatLiteral {
@@ -158,8 +167,8 @@ impl fmt::Display for FloatLiteral {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{{ source_position: {:?}, il: {:?} }}",
-            self.source_position, self.spelling
+            "{{ span: {:?}, il: {:?} }}",
+            self.span, self.spelling
         )
     }
 }
@@ -178,17 +187,19 @@ impl PrintUnparsedAST for FloatLiteral {
 }
 
 impl FloatLiteral {
-    pub fn new(source_position: SourcePosition, spelling: String) -> Self {
+    pub fn new(span: Span, spelling: String) -> Self {
+        let value = float_literal_value(&spelling);
         Self {
-            source_position,
+            span,
             spelling,
+            value,
         }
     }
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct BooleanLiteral {
-    pub source_position: SourcePosition,
+    pub span: Span,
     pub spelling: String,
 }
 
@@ -203,8 +214,8 @@ impl fmt::Display for BooleanLiteral {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{{ source_position: {:?}, il: {:?} }}",
-            self.source_position, self.spelling
+            "{{ span: {:?}, il: {:?} }}",
+            self.span, self.spelling
         )
     }
 }
@@ -223,18 +234,74 @@ impl PrintUnparsedAST for BooleanLiteral {
 }
 
 impl BooleanLiteral {
-    pub fn new(source_position: SourcePosition, spelling: String) -> Self {
+    pub fn new(span: Span, spelling: String) -> Self {
         Self {
-            source_position,
+            span,
             spelling,
         }
     }
 }
 
+// The spelling is the character as written between the quotes, so '\n' is spelt \n.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CharLiteral {
+    pub span: Span,
+    pub spelling: String,
+}
+
+impl Checking for CharLiteral {
+    fn visit_for_semantics_checking(&self) {
+        println!("Visiting CharLiteral node.");
+    }
+}
+
+impl fmt::Display for CharLiteral {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{ span: {:?}, il: {:?} }}",
+            self.span, self.spelling
+        )
+    }
+}
+
+impl PrintAST for CharLiteral {
+    fn visit_for_printing(&self, depth: i32) {
+        let tabbed_string = generate_tabbed_string(std::any::type_name::<Self>(), depth);
+        println!("{} ({})", tabbed_string, self.spelling);
+    }
+}
+
+impl PrintUnparsedAST for CharLiteral {
+    fn unparse_to_code(&self, depth: i32) {
+        print!("'{}'", self.spelling);
+    }
+}
+
+impl CharLiteral {
+    pub fn new(span: Span, spelling: String) -> Self {
+        Self {
+            span,
+            spelling,
+        }
+    }
+
+    // The character the literal stands for, if the scanner accepted it.
+    pub fn value(&self) -> Option<char> {
+        let value = decode_escapes(&self.spelling);
+        let mut chars = value.chars();
+        let c = chars.next()?;
+        chars.next().is_none().then_some(c)
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct StringLiteral {
-    pub source_position: SourcePosition,
+    pub span: Span,
+    // As written between the quotes, escapes and all, which is what the unparser prints.
     pub spelling: String,
+    // The characters the literal stands for, with its escapes decoded.
+    pub value: String,
 }
 
 impl Checking for StringLiteral {
@@ -248,8 +315,8 @@ impl fmt::Display for StringLiteral {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{{ source_position: {:?}, il: {:?} }}",
-            self.source_position, self.spelling
+            "{{ span: {:?}, il: {:?} }}",
+            self.span, self.spelling
         )
     }
 }
@@ -269,10 +336,12 @@ impl PrintUnparsedAST for StringLiteral {
 }
 
 impl StringLiteral {
-    pub fn new(source_position: SourcePosition, spelling: String) -> Self {
+    pub fn new(span: Span, spelling: String) -> Self {
+        let value = decode_escapes(&spelling);
         Self {
-            source_position,
+            span,
             spelling,
+            value,
         }
     }
 }
@@ -5,15 +5,19 @@ use std::sync::Arc;
 
 use crate::ast::{Checking, PrintAST, PrintUnparsedAST};
 use crate::ast::expression::ExprType;
+use crate::ast::ident::Ident;
 use crate::globals::TAB_SIZE;
-use crate::utils::{generate_indent, generate_tabbed_string, SourcePosition};
+use crate::source_map::Span;
+use crate::utils::{generate_indent, generate_tabbed_string};
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum AstTypes {
     BooleanType(BooleanType),
+    CharType(CharType),
     FloatType(FloatType),
     IntType(IntType),
     StringType(StringType),
+    StructType(StructType),
     VoidType(VoidType),
     ErrorType(ErrorType),
 }
@@ -22,9 +26,11 @@ impl PrintAST for AstTypes {
     fn visit_for_printing(&self, depth: i32) {
         match self {
             AstTypes::BooleanType(boolean_type) => boolean_type.visit_for_printing(depth),
+            AstTypes::CharType(char_type) => char_type.visit_for_printing(depth),
             AstTypes::FloatType(float_type) => float_type.visit_for_printing(depth),
             AstTypes::IntType(int_type) => int_type.visit_for_printing(depth),
             AstTypes::StringType(string_type) => string_type.visit_for_printing(depth),
+            AstTypes::StructType(struct_type) => struct_type.visit_for_printing(depth),
             AstTypes::VoidType(void_type) => void_type.visit_for_printing(depth),
             AstTypes::ErrorType(error_type) => error_type.visit_for_printing(depth),
         }
@@ -35,9 +41,11 @@ impl PrintUnparsedAST for AstTypes {
     fn unparse_to_code(&self, depth: i32) {
         match self {
             AstTypes::BooleanType(boolean_type) => boolean_type.unparse_to_code(depth),
+            AstTypes::CharType(char_type) => char_type.unparse_to_code(depth),
             AstTypes::FloatType(float_type) => float_type.unparse_to_code(depth),
             AstTypes::IntType(int_type) => int_type.unparse_to_code(depth),
             AstTypes::StringType(string_type) => string_type.unparse_to_code(depth),
+            AstTypes::StructType(struct_type) => struct_type.unparse_to_code(depth),
             AstTypes::VoidType(void_type) => void_type.unparse_to_code(depth),
             AstTypes::ErrorType(error_type) => error_type.unparse_to_code(depth),
         }
@@ -48,6 +56,7 @@ impl AstTypes {
     fn is_primitive_type(&self) -> bool {
         match self {
             AstTypes::BooleanType(_) => true,
+            AstTypes::CharType(_) => true,
             AstTypes::FloatType(_) => true,
             AstTypes::IntType(_) => true,
             AstTypes::VoidType(_) => true,
@@ -58,7 +67,7 @@ impl AstTypes {
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct BooleanType {
-    source_position: SourcePosition,
+    pub(crate) span: Span,
 }
 
 impl Checking for BooleanType {
@@ -89,14 +98,52 @@ impl PrintUnparsedAST for BooleanType {
 }
 
 impl BooleanType {
-    pub fn new(source_position: SourcePosition) -> Self {
-        Self { source_position }
+    pub fn new(span: Span) -> Self {
+        Self { span }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CharType {
+    pub(crate) span: Span,
+}
+
+impl Checking for CharType {
+    fn visit_for_semantics_checking(&self) {
+        println!("Visiting CharType node.");
+        // Implement visitCharType function...
+    }
+}
+
+impl fmt::Display for CharType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CharType")
+    }
+}
+
+impl PrintAST for CharType {
+    fn visit_for_printing(&self, depth: i32) {
+        let tabbed_string = generate_tabbed_string(
+            std::any::type_name::<Self>(), depth);
+        println!("{}", tabbed_string);
+    }
+}
+
+impl PrintUnparsedAST for CharType {
+    fn unparse_to_code(&self, depth: i32) {
+        print!("char")
+    }
+}
+
+impl CharType {
+    pub fn new(span: Span) -> Self {
+        Self { span }
     }
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct ErrorType {
-    source_position: SourcePosition,
+    pub(crate) span: Span,
 }
 
 impl Checking for ErrorType {
@@ -127,14 +174,14 @@ impl PrintUnparsedAST for ErrorType {
 }
 
 impl ErrorType {
-    pub fn new(source_position: SourcePosition) -> Self {
-        Self { source_position }
+    pub fn new(span: Span) -> Self {
+        Self { span }
     }
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct FloatType {
-    source_position: SourcePosition,
+    pub(crate) span: Span,
 }
 
 impl Checking for FloatType {
@@ -165,14 +212,14 @@ impl PrintUnparsedAST for FloatType {
 }
 
 impl FloatType {
-    pub fn new(source_position: SourcePosition) -> Self {
-        Self { source_position }
+    pub fn new(span: Span) -> Self {
+        Self { span }
     }
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct IntType {
-    source_position: SourcePosition,
+    pub(crate) span: Span,
 }
 
 impl Checking for IntType {
@@ -203,14 +250,14 @@ impl PrintUnparsedAST for IntType {
 }
 
 impl IntType {
-    pub fn new(source_position: SourcePosition) -> Self {
-        Self { source_position }
+    pub fn new(span: Span) -> Self {
+        Self { span }
     }
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct StringType {
-    source_position: SourcePosition,
+    pub(crate) span: Span,
 }
 
 impl Checking for StringType {
@@ -241,14 +288,56 @@ impl PrintUnparsedAST for StringType {
 }
 
 impl StringType {
-    pub fn new(source_position: SourcePosition) -> Self {
-        Self { source_position }
+    pub fn new(span: Span) -> Self {
+        Self { span }
+    }
+}
+
+// struct Name, naming a type declared by a StructDecl.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StructType {
+    pub(crate) span: Span,
+    pub(crate) ident: Ident,
+}
+
+impl Checking for StructType {
+    fn visit_for_semantics_checking(&self) {
+        println!("Visiting StructType node.");
+        // Implement visitStructType function...
+    }
+}
+
+impl fmt::Display for StructType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "StructType({})", self.ident)
+    }
+}
+
+impl PrintAST for StructType {
+    fn visit_for_printing(&self, depth: i32) {
+        let tabbed_string = generate_tabbed_string(
+            std::any::type_name::<Self>(), depth);
+        println!("{}", tabbed_string);
+        self.ident.visit_for_printing(depth + 1);
+    }
+}
+
+impl PrintUnparsedAST for StructType {
+    fn unparse_to_code(&self, depth: i32) {
+        print!("struct ");
+        self.ident.unparse_to_code(depth);
+    }
+}
+
+impl StructType {
+    pub fn new(span: Span, ident: Ident) -> Self {
+        Self { span, ident }
     }
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct VoidType {
-    source_position: SourcePosition,
+    pub(crate) span: Span,
 }
 
 impl Checking for VoidType {
@@ -279,8 +368,8 @@ impl PrintUnparsedAST for VoidType {
 }
 
 impl VoidType {
-    pub fn new(source_position: SourcePosition) -> Self {
-        Self { source_position }
+    pub fn new(span: Span) -> Self {
+        Self { span }
     }
 }
 // Many Rust programmers use compile time macros to generate large blocks of code like this.
@@ -6,11 +6,12 @@ use std::io::BufWriter;
 use crate::ast::{Checking, PrintAST, PrintUnparsedAST};
 use crate::ast::list::DeclList;
 use crate::globals::TAB_SIZE;
-use crate::utils::{generate_tabbed_string, SourcePosition};
+use crate::source_map::Span;
+use crate::utils::generate_tabbed_string;
 
 #[derive(Debug)]
 pub struct Program {
-    declaration_list: DeclList,
+    pub(crate) declaration_list: DeclList,
 }
 
 impl Display for Program {
@@ -4,30 +4,51 @@ use std::io::Write;
 use crate::ast::expression::ExprType;
 use crate::ast::list::ListType;
 use crate::ast::{Checking, PrintAST, PrintUnparsedAST};
-use crate::utils::{
-    generate_indent, generate_tabbed_string, print_indent, print_newline_and_indent, SourcePosition,
-};
+use crate::source_map::Span;
+use crate::utils::{generate_indent, generate_tabbed_string, print_indent, print_newline_and_indent};
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum StmtType {
     BreakStmt(BreakStmt),
     CompoundStmt(CompoundStmt),
     ContinueStmt(ContinueStmt),
+    DoWhileStmt(DoWhileStmt),
     EmptyCompoundStmt(EmptyCompoundStmt),
     EmptyStmt(EmptyStmt),
     ExprStmt(ExprStmt),
     ForStmt(ForStmt),
     IfStmt(IfStmt),
     ReturnStmt(ReturnStmt),
+    SwitchStmt(SwitchStmt),
     WhileStmt(WhileStmt),
 }
 
+impl StmtType {
+    pub fn get_span(&self) -> &Span {
+        match self {
+            StmtType::BreakStmt(stmt) => &stmt.span,
+            StmtType::CompoundStmt(stmt) => &stmt.span,
+            StmtType::ContinueStmt(stmt) => &stmt.span,
+            StmtType::DoWhileStmt(stmt) => &stmt.span,
+            StmtType::EmptyCompoundStmt(stmt) => &stmt.span,
+            StmtType::EmptyStmt(stmt) => &stmt.span,
+            StmtType::ExprStmt(stmt) => &stmt.span,
+            StmtType::ForStmt(stmt) => &stmt.span,
+            StmtType::IfStmt(stmt) => &stmt.span,
+            StmtType::ReturnStmt(stmt) => &stmt.span,
+            StmtType::SwitchStmt(stmt) => &stmt.span,
+            StmtType::WhileStmt(stmt) => &stmt.span,
+        }
+    }
+}
+
 impl PrintAST for StmtType {
     fn visit_for_printing(&self, depth: i32) {
         match self {
             StmtType::BreakStmt(break_stmt) => break_stmt.visit_for_printing(depth),
             StmtType::CompoundStmt(compound_stmt) => compound_stmt.visit_for_printing(depth),
             StmtType::ContinueStmt(continue_stmt) => continue_stmt.visit_for_printing(depth),
+            StmtType::DoWhileStmt(do_while_stmt) => do_while_stmt.visit_for_printing(depth),
             StmtType::EmptyCompoundStmt(empty_compound_stmt) => {
                 empty_compound_stmt.visit_for_printing(depth)
             }
@@ -36,6 +57,7 @@ impl PrintAST for StmtType {
             StmtType::ForStmt(for_stmt) => for_stmt.visit_for_printing(depth),
             StmtType::IfStmt(if_stmt) => if_stmt.visit_for_printing(depth),
             StmtType::ReturnStmt(return_stmt) => return_stmt.visit_for_printing(depth),
+            StmtType::SwitchStmt(switch_stmt) => switch_stmt.visit_for_printing(depth),
             StmtType::WhileStmt(while_stmt) => while_stmt.visit_for_printing(depth),
         }
     }
@@ -47,6 +69,7 @@ impl PrintUnparsedAST for StmtType {
             StmtType::BreakStmt(break_stmt) => break_stmt.unparse_to_code(depth),
             StmtType::CompoundStmt(compound_stmt) => compound_stmt.unparse_to_code(depth),
             StmtType::ContinueStmt(continue_stmt) => continue_stmt.unparse_to_code(depth),
+            StmtType::DoWhileStmt(do_while_stmt) => do_while_stmt.unparse_to_code(depth),
             StmtType::EmptyCompoundStmt(empty_compound_stmt) => {
                 empty_compound_stmt.unparse_to_code(depth)
             }
@@ -55,6 +78,7 @@ impl PrintUnparsedAST for StmtType {
             StmtType::ForStmt(for_stmt) => for_stmt.unparse_to_code(depth),
             StmtType::IfStmt(if_stmt) => if_stmt.unparse_to_code(depth),
             StmtType::ReturnStmt(return_stmt) => return_stmt.unparse_to_code(depth),
+            StmtType::SwitchStmt(switch_stmt) => switch_stmt.unparse_to_code(depth),
             StmtType::WhileStmt(while_stmt) => while_stmt.unparse_to_code(depth),
         }
     }
@@ -62,7 +86,7 @@ impl PrintUnparsedAST for StmtType {
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct BreakStmt {
-    source_position: SourcePosition,
+    pub(crate) span: Span,
 }
 
 impl Checking for BreakStmt {
@@ -93,8 +117,8 @@ impl PrintUnparsedAST for BreakStmt {
 }
 
 impl BreakStmt {
-    pub fn new(source_position: SourcePosition) -> Self {
-        Self { source_position }
+    pub fn new(span: Span) -> Self {
+        Self { span }
     }
 }
 
@@ -102,7 +126,7 @@ impl BreakStmt {
 pub struct CompoundStmt {
     pub decl_list: Box<ListType>,
     pub stmt_list: Box<ListType>,
-    pub source_position: SourcePosition,
+    pub span: Span,
 }
 
 impl Checking for CompoundStmt {
@@ -130,8 +154,6 @@ impl PrintAST for CompoundStmt {
 impl PrintUnparsedAST for CompoundStmt {
     fn unparse_to_code(&self, depth: i32) {
         print!("{{");
-        print_indent(depth);
-        // Check depth + 1 here.  Might need to be depth.
 
         self.decl_list.unparse_to_code(depth + 1);
         self.stmt_list.unparse_to_code(depth + 1);
@@ -146,19 +168,19 @@ impl CompoundStmt {
     pub fn new(
         decl_list: Box<ListType>,
         stmt_list: Box<ListType>,
-        source_position: SourcePosition,
+        span: Span,
     ) -> Self {
         Self {
             decl_list,
             stmt_list,
-            source_position,
+            span,
         }
     }
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct ContinueStmt {
-    source_position: SourcePosition,
+    pub(crate) span: Span,
 }
 
 impl Checking for ContinueStmt {
@@ -187,14 +209,14 @@ impl PrintUnparsedAST for ContinueStmt {
 }
 
 impl ContinueStmt {
-    pub fn new(source_position: SourcePosition) -> Self {
-        Self { source_position }
+    pub fn new(span: Span) -> Self {
+        Self { span }
     }
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct EmptyStmt {
-    source_position: SourcePosition,
+    pub(crate) span: Span,
 }
 
 impl Checking for EmptyStmt {
@@ -224,15 +246,15 @@ impl PrintUnparsedAST for EmptyStmt {
 }
 
 impl EmptyStmt {
-    pub fn new(source_position: SourcePosition) -> Self {
-        Self { source_position }
+    pub fn new(span: Span) -> Self {
+        Self { span }
     }
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct ExprStmt {
-    source_position: SourcePosition,
-    expr: ExprType,
+    pub(crate) span: Span,
+    pub(crate) expr: ExprType,
 }
 
 impl Checking for ExprStmt {
@@ -265,9 +287,9 @@ impl PrintUnparsedAST for ExprStmt {
 }
 
 impl ExprStmt {
-    pub fn new(source_position: SourcePosition, e: ExprType) -> Self {
+    pub fn new(span: Span, e: ExprType) -> Self {
         Self {
-            source_position,
+            span,
             expr: e,
         }
     }
@@ -275,7 +297,7 @@ impl ExprStmt {
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct EmptyCompoundStmt {
-    source_position: SourcePosition,
+    pub(crate) span: Span,
 }
 
 impl Checking for EmptyCompoundStmt {
@@ -305,18 +327,18 @@ impl PrintUnparsedAST for EmptyCompoundStmt {
 }
 
 impl EmptyCompoundStmt {
-    pub fn new(source_position: SourcePosition) -> Self {
-        Self { source_position }
+    pub fn new(span: Span) -> Self {
+        Self { span }
     }
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct ForStmt {
-    source_position: SourcePosition,
-    expr_1: Box<ExprType>,
-    expr_2: Box<ExprType>,
-    expr_3: Box<ExprType>,
-    stmt: Box<StmtType>,
+    pub(crate) span: Span,
+    pub(crate) expr_1: Box<ExprType>,
+    pub(crate) expr_2: Box<ExprType>,
+    pub(crate) expr_3: Box<ExprType>,
+    pub(crate) stmt: Box<StmtType>,
 }
 
 impl Checking for ForStmt {
@@ -351,9 +373,12 @@ impl PrintUnparsedAST for ForStmt {
         self.expr_2.unparse_to_code(depth);
         print!("; ");
         self.expr_3.unparse_to_code(depth);
-        print!(") ");
+        print!(")");
         let extra_depth = match *self.stmt {
-            StmtType::CompoundStmt(_) => 0,
+            StmtType::CompoundStmt(_) => {
+                print!(" ");
+                0
+            }
             _ => 1,
         };
         self.stmt.unparse_to_code(depth + extra_depth);
@@ -363,14 +388,14 @@ impl PrintUnparsedAST for ForStmt {
 
 impl ForStmt {
     pub fn new(
-        source_position: SourcePosition,
+        span: Span,
         expr_1: Box<ExprType>,
         expr_2: Box<ExprType>,
         expr_3: Box<ExprType>,
         stmt: Box<StmtType>,
     ) -> Self {
         Self {
-            source_position,
+            span,
             expr_1,
             expr_2,
             expr_3,
@@ -381,10 +406,10 @@ impl ForStmt {
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct IfStmt {
-    source_position: SourcePosition,
-    expr: Box<ExprType>,
-    stmt_1: Box<StmtType>,
-    stmt_2: Box<StmtType>,
+    pub(crate) span: Span,
+    pub(crate) expr: Box<ExprType>,
+    pub(crate) stmt_1: Box<StmtType>,
+    pub(crate) stmt_2: Box<StmtType>,
 }
 
 impl Checking for IfStmt {
@@ -416,6 +441,7 @@ impl PrintUnparsedAST for IfStmt {
         self.expr.unparse_to_code(depth);
         print!(")");
         if let StmtType::CompoundStmt(_) = *self.stmt_1 {
+            print!(" ");
             self.stmt_1.unparse_to_code(depth);
         } else {
             self.stmt_1.unparse_to_code(depth + 1);
@@ -429,9 +455,12 @@ impl PrintUnparsedAST for IfStmt {
             }
             _ => {
                 print_newline_and_indent(depth);
-                print!("else ");
+                print!("else");
                 let extra_depth_s2 = match *self.stmt_2 {
-                    StmtType::CompoundStmt(_) => 0,
+                    StmtType::CompoundStmt(_) => {
+                        print!(" ");
+                        0
+                    }
                     _ => 1,
                 };
                 self.stmt_2.unparse_to_code(depth + extra_depth_s2);
@@ -443,13 +472,13 @@ impl PrintUnparsedAST for IfStmt {
 
 impl IfStmt {
     pub fn new(
-        source_position: SourcePosition,
+        span: Span,
         expr: Box<ExprType>,
         stmt_1: Box<StmtType>,
         stmt_2: Box<StmtType>,
     ) -> Self {
         Self {
-            source_position,
+            span,
             expr,
             stmt_1,
             stmt_2,
@@ -459,8 +488,8 @@ impl IfStmt {
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct ReturnStmt {
-    source_position: SourcePosition,
-    expr: Box<ExprType>,
+    pub(crate) span: Span,
+    pub(crate) expr: Box<ExprType>,
 }
 
 impl Checking for ReturnStmt {
@@ -488,15 +517,18 @@ impl PrintUnparsedAST for ReturnStmt {
     fn unparse_to_code(&self, depth: i32) {
         print_newline_and_indent(depth);
         print!("return");
+        if !matches!(*self.expr, ExprType::EmptyExpr(_)) {
+            print!(" ");
+        }
         self.expr.unparse_to_code(depth);
         print!(";");
     }
 }
 
 impl ReturnStmt {
-    pub fn new(source_position: SourcePosition, expr: Box<ExprType>) -> Self {
+    pub fn new(span: Span, expr: Box<ExprType>) -> Self {
         Self {
-            source_position,
+            span,
             expr,
         }
     }
@@ -504,9 +536,9 @@ impl ReturnStmt {
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct WhileStmt {
-    source_position: SourcePosition,
-    stmt: Box<StmtType>,
-    expr: Box<ExprType>,
+    pub(crate) span: Span,
+    pub(crate) stmt: Box<StmtType>,
+    pub(crate) expr: Box<ExprType>,
 }
 
 impl Checking for WhileStmt {
@@ -534,24 +566,187 @@ impl PrintUnparsedAST for WhileStmt {
     fn unparse_to_code(&self, depth: i32) {
         print_newline_and_indent(depth);
         print!("while (");
+        self.expr.unparse_to_code(depth);
+        print!(")");
 
         let extra_depth = match *self.stmt {
-            StmtType::CompoundStmt(_) => 0,
+            StmtType::CompoundStmt(_) => {
+                print!(" ");
+                0
+            }
             _ => 1,
         };
 
-        self.expr.unparse_to_code(depth);
-        println!(") ");
         self.stmt.unparse_to_code(depth + extra_depth);
     }
 }
 
 impl WhileStmt {
-    pub fn new(source_position: SourcePosition, expr: Box<ExprType>, stmt: Box<StmtType>) -> Self {
+    pub fn new(span: Span, expr: Box<ExprType>, stmt: Box<StmtType>) -> Self {
         Self {
-            source_position,
+            span,
             expr,
             stmt,
         }
     }
 }
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DoWhileStmt {
+    pub(crate) span: Span,
+    pub(crate) stmt: Box<StmtType>,
+    pub(crate) expr: Box<ExprType>,
+}
+
+impl Checking for DoWhileStmt {
+    fn visit_for_semantics_checking(&self) {
+        println!("Visiting DoWhileStmt node.");
+    }
+}
+
+impl fmt::Display for DoWhileStmt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "DoWhileStmt")
+    }
+}
+
+impl PrintAST for DoWhileStmt {
+    fn visit_for_printing(&self, depth: i32) {
+        let tabbed_string = generate_tabbed_string(std::any::type_name::<Self>(), depth);
+        println!("{}", tabbed_string);
+        self.stmt.visit_for_printing(depth);
+        self.expr.visit_for_printing(depth);
+    }
+}
+
+impl PrintUnparsedAST for DoWhileStmt {
+    fn unparse_to_code(&self, depth: i32) {
+        print_newline_and_indent(depth);
+        print!("do ");
+
+        let extra_depth = match *self.stmt {
+            StmtType::CompoundStmt(_) => 0,
+            _ => 1,
+        };
+
+        self.stmt.unparse_to_code(depth + extra_depth);
+        print_newline_and_indent(depth);
+        print!("while (");
+        self.expr.unparse_to_code(depth);
+        print!(");");
+    }
+}
+
+impl DoWhileStmt {
+    pub fn new(span: Span, stmt: Box<StmtType>, expr: Box<ExprType>) -> Self {
+        Self { span, stmt, expr }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SwitchStmt {
+    pub(crate) span: Span,
+    pub(crate) expr: Box<ExprType>,
+    pub(crate) case_list: Box<ListType>,
+}
+
+impl Checking for SwitchStmt {
+    fn visit_for_semantics_checking(&self) {
+        println!("Visiting SwitchStmt node.");
+    }
+}
+
+impl fmt::Display for SwitchStmt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SwitchStmt")
+    }
+}
+
+impl PrintAST for SwitchStmt {
+    fn visit_for_printing(&self, depth: i32) {
+        let tabbed_string = generate_tabbed_string(std::any::type_name::<Self>(), depth);
+        println!("{}", tabbed_string);
+        self.expr.visit_for_printing(depth + 1);
+        self.case_list.visit_for_printing(depth + 1);
+    }
+}
+
+impl PrintUnparsedAST for SwitchStmt {
+    fn unparse_to_code(&self, depth: i32) {
+        print_newline_and_indent(depth);
+        print!("switch (");
+        self.expr.unparse_to_code(depth);
+        print!(") {{");
+        self.case_list.unparse_to_code(depth + 1);
+        print_newline_and_indent(depth);
+        print!("}}");
+    }
+}
+
+impl SwitchStmt {
+    pub fn new(span: Span, expr: Box<ExprType>, case_list: Box<ListType>) -> Self {
+        Self {
+            span,
+            expr,
+            case_list,
+        }
+    }
+}
+
+// One label of a switch and the statements up to the next label.  A default label has an
+// EmptyExpr in place of its constant.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CaseStmt {
+    pub(crate) span: Span,
+    pub(crate) expr: Box<ExprType>,
+    pub(crate) stmt_list: Box<ListType>,
+}
+
+impl Checking for CaseStmt {
+    fn visit_for_semantics_checking(&self) {
+        println!("Visiting CaseStmt node.");
+    }
+}
+
+impl fmt::Display for CaseStmt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CaseStmt")
+    }
+}
+
+impl PrintAST for CaseStmt {
+    fn visit_for_printing(&self, depth: i32) {
+        let tabbed_string = generate_tabbed_string(std::any::type_name::<Self>(), depth);
+        println!("{}", tabbed_string);
+        self.expr.visit_for_printing(depth + 1);
+        self.stmt_list.visit_for_printing(depth + 1);
+    }
+}
+
+impl PrintUnparsedAST for CaseStmt {
+    fn unparse_to_code(&self, depth: i32) {
+        print_newline_and_indent(depth);
+        if self.is_default() {
+            print!("default:");
+        } else {
+            print!("case ");
+            self.expr.unparse_to_code(depth);
+            print!(":");
+        }
+        self.stmt_list.unparse_to_code(depth + 1);
+    }
+}
+
+impl CaseStmt {
+    pub fn new(span: Span, expr: Box<ExprType>, stmt_list: Box<ListType>) -> Self {
+        Self {
+            span,
+            expr,
+            stmt_list,
+        }
+    }
+
+    pub fn is_default(&self) -> bool {
+        matches!(*self.expr, ExprType::EmptyExpr(_))
+    }
+}
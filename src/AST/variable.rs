@@ -6,12 +6,13 @@ use crate::ast::{Checking, PrintAST, PrintUnparsedAST};
 use crate::ast::ident::Ident;
 use crate::ast::primitive_types::AstTypes;
 use crate::globals::TAB_SIZE;
-use crate::utils::{generate_tabbed_string, SourcePosition};
+use crate::source_map::Span;
+use crate::utils::generate_tabbed_string;
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct VarTyped {
-    source_position: SourcePosition,
-    var_type: Box<AstTypes>,
+    pub(crate) span: Span,
+    pub(crate) var_type: Box<AstTypes>,
 }
 
 impl Checking for VarTyped {
@@ -43,9 +44,9 @@ impl PrintUnparsedAST for VarTyped {
 }
 
 impl VarTyped {
-    pub fn new(source_position: SourcePosition, var_type: Box<AstTypes>) -> Self {
+    pub fn new(span: Span, var_type: Box<AstTypes>) -> Self {
         Self {
-            source_position,
+            span,
             var_type,
         }
     }
@@ -54,8 +55,8 @@ impl VarTyped {
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct VarUntyped {
-    source_position: SourcePosition,
-    ident: Ident,
+    pub(crate) span: Span,
+    pub(crate) ident: Ident,
 }
 
 impl Checking for VarUntyped {
@@ -87,9 +88,9 @@ impl PrintUnparsedAST for VarUntyped {
 }
 
 impl VarUntyped {
-    pub fn new(source_position: SourcePosition, ident: Ident) -> Self {
+    pub fn new(span: Span, ident: Ident) -> Self {
         Self {
-            source_position,
+            span,
             ident,
         }
     }
@@ -5,27 +5,27 @@ use std::fs::File;
 use std::io::BufWriter;
 
 use crate::ast::array_type::ArrayType;
-use crate::ast::decl::{FuncDecl, GlobalVarDecl, LocalVarDecl, ParaDecl};
+use crate::ast::decl::{FieldDecl, FuncDecl, GlobalVarDecl, LocalVarDecl, ParaDecl, StructDecl};
 use crate::ast::expression::{
-    Arg, ArrayExpr, ArrayInitExpr, AssignExpr, BinaryExpr, BooleanExpr, CallExpr, FloatExpr,
-    IntExpr, StringExpr, UnaryExpr, VarExpr,
+    Arg, ArrayExpr, ArrayInitExpr, AssignExpr, BinaryExpr, BooleanExpr, CallExpr, CastExpr,
+    CharExpr, CompoundAssignExpr, ConditionalExpr, FieldExpr, FloatExpr, IntExpr, ParenExpr, PostfixExpr, StringExpr, UnaryExpr, VarExpr,
 };
 use crate::ast::ident::Ident;
 use crate::ast::list::{ArrayExprList, DeclList, EmptyArgList, EmptyArrayExprList, EmptyParamList, ListType, ParamList, StmtList};
 use crate::ast::literals::{
-    BooleanLiteral, FloatLiteral, IntLiteral, Operator, StringLiteral, Terminal,
+    BooleanLiteral, CharLiteral, FloatLiteral, IntLiteral, Operator, StringLiteral, Terminal,
 };
 use crate::ast::primitive_types::{
-    BooleanType, ErrorType, FloatType, IntType, StringType, VoidType,
+    BooleanType, CharType, ErrorType, FloatType, IntType, StringType, StructType, VoidType,
 };
 use crate::ast::program::Program;
 use crate::ast::statement::{
-    BreakStmt, CompoundStmt, ContinueStmt, EmptyCompoundStmt, EmptyStmt, ExprStmt, ForStmt, IfStmt,
-    ReturnStmt, WhileStmt,
+    BreakStmt, CaseStmt, CompoundStmt, ContinueStmt, DoWhileStmt, EmptyCompoundStmt, EmptyStmt,
+    ExprStmt, ForStmt, IfStmt, ReturnStmt, SwitchStmt, WhileStmt,
 };
 use crate::ast::variable::VarUntyped;
 use crate::globals::TAB_SIZE;
-use crate::utils::SourcePosition;
+use crate::source_map::Span;
 
 pub mod decl;
 pub mod expression;
@@ -66,8 +66,16 @@ pub enum AstNode {
     BooleanType(BooleanType),
     BreakStmt(BreakStmt),
     CallExpr(CallExpr),
+    CaseStmt(CaseStmt),
+    CastExpr(CastExpr),
+    CharExpr(CharExpr),
+    CharLiteral(CharLiteral),
+    CharType(CharType),
+    CompoundAssignExpr(CompoundAssignExpr),
     CompoundStmt(CompoundStmt),
+    ConditionalExpr(ConditionalExpr),
     ContinueStmt(ContinueStmt),
+    DoWhileStmt(DoWhileStmt),
     DeclList(DeclList),
     EmptyArgList(EmptyArgList),
     EmptyArrayExprList(EmptyArrayExprList),
@@ -79,6 +87,8 @@ pub enum AstNode {
     EmptyStmtList(EmptyArgList),
     ErrorType(ErrorType),
     ExprStmt(ExprStmt),
+    FieldDecl(FieldDecl),
+    FieldExpr(FieldExpr),
     FloatExpr(FloatExpr),
     FloatLiteral(FloatLiteral),
     FloatType(FloatType),
@@ -94,12 +104,17 @@ pub enum AstNode {
     Operator(Operator),
     ParaDecl(ParaDecl),
     ParamList(ParamList),
+    ParenExpr(ParenExpr),
+    PostfixExpr(PostfixExpr),
     Program(Program),
     ReturnStmt(ReturnStmt),
     StmtList(StmtList),
     StringExpr(StringExpr),
     StringLiteral(StringLiteral),
     StringType(StringType),
+    StructDecl(StructDecl),
+    StructType(StructType),
+    SwitchStmt(SwitchStmt),
     Terminal(Terminal),
     UnaryExpr(UnaryExpr),
     Var(VarUntyped),
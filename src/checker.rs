@@ -1,62 +1,1878 @@
-// // Definition for types are skipped here
-//
-// fn check_program(ast_node
-//
-// // Lists for denoting the null reference
-// fn visit_empty_decl_list(&mut self, ast: &EmptyDeclList, o: &mut Object) -> Object {
-//     todo!()
-// }
-//
-// fn visit_empty_stmt_list(&mut self, ast: &EmptyStmtList, o: &mut Object) -> Object {
-//     todo!()
-// }
-// // ... Add other similar methods here
-//
-// // Declarations
-// fn visit_decl_list(&mut self, ast: &DeclList, o: &mut Object) -> Object {
-//     todo!()
-// }
-// // ... Add other similar methods here
-//
-// // Stmts
-// fn visit_stmt_list(&mut self, ast: &StmtList, o: &mut Object) -> Object {
-//     todo!()
-// }
-// // ... Add other similar methods here
-//
-// // Expressions
-// fn visit_int_expr(&mut self, ast: &IntExpr, o: &mut Object) -> Object {
-//     todo!()
-// }
-// // ... Add other similar methods here
-//
-// // Literals and identifiers
-// fn visit_int_literal(&mut self, ast: &IntLiteral, o: &mut Object) -> Object {
-//     todo!()
-// }
-// // ... Add other similar methods here
-//
-// // Parameters
-// fn visit_para_list(&mut self, ast: &ParaList, o: &mut Object) -> Object {
-//     todo!()
-// }
-// // ... Add other similar methods here
-//
-// // Arguments
-// fn visit_arg_list(&mut self, ast: &ArgList, o: &mut Object) -> Object {
-//     todo!()
-// }
-// // ... Add other similar methods here
-//
-// // Types
-// fn visit_void_type(&mut self, ast: &VoidType, o: &mut Object) -> Object {
-//     todo!()
-// }
-// // ... Add other similar methods here
-//
-// // Variables
-// fn visit_simple_var(&mut self, ast: &SimpleVar, o: &mut Object) -> Object {
-//     todo!()
-// }
-// // ... Add other similar methods here
-//
+use std::collections::HashMap;
+
+use crate::ast::array_type::AstTypeVariant;
+use crate::ast::decl::{DeclType, FuncDecl, ParaDecl, StructDecl};
+use crate::ast::expression::{
+    ArrayExpr, AssignExpr, BinaryExpr, CallExpr, CastExpr, CompoundAssignExpr, ConditionalExpr,
+    ExprType, FieldExpr, PostfixExpr, UnaryExpr, VarExpr,
+};
+use crate::ast::ident::Ident;
+use crate::ast::list::ListType;
+use crate::ast::literals::{FloatLiteral, IntLiteral};
+use crate::ast::primitive_types::AstTypes;
+use crate::ast::program::Program;
+use crate::ast::statement::{CompoundStmt, ReturnStmt, StmtType, SwitchStmt};
+use crate::checker::semantic_types::Type;
+use crate::checker::symbol_table::{Parameter, Symbol, SymbolKind, SymbolTable};
+use crate::diagnostics::Diagnostic;
+use crate::source_map::{FileId, Span};
+
+pub mod semantic_types;
+pub mod symbol_table;
+
+// length(a) gives the number of elements in an array, or in a row of a matrix.  Its parameter
+// takes an array of any type and size, which no Type can say, so it is declared here, by name and
+// parameter names, rather than among the other built-ins.
+const LENGTH_BUILTIN: (&str, &[&str]) = ("length", &["a"]);
+
+/*
+   Checker
+   Walks the AST once, top to bottom, declaring identifiers as they are met and checking every
+   use against the VC typing rules.  Errors are collected rather than returned, so a single run
+   reports everything it can find.
+*/
+pub fn check_program(program: &Program) -> Vec<Diagnostic> {
+    analyse_program(program).diagnostics
+}
+
+// Like check_program, but also says which declaration every identifier refers to.
+pub fn analyse_program(program: &Program) -> Analysis {
+    let mut checker = Checker::new();
+    checker.visit_program(program);
+    Analysis {
+        diagnostics: checker.diagnostics,
+        resolutions: checker.resolutions,
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Analysis {
+    pub diagnostics: Vec<Diagnostic>,
+    // In source order, declarations included, for tools such as the language server.
+    pub resolutions: Vec<Resolution>,
+}
+
+// An identifier and the symbol it resolved to.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Resolution {
+    pub span: Span,
+    pub symbol: Symbol,
+}
+
+impl Analysis {
+    // The identifier at offset in a file, including the offset just past its last character.
+    pub fn resolution_at(&self, file_id: FileId, offset: usize) -> Option<&Resolution> {
+        self.resolutions.iter().find(|resolution| {
+            let span = resolution.span;
+            span.file_id == file_id && span.lo <= offset && offset <= span.hi
+        })
+    }
+}
+
+// A declared struct: its fields in order, and where its name was declared.
+#[derive(Clone, Debug)]
+struct StructDef {
+    fields: Vec<(String, Type)>,
+    position: Span,
+}
+
+struct Checker {
+    symbol_table: SymbolTable,
+    // Struct names live apart from variables and functions, as C's struct tags do.
+    structs: HashMap<String, StructDef>,
+    // Every function declared at global scope, so a call can reach one declared further down.
+    functions: HashMap<String, Symbol>,
+    // Prototypes still waiting for their definition, in the order they were declared.
+    undefined_prototypes: Vec<(String, Span)>,
+    diagnostics: Vec<Diagnostic>,
+    resolutions: Vec<Resolution>,
+    current_return_type: Type,
+    loop_depth: usize,
+    // A break may leave a switch as well as a loop; a continue only a loop.
+    switch_depth: usize,
+}
+
+impl Checker {
+    fn new() -> Self {
+        Self {
+            symbol_table: SymbolTable::new(),
+            structs: HashMap::new(),
+            functions: HashMap::new(),
+            undefined_prototypes: Vec::new(),
+            diagnostics: Vec::new(),
+            resolutions: Vec::new(),
+            current_return_type: Type::Void,
+            loop_depth: 0,
+            switch_depth: 0,
+        }
+    }
+
+    fn report(&mut self, diagnostic: Diagnostic) {
+        self.diagnostics.push(diagnostic);
+    }
+
+    fn resolve(&mut self, ident: &Ident, symbol: &Symbol) {
+        self.resolutions.push(Resolution {
+            span: ident.span,
+            symbol: symbol.clone(),
+        });
+    }
+
+    // ===================================== Declarations ==========================================
+
+    fn visit_program(&mut self, program: &Program) {
+        let declaration_list = &program.declaration_list;
+        self.collect_functions(program);
+        self.visit_decl(&declaration_list.decl_type);
+        self.visit_decl_list(&declaration_list.decl_list);
+
+        for (name, position) in std::mem::take(&mut self.undefined_prototypes) {
+            self.report(
+                Diagnostic::error(
+                    position,
+                    format!("function declared but never defined: {}", name),
+                )
+                .with_code("*41"),
+            );
+        }
+
+        match self.symbol_table.lookup("main").cloned() {
+            Some(main) if main.is_function() && main.position.is_some() => {
+                if main.symbol_type != Type::Int {
+                    self.report(
+                        Diagnostic::error(main.position.unwrap(), "return type of main is not int")
+                            .with_code("*1")
+                            .with_primary_label(format!("main returns {}", main.symbol_type)),
+                    );
+                }
+            }
+            _ => self.report(
+                Diagnostic::error(declaration_list.span, "main function is missing")
+                    .with_code("*0"),
+            ),
+        }
+    }
+
+    // The first declaration of each function, found before anything is checked.  Types are taken
+    // as written; visit_func_decl reports any problems with them in order.
+    fn collect_functions(&mut self, program: &Program) {
+        let declaration_list = &program.declaration_list;
+        let mut decls = vec![&declaration_list.decl_type];
+        let mut current = &*declaration_list.decl_list;
+        while let ListType::DeclList(list) = current {
+            decls.push(&list.decl_type);
+            current = &list.decl_list;
+        }
+
+        for decl in decls {
+            if let DeclType::FuncDecl(func_decl) = &**decl {
+                let (parameters, return_type) = function_signature(func_decl);
+                self.functions
+                    .entry(func_decl.ident.spelling.clone())
+                    .or_insert_with(|| Symbol {
+                        name: func_decl.ident.spelling.clone(),
+                        kind: SymbolKind::Function { parameters },
+                        symbol_type: return_type,
+                        position: Some(func_decl.ident.span),
+                        is_const: false,
+                        value: None,
+                    });
+            }
+        }
+    }
+
+    fn visit_decl_list(&mut self, decl_list: &ListType) {
+        let mut current = decl_list;
+        while let ListType::DeclList(list) = current {
+            self.visit_decl(&list.decl_type);
+            current = &list.decl_list;
+        }
+    }
+
+    fn visit_decl(&mut self, decl: &DeclType) {
+        match decl {
+            DeclType::FuncDecl(func_decl) => self.visit_func_decl(func_decl),
+            DeclType::GlobalVarDecl(var_decl) => self.visit_var_decl(
+                &var_decl.declaration_type,
+                &var_decl.ident,
+                &var_decl.expr,
+                var_decl.is_const,
+            ),
+            DeclType::LocalVarDecl(var_decl) => self.visit_var_decl(
+                &var_decl.declaration_type,
+                &var_decl.ident,
+                &var_decl.expr,
+                var_decl.is_const,
+            ),
+            DeclType::ParaDecl(para_decl) => self.visit_para_decl(para_decl),
+            DeclType::StructDecl(struct_decl) => self.visit_struct_decl(struct_decl),
+            // The loader has already put the included declarations in its place.
+            DeclType::IncludeDecl(_) => (),
+            // Fields are visited with the struct that declares them.
+            DeclType::FieldDecl(_) => (),
+        }
+    }
+
+    fn declare(&mut self, ident: &Ident, kind: SymbolKind, symbol_type: Type) {
+        self.declare_symbol(
+            ident,
+            Symbol {
+                name: ident.spelling.clone(),
+                kind,
+                symbol_type,
+                position: Some(ident.span),
+                is_const: false,
+                value: None,
+            },
+        );
+    }
+
+    fn declare_symbol(&mut self, ident: &Ident, symbol: Symbol) {
+        self.resolve(ident, &symbol);
+
+        if let Some(previous) = self.symbol_table.insert(symbol) {
+            let mut diagnostic = Diagnostic::error(
+                ident.span,
+                format!("identifier redeclared: {}", ident.spelling),
+            )
+            .with_code("*2");
+            if let Some(previous_position) = previous.position {
+                diagnostic = diagnostic.with_label(previous_position, "previously declared here");
+            }
+            self.report(diagnostic);
+        }
+    }
+
+    // The type a declaration names, or Error if it names a struct that has not been declared or
+    // sizes an array with something other than an int constant.
+    fn visit_declared_type(&mut self, declaration_type: &AstTypeVariant) -> Type {
+        let declared_type = self.sized_type(declaration_type);
+
+        let mut dimension = declaration_type;
+        while let AstTypeVariant::Array(array_type) = dimension {
+            let size = &array_type.expression;
+            if !matches!(size, ExprType::EmptyExpr(_)) {
+                if self.visit_expr(size).is_error() {
+                    return Type::Error;
+                }
+                if self.constant_int(size).is_none_or(|size| size < 0) {
+                    self.report(
+                        Diagnostic::error(*size.get_span(), "array size is not an int constant")
+                            .with_code("*44")
+                            .with_note(
+                                "array sizes are int literals, const ints, or expressions of them",
+                            ),
+                    );
+                    return Type::Error;
+                }
+            }
+            dimension = &array_type.array_type;
+        }
+
+        match declaration_type.base_type() {
+            AstTypes::StructType(struct_type)
+                if !self.structs.contains_key(&struct_type.ident.spelling) =>
+            {
+                self.report(
+                    Diagnostic::error(
+                        struct_type.span,
+                        format!("struct undeclared: {}", struct_type.ident.spelling),
+                    )
+                    .with_code("*37"),
+                );
+                Type::Error
+            }
+            _ => declared_type,
+        }
+    }
+
+    // The semantic type of a declaration, with array sizes worked out from constant expressions.
+    fn sized_type(&self, declaration_type: &AstTypeVariant) -> Type {
+        match declaration_type {
+            AstTypeVariant::Primitive(primitive_type) => Type::from_ast_type(primitive_type),
+            AstTypeVariant::Array(array_type) => Type::Array {
+                element: Box::new(self.sized_type(&array_type.array_type)),
+                size: self
+                    .constant_int(&array_type.expression)
+                    .and_then(|size| usize::try_from(size).ok()),
+            },
+        }
+    }
+
+    fn visit_struct_decl(&mut self, struct_decl: &StructDecl) {
+        let ident = &struct_decl.ident;
+        let mut fields: Vec<(String, Type)> = Vec::new();
+        let mut field_positions: HashMap<&str, Span> = HashMap::new();
+
+        for field_decl in struct_decl.fields() {
+            let field_ident = &field_decl.ident;
+            let mut field_type = self.visit_declared_type(&field_decl.declaration_type);
+            if let Some(error) = void_declaration_error(&field_type, field_ident) {
+                self.report(error);
+                field_type = Type::Error;
+            } else if field_type.dimensions().contains(&None) {
+                self.report(
+                    Diagnostic::error(
+                        field_ident.span,
+                        format!("array size missing: {}", field_ident.spelling),
+                    )
+                    .with_code("*18"),
+                );
+            }
+
+            if let Some(previous_position) =
+                field_positions.insert(&field_ident.spelling, field_ident.span)
+            {
+                self.report(
+                    Diagnostic::error(
+                        field_ident.span,
+                        format!("identifier redeclared: {}", field_ident.spelling),
+                    )
+                    .with_code("*2")
+                    .with_label(previous_position, "previously declared here"),
+                );
+                continue;
+            }
+            fields.push((field_ident.spelling.clone(), field_type));
+        }
+
+        // Declared after its fields, so a struct cannot contain itself.
+        if let Some(previous) = self.structs.get(&ident.spelling) {
+            let previous_position = previous.position;
+            self.report(
+                Diagnostic::error(
+                    ident.span,
+                    format!("identifier redeclared: struct {}", ident.spelling),
+                )
+                .with_code("*2")
+                .with_label(previous_position, "previously declared here"),
+            );
+            return;
+        }
+        self.structs.insert(
+            ident.spelling.clone(),
+            StructDef {
+                fields,
+                position: ident.span,
+            },
+        );
+    }
+
+    fn visit_func_decl(&mut self, func_decl: &FuncDecl) {
+        let return_type = self.visit_declared_type(&func_decl.function_type);
+        let parameters = parameter_decls(&func_decl.param_list)
+            .into_iter()
+            .map(|para_decl| Parameter {
+                name: para_decl.ident.spelling.clone(),
+                parameter_type: self.sized_type(&para_decl.declaration_type),
+                is_const: para_decl.is_const,
+            })
+            .collect();
+        self.declare_function(func_decl, parameters, return_type.clone());
+
+        // Parameters share a scope with the outermost block of the body.
+        self.symbol_table.open_scope();
+        for para_decl in parameter_decls(&func_decl.param_list) {
+            self.visit_para_decl(para_decl);
+        }
+        if func_decl.is_prototype() {
+            self.symbol_table.close_scope();
+            return;
+        }
+
+        self.current_return_type = return_type.clone();
+        match &*func_decl.statements {
+            StmtType::CompoundStmt(body) => self.visit_compound_body(body),
+            other => self.visit_stmt(other),
+        }
+        self.symbol_table.close_scope();
+
+        if return_type != Type::Void && !always_returns(&func_decl.statements) {
+            let function_span = func_decl.ident.span.to(func_decl.span);
+            self.report(
+                Diagnostic::error(function_span, "missing return statement")
+                    .with_code("*31")
+                    .with_note(format!(
+                        "{} is declared to return {}",
+                        func_decl.ident.spelling, return_type
+                    )),
+            );
+        }
+    }
+
+    // Declares a function, or matches it against the prototype that declared it first.
+    fn declare_function(
+        &mut self,
+        func_decl: &FuncDecl,
+        parameters: Vec<Parameter>,
+        return_type: Type,
+    ) {
+        let ident = &func_decl.ident;
+        let pending = self
+            .undefined_prototypes
+            .iter()
+            .position(|(name, _)| *name == ident.spelling);
+        let previous = self
+            .symbol_table
+            .lookup(&ident.spelling)
+            .filter(|symbol| symbol.is_function() && symbol.position.is_some())
+            .cloned();
+
+        match previous {
+            Some(previous) if func_decl.is_prototype() || pending.is_some() => {
+                self.check_prototype_match(ident, &previous, &parameters, &return_type);
+                if func_decl.is_prototype() {
+                    self.resolve(ident, &previous);
+                    return;
+                }
+
+                if let Some(pending) = pending {
+                    self.undefined_prototypes.remove(pending);
+                }
+                // The definition takes over from the prototype, parameter names and all.
+                let symbol = Symbol {
+                    name: ident.spelling.clone(),
+                    kind: SymbolKind::Function { parameters },
+                    symbol_type: return_type,
+                    position: Some(ident.span),
+                    is_const: false,
+                    value: None,
+                };
+                self.resolve(ident, &symbol);
+                self.symbol_table.insert(symbol);
+            }
+            _ => {
+                self.declare(ident, SymbolKind::Function { parameters }, return_type);
+                if func_decl.is_prototype() {
+                    self.undefined_prototypes.push((ident.spelling.clone(), ident.span));
+                }
+            }
+        }
+    }
+
+    fn check_prototype_match(
+        &mut self,
+        ident: &Ident,
+        previous: &Symbol,
+        parameters: &[Parameter],
+        return_type: &Type,
+    ) {
+        let SymbolKind::Function {
+            parameters: previous_parameters,
+        } = &previous.kind
+        else {
+            return;
+        };
+        let differ = |declared: &Type, found: &Type| {
+            !declared.is_error() && !found.is_error() && declared != found
+        };
+
+        let mismatch = if differ(&previous.symbol_type, return_type) {
+            Some(format!(
+                "{} was declared to return {}",
+                ident.spelling, previous.symbol_type
+            ))
+        } else if previous_parameters.len() != parameters.len() {
+            Some(format!(
+                "{} was declared with {} parameter(s)",
+                ident.spelling,
+                previous_parameters.len()
+            ))
+        } else {
+            previous_parameters
+                .iter()
+                .zip(parameters)
+                .position(|(declared, found)| {
+                    differ(&declared.parameter_type, &found.parameter_type)
+                })
+                .map(|index| {
+                    format!(
+                        "parameter {} of {} was declared as {}",
+                        index + 1,
+                        ident.spelling,
+                        previous_parameters[index].parameter_type
+                    )
+                })
+        };
+
+        if let (Some(note), Some(previous_position)) = (mismatch, previous.position) {
+            self.report(
+                Diagnostic::error(
+                    ident.span,
+                    format!("declaration does not match prototype: {}", ident.spelling),
+                )
+                .with_code("*40")
+                .with_label(previous_position, "previously declared here")
+                .with_note(note),
+            );
+        }
+    }
+
+    fn visit_para_decl(&mut self, para_decl: &ParaDecl) {
+        let mut para_type = self.visit_declared_type(&para_decl.declaration_type);
+        if let Some(error) = void_declaration_error(&para_type, &para_decl.ident) {
+            self.report(error);
+            para_type = Type::Error;
+        } else if para_type.dimensions().iter().skip(1).any(Option::is_none) {
+            // The caller's array fixes the first dimension, but rows need a known length.
+            self.report(
+                Diagnostic::error(
+                    para_decl.ident.span,
+                    format!("array size missing: {}", para_decl.ident.spelling),
+                )
+                .with_code("*18")
+                .with_note("only the first dimension of an array parameter may be left unsized"),
+            );
+        }
+
+        self.declare_symbol(
+            &para_decl.ident,
+            Symbol {
+                name: para_decl.ident.spelling.clone(),
+                kind: SymbolKind::Parameter,
+                symbol_type: para_type,
+                position: Some(para_decl.ident.span),
+                is_const: para_decl.is_const,
+                value: None,
+            },
+        );
+    }
+
+    fn visit_var_decl(
+        &mut self,
+        declaration_type: &AstTypeVariant,
+        ident: &Ident,
+        init_expr: &ExprType,
+        is_const: bool,
+    ) {
+        let mut var_type = self.visit_declared_type(declaration_type);
+
+        let dimensions = var_type.dimensions();
+        if let Some(error) = void_declaration_error(&var_type, ident) {
+            self.report(error);
+            var_type = Type::Error;
+        } else if dimensions.iter().skip(1).any(Option::is_none) {
+            self.report(
+                Diagnostic::error(
+                    ident.span,
+                    format!("array size missing: {}", ident.spelling),
+                )
+                .with_code("*18")
+                .with_note("only the first dimension of an array may be left unsized"),
+            );
+        } else if dimensions.first() == Some(&None) {
+            // int a[] = {...} takes its size from the initialiser.  A scalar initialiser is
+            // reported by visit_initialiser.
+            match (initialiser_length(init_expr), &mut var_type) {
+                (Some(length), Type::Array { size, .. }) => *size = Some(length),
+                _ if matches!(init_expr, ExprType::EmptyExpr(_)) => self.report(
+                    Diagnostic::error(
+                        ident.span,
+                        format!("array size missing: {}", ident.spelling),
+                    )
+                    .with_code("*18")
+                    .with_note("an array declared without a size takes it from an initialiser"),
+                ),
+                _ => (),
+            }
+        }
+
+        let mut value = None;
+        if is_const {
+            if matches!(init_expr, ExprType::EmptyExpr(_)) {
+                self.report(
+                    Diagnostic::error(
+                        ident.span,
+                        format!("const declaration without an initialiser: {}", ident.spelling),
+                    )
+                    .with_code("*42"),
+                );
+            } else if var_type == Type::Int {
+                value = self.constant_int(init_expr);
+            }
+        }
+
+        self.declare_symbol(
+            ident,
+            Symbol {
+                name: ident.spelling.clone(),
+                kind: SymbolKind::Variable,
+                symbol_type: var_type.clone(),
+                position: Some(ident.span),
+                is_const,
+                value,
+            },
+        );
+        self.visit_initialiser(&var_type, ident, init_expr, &ident.spelling);
+    }
+
+    // owner names what is being initialised in notes: the variable, or a row of it such as
+    // "row 1 of m".
+    fn visit_initialiser(
+        &mut self,
+        var_type: &Type,
+        ident: &Ident,
+        init_expr: &ExprType,
+        owner: &str,
+    ) {
+        match (init_expr, var_type) {
+            (ExprType::EmptyExpr(_), _) => (),
+            (ExprType::ArrayInitExpr(array_init), Type::Array { element, size }) => {
+                let mut element_count = 0;
+                let mut current = &*array_init.init_list;
+
+                while let ListType::ArrayExprList(list) = current {
+                    // Each row of a multidimensional array is an initialiser in its own right.
+                    if element.is_array() || matches!(list.expression, ExprType::ArrayInitExpr(_)) {
+                        let row = format!("row {} of {}", element_count, owner);
+                        self.visit_initialiser(element, ident, &list.expression, &row);
+                    } else {
+                        let found = self.visit_scalar_expr(&list.expression);
+                        if !element.is_assignable_from(&found) {
+                            self.report(
+                                Diagnostic::error(
+                                    *list.expression.get_span(),
+                                    format!(
+                                        "wrong type for element in array initialiser: at position {}",
+                                        element_count
+                                    ),
+                                )
+                                .with_code("*13")
+                                .with_primary_label(format!(
+                                    "expected {}, found {}",
+                                    element, found
+                                )),
+                            );
+                        }
+                    }
+                    element_count += 1;
+                    current = &list.expr_list;
+                }
+
+                if let Some(size) = size {
+                    if element_count > *size {
+                        self.report(
+                            Diagnostic::error(
+                                array_init.span,
+                                format!("excess elements in array initialiser: {}", ident.spelling),
+                            )
+                            .with_code("*16")
+                            .with_note(format!(
+                                "{} has {} elements but {} initialisers were given",
+                                owner, size, element_count
+                            )),
+                        );
+                    }
+                }
+            }
+            (ExprType::ArrayInitExpr(_), Type::Error) => (),
+            (ExprType::ArrayInitExpr(array_init), _) => self.report(
+                Diagnostic::error(
+                    array_init.span,
+                    "invalid initialiser: array initialiser for scalar",
+                )
+                .with_code("*14"),
+            ),
+            (expr, Type::Array { .. }) => {
+                self.visit_expr(expr);
+                self.report(
+                    Diagnostic::error(
+                        *expr.get_span(),
+                        format!(
+                            "invalid initialiser: scalar initialiser for array: {}",
+                            ident.spelling
+                        ),
+                    )
+                    .with_code("*15"),
+                );
+            }
+            (expr, var_type) => {
+                let found = self.visit_scalar_expr(expr);
+                if !var_type.is_assignable_from(&found) {
+                    self.report(
+                        Diagnostic::error(*expr.get_span(), "incompatible type for =")
+                            .with_code("*6")
+                            .with_primary_label(format!("expected {}, found {}", var_type, found)),
+                    );
+                }
+            }
+        }
+    }
+
+    // ====================================== Statements ===========================================
+
+    fn visit_compound_stmt(&mut self, compound_stmt: &CompoundStmt) {
+        self.symbol_table.open_scope();
+        self.visit_compound_body(compound_stmt);
+        self.symbol_table.close_scope();
+    }
+
+    fn visit_compound_body(&mut self, compound_stmt: &CompoundStmt) {
+        self.visit_decl_list(&compound_stmt.decl_list);
+        self.visit_stmt_list(&compound_stmt.stmt_list);
+    }
+
+    fn visit_stmt_list(&mut self, stmt_list: &ListType) {
+        let mut current = stmt_list;
+        let mut control_transferred = false;
+        let mut reported_unreachable = false;
+
+        while let ListType::StmtList(list) = current {
+            if control_transferred && !reported_unreachable {
+                let unreachable_span = list.stmt.get_span().to(last_stmt_span(current));
+                self.report(
+                    Diagnostic::error(unreachable_span, "statement(s) not reached")
+                        .with_code("*30"),
+                );
+                reported_unreachable = true;
+            }
+
+            self.visit_stmt(&list.stmt);
+            control_transferred |= match *list.stmt {
+                StmtType::ReturnStmt(_) => true,
+                // A misplaced break or continue has already been reported.
+                StmtType::BreakStmt(_) => self.loop_depth > 0 || self.switch_depth > 0,
+                StmtType::ContinueStmt(_) => self.loop_depth > 0,
+                _ => false,
+            };
+            current = &list.stmt_list;
+        }
+    }
+
+    fn visit_stmt(&mut self, stmt: &StmtType) {
+        match stmt {
+            StmtType::BreakStmt(break_stmt) => {
+                if self.loop_depth == 0 && self.switch_depth == 0 {
+                    self.report(
+                        Diagnostic::error(
+                            break_stmt.span,
+                            "break must be in a loop or switch",
+                        )
+                        .with_code("*23"),
+                    );
+                }
+            }
+            StmtType::ContinueStmt(continue_stmt) => {
+                if self.loop_depth == 0 {
+                    self.report(
+                        Diagnostic::error(
+                            continue_stmt.span,
+                            "continue must be in a while/for",
+                        )
+                        .with_code("*24"),
+                    );
+                }
+            }
+            StmtType::CompoundStmt(compound_stmt) => self.visit_compound_stmt(compound_stmt),
+            StmtType::EmptyCompoundStmt(_) | StmtType::EmptyStmt(_) => (),
+            StmtType::ExprStmt(expr_stmt) => {
+                self.visit_expr(&expr_stmt.expr);
+            }
+            StmtType::IfStmt(if_stmt) => {
+                self.visit_condition(&if_stmt.expr, "if");
+                self.visit_stmt(&if_stmt.stmt_1);
+                self.visit_stmt(&if_stmt.stmt_2);
+            }
+            StmtType::ForStmt(for_stmt) => {
+                self.visit_expr(&for_stmt.expr_1);
+                if !matches!(*for_stmt.expr_2, ExprType::EmptyExpr(_)) {
+                    self.visit_condition(&for_stmt.expr_2, "for");
+                }
+                self.visit_expr(&for_stmt.expr_3);
+                self.visit_loop_body(&for_stmt.stmt);
+            }
+            StmtType::WhileStmt(while_stmt) => {
+                self.visit_condition(&while_stmt.expr, "while");
+                self.visit_loop_body(&while_stmt.stmt);
+            }
+            StmtType::DoWhileStmt(do_while_stmt) => {
+                self.visit_loop_body(&do_while_stmt.stmt);
+                self.visit_condition(&do_while_stmt.expr, "while");
+            }
+            StmtType::SwitchStmt(switch_stmt) => self.visit_switch_stmt(switch_stmt),
+            StmtType::ReturnStmt(return_stmt) => self.visit_return_stmt(return_stmt),
+        }
+    }
+
+    fn visit_switch_stmt(&mut self, switch_stmt: &SwitchStmt) {
+        let found = self.visit_scalar_expr(&switch_stmt.expr);
+        if found.promoted() != Type::Int && !found.is_error() {
+            self.report(
+                Diagnostic::error(
+                    *switch_stmt.expr.get_span(),
+                    format!("switch expression is not int (found: {})", found),
+                )
+                .with_code("*32"),
+            );
+        }
+
+        let mut labels: HashMap<i64, Span> = HashMap::new();
+        let mut default_span: Option<Span> = None;
+
+        self.switch_depth += 1;
+        let mut current = &*switch_stmt.case_list;
+        while let ListType::CaseList(list) = current {
+            let case_stmt = &list.case_stmt;
+            let label_span = *case_stmt.expr.get_span();
+
+            let (label, previous) = if case_stmt.is_default() {
+                (String::from("default"), default_span.replace(label_span))
+            } else {
+                match self.visit_case_label(&case_stmt.expr) {
+                    Some(value) => (value.to_string(), labels.insert(value, label_span)),
+                    None => (String::new(), None),
+                }
+            };
+            if let Some(previous_span) = previous {
+                self.report(
+                    Diagnostic::error(label_span, format!("duplicate case label: {}", label))
+                        .with_code("*34")
+                        .with_label(previous_span, "first used here"),
+                );
+            }
+
+            self.visit_stmt_list(&case_stmt.stmt_list);
+            current = &list.case_list;
+        }
+        self.switch_depth -= 1;
+    }
+
+    // The value of a case label, which must be a constant int (or char) expression.
+    fn visit_case_label(&mut self, label: &ExprType) -> Option<i64> {
+        let found = self.visit_scalar_expr(label);
+        if found.is_error() {
+            return None;
+        }
+
+        let value = self.constant_int(label).filter(|_| found.promoted() == Type::Int);
+        if value.is_none() {
+            let reason = match found.promoted() {
+                Type::Int => String::from("not a constant"),
+                _ => format!("found {}", found),
+            };
+            self.report(
+                Diagnostic::error(*label.get_span(), "case label is not an int constant")
+                    .with_code("*33")
+                    .with_primary_label(reason),
+            );
+        }
+        value
+    }
+
+    fn visit_loop_body(&mut self, body: &StmtType) {
+        self.loop_depth += 1;
+        self.visit_stmt(body);
+        self.loop_depth -= 1;
+    }
+
+    fn visit_condition(&mut self, condition: &ExprType, construct: &str) {
+        let found = self.visit_scalar_expr(condition);
+        if found != Type::Boolean && !found.is_error() {
+            let code = match construct {
+                "if" => "*20",
+                "for" => "*21",
+                "while" => "*22",
+                _ => "*35",
+            };
+            self.report(
+                Diagnostic::error(
+                    *condition.get_span(),
+                    format!(
+                        "{} conditional is not boolean (found: {})",
+                        construct, found
+                    ),
+                )
+                .with_code(code),
+            );
+        }
+    }
+
+    fn visit_return_stmt(&mut self, return_stmt: &ReturnStmt) {
+        let expected = self.current_return_type.clone();
+
+        let label = match (&*return_stmt.expr, &expected) {
+            (ExprType::EmptyExpr(_), Type::Void) => None,
+            (ExprType::EmptyExpr(_), _) => Some(format!("expected a value of type {}", expected)),
+            (expr, Type::Void) => {
+                self.visit_expr(expr);
+                Some(String::from("a void function cannot return a value"))
+            }
+            (expr, _) => {
+                let found = self.visit_scalar_expr(expr);
+                (!expected.is_assignable_from(&found))
+                    .then(|| format!("expected {}, found {}", expected, found))
+            }
+        };
+
+        if let Some(label) = label {
+            self.report(
+                Diagnostic::error(return_stmt.span, "incompatible type for return")
+                    .with_code("*8")
+                    .with_primary_label(label),
+            );
+        }
+    }
+
+    // ===================================== Expressions ===========================================
+
+    // Like visit_expr, but whole arrays are not allowed: they may only be passed as arguments.
+    fn visit_scalar_expr(&mut self, expr: &ExprType) -> Type {
+        let found = self.visit_expr(expr);
+        if !found.is_array() {
+            return found;
+        }
+
+        let message = match variable_name(expr) {
+            Some(name) => format!("attempt to use an array/function as a scalar: {}", name),
+            None => String::from("attempt to use an array/function as a scalar"),
+        };
+        self.report(Diagnostic::error(*expr.get_span(), message).with_code("*11"));
+        Type::Error
+    }
+
+    fn visit_expr(&mut self, expr: &ExprType) -> Type {
+        match expr {
+            ExprType::Arg(arg) => self.visit_expr(&arg.expr),
+            ExprType::ArrayExpr(array_expr) => self.visit_array_expr(array_expr),
+            ExprType::AssignExpr(assign_expr) => self.visit_assign_expr(assign_expr),
+            ExprType::BinaryExpr(binary_expr) => self.visit_binary_expr(binary_expr),
+            ExprType::BooleanExpr(_) => Type::Boolean,
+            ExprType::CallExpr(call_expr) => self.visit_call_expr(call_expr),
+            ExprType::CastExpr(cast_expr) => self.visit_cast_expr(cast_expr),
+            ExprType::CharExpr(_) => Type::Char,
+            ExprType::CompoundAssignExpr(compound_expr) => {
+                self.visit_compound_assign_expr(compound_expr)
+            }
+            ExprType::ConditionalExpr(conditional_expr) => {
+                self.visit_conditional_expr(conditional_expr)
+            }
+            ExprType::EmptyExpr(_) => Type::Void,
+            ExprType::FieldExpr(field_expr) => self.visit_field_expr(field_expr),
+            ExprType::FloatExpr(float_expr) => self.visit_float_literal(&float_expr.float_literal),
+            ExprType::IntExpr(int_expr) => self.visit_int_literal(&int_expr.int_literal, false),
+            ExprType::ParenExpr(paren_expr) => self.visit_expr(&paren_expr.expr),
+            ExprType::PostfixExpr(postfix_expr) => self.visit_increment(
+                &postfix_expr.expression,
+                &postfix_expr.operator.spelling,
+                postfix_expr.span,
+            ),
+            ExprType::StringExpr(_) => Type::String,
+            ExprType::UnaryExpr(unary_expr) => self.visit_unary_expr(unary_expr),
+            ExprType::VarExpr(var_expr) => self.visit_var_expr(var_expr),
+            // Only produced inside declarations, where visit_initialiser handles them.
+            ExprType::ArrayInitExpr(_) | ExprType::EmptyArrayExprList(_) => Type::Error,
+        }
+    }
+
+    fn visit_var_expr(&mut self, var_expr: &VarExpr) -> Type {
+        let ident = &var_expr.var.ident;
+
+        match self.symbol_table.lookup(&ident.spelling).cloned() {
+            None => {
+                self.report_undeclared(ident);
+                Type::Error
+            }
+            Some(symbol) if symbol.is_function() => {
+                self.resolve(ident, &symbol);
+                self.report(
+                    Diagnostic::error(
+                        ident.span,
+                        format!(
+                            "attempt to use an array/function as a scalar: {}",
+                            ident.spelling
+                        ),
+                    )
+                    .with_code("*11")
+                    .with_label_if_declared(&symbol),
+                );
+                Type::Error
+            }
+            Some(symbol) => {
+                self.resolve(ident, &symbol);
+                symbol.symbol_type
+            }
+        }
+    }
+
+    fn visit_array_expr(&mut self, array_expr: &ArrayExpr) -> Type {
+        let element_type = match &*array_expr.array {
+            ExprType::VarExpr(var_expr) => self.visit_indexed_var(&var_expr.var.ident, array_expr),
+            // A row of a multidimensional array, or anything else that might be one.
+            array => {
+                let array_type = self.visit_expr(array);
+                match array_type.element_type() {
+                    Some(element_type) => element_type.clone(),
+                    None => {
+                        if !array_type.is_error() {
+                            self.report(
+                                Diagnostic::error(
+                                    array_expr.span,
+                                    "attempt to use a scalar/function as an array",
+                                )
+                                .with_code("*12")
+                                .with_label(*array.get_span(), format!("this is {}", array_type)),
+                            );
+                        }
+                        Type::Error
+                    }
+                }
+            }
+        };
+
+        let index_type = self.visit_scalar_expr(&array_expr.expr);
+        if index_type != Type::Int && !index_type.is_error() {
+            self.report(
+                Diagnostic::error(
+                    *array_expr.expr.get_span(),
+                    "array subscript is not an integer",
+                )
+                .with_code("*17")
+                .with_primary_label(format!("found {}", index_type)),
+            );
+        } else if let (Some(index), Some(size)) = (
+            self.constant_int(&array_expr.expr),
+            self.array_size(&array_expr.array),
+        ) {
+            if usize::try_from(index).ok().is_none_or(|index| index >= size) {
+                self.report(
+                    Diagnostic::error(
+                        *array_expr.expr.get_span(),
+                        format!("array index out of bounds: {}", index),
+                    )
+                    .with_code("*48")
+                    .with_primary_label(format!("the array has {} element(s)", size)),
+                );
+            }
+        }
+
+        element_type
+    }
+
+    // The element type of a variable being indexed, reported against its declaration if it is
+    // not an array.
+    fn visit_indexed_var(&mut self, ident: &Ident, array_expr: &ArrayExpr) -> Type {
+        match self.symbol_table.lookup(&ident.spelling).cloned() {
+            None => {
+                self.report_undeclared(ident);
+                Type::Error
+            }
+            Some(symbol) => {
+                self.resolve(ident, &symbol);
+                match (&symbol.kind, symbol.symbol_type.element_type()) {
+                    (SymbolKind::Function { .. }, _) | (_, None) => {
+                        if !symbol.symbol_type.is_error() {
+                            self.report(
+                                Diagnostic::error(
+                                    array_expr.span,
+                                    format!(
+                                        "attempt to use a scalar/function as an array: {}",
+                                        ident.spelling
+                                    ),
+                                )
+                                .with_code("*12")
+                                .with_label_if_declared(&symbol),
+                            );
+                        }
+                        Type::Error
+                    }
+                    (_, Some(element_type)) => element_type.clone(),
+                }
+            }
+        }
+    }
+
+    // An int is 32 bits, so a literal may be at most 2147483647, or 2147483648 when negated.
+    fn visit_int_literal(&mut self, int_literal: &IntLiteral, negated: bool) -> Type {
+        let (limit, label) = if negated {
+            (1 << 31, format!("an int is at least {}", i32::MIN))
+        } else {
+            ((1 << 31) - 1, format!("an int is at most {}", i32::MAX))
+        };
+        if int_literal.value.is_none_or(|value| value > limit) {
+            self.report(
+                Diagnostic::error(
+                    int_literal.span,
+                    format!("integer literal too large: {}", int_literal.spelling),
+                )
+                .with_code("*46")
+                .with_primary_label(label),
+            );
+        }
+        Type::Int
+    }
+
+    // A float is 32 bits, so a literal must stay finite once rounded to one.
+    fn visit_float_literal(&mut self, float_literal: &FloatLiteral) -> Type {
+        if float_literal
+            .value
+            .is_none_or(|value| !(value as f32).is_finite())
+        {
+            self.report(
+                Diagnostic::error(
+                    float_literal.span,
+                    format!("float literal too large: {}", float_literal.spelling),
+                )
+                .with_code("*47")
+                .with_primary_label(format!("a float is at most {:e}", f32::MAX)),
+            );
+        }
+        Type::Float
+    }
+
+    fn visit_cast_expr(&mut self, cast_expr: &CastExpr) -> Type {
+        let operand_type = self.visit_scalar_expr(&cast_expr.expression);
+        let target_type = Type::from_ast_type(&cast_expr.cast_type);
+
+        if !operand_type.is_error() && !operand_type.can_cast_to(&target_type) {
+            self.report(
+                Diagnostic::error(
+                    cast_expr.span,
+                    format!("invalid cast: {} to {}", operand_type, target_type),
+                )
+                .with_code("*45")
+                .with_label(
+                    *cast_expr.expression.get_span(),
+                    format!("this is {}", operand_type),
+                ),
+            );
+            return Type::Error;
+        }
+
+        target_type
+    }
+
+    fn visit_field_expr(&mut self, field_expr: &FieldExpr) -> Type {
+        let base_type = self.visit_expr(&field_expr.expression);
+        let field = &field_expr.field;
+
+        let struct_name = match base_type {
+            Type::Error => return Type::Error,
+            Type::Struct(name) => name,
+            other => {
+                self.report(
+                    Diagnostic::error(
+                        field_expr.span,
+                        format!("attempt to access a field of a non-struct: {}", field.spelling),
+                    )
+                    .with_code("*38")
+                    .with_label(
+                        *field_expr.expression.get_span(),
+                        format!("this is {}", other),
+                    ),
+                );
+                return Type::Error;
+            }
+        };
+
+        let field_type = self.structs.get(&struct_name).and_then(|struct_def| {
+            struct_def
+                .fields
+                .iter()
+                .find(|(name, _)| *name == field.spelling)
+                .map(|(_, field_type)| field_type.clone())
+        });
+        field_type.unwrap_or_else(|| {
+            self.report(
+                Diagnostic::error(field.span, format!("no such field: {}", field.spelling))
+                    .with_code("*39")
+                    .with_note(format!(
+                        "struct {} has no field named {}",
+                        struct_name, field.spelling
+                    )),
+            );
+            Type::Error
+        })
+    }
+
+    fn visit_assign_expr(&mut self, assign_expr: &AssignExpr) -> Type {
+        let lhs_type = self.visit_expr(&assign_expr.expression_one);
+        let rhs_type = self.visit_scalar_expr(&assign_expr.expression_two);
+
+        if !is_lvalue(&assign_expr.expression_one) || lhs_type.is_array() {
+            self.report_invalid_lvalue(&assign_expr.expression_one, assign_expr.span);
+            return Type::Error;
+        }
+        if self.report_const_assignment(&assign_expr.expression_one, assign_expr.span) {
+            return Type::Error;
+        }
+
+        if !lhs_type.is_assignable_from(&rhs_type) {
+            self.report(
+                Diagnostic::error(assign_expr.span, "incompatible type for =")
+                    .with_code("*6")
+                    .with_primary_label(format!("cannot assign {} to {}", rhs_type, lhs_type)),
+            );
+            return Type::Error;
+        }
+
+        lhs_type
+    }
+
+    fn visit_compound_assign_expr(&mut self, compound_expr: &CompoundAssignExpr) -> Type {
+        let lhs_type = self.visit_expr(&compound_expr.expression_one);
+        let rhs_type = self.visit_scalar_expr(&compound_expr.expression_two);
+        let operator = &compound_expr.operator.spelling;
+
+        if !is_lvalue(&compound_expr.expression_one) || lhs_type.is_array() {
+            self.report_invalid_lvalue(&compound_expr.expression_one, compound_expr.span);
+            return Type::Error;
+        }
+        if self.report_const_assignment(&compound_expr.expression_one, compound_expr.span) {
+            return Type::Error;
+        }
+        if lhs_type.is_error() || rhs_type.is_error() {
+            return Type::Error;
+        }
+
+        let binary_operator = compound_expr.binary_operator();
+        let Some(result_type) = binary_result_type(binary_operator, &lhs_type, &rhs_type) else {
+            self.report(
+                Diagnostic::error(
+                    compound_expr.span,
+                    format!("incompatible type for this binary operator: {}", binary_operator),
+                )
+                .with_code("*9")
+                .with_label(
+                    compound_expr.operator.span,
+                    format!("{} {} {}", lhs_type, operator, rhs_type),
+                ),
+            );
+            return Type::Error;
+        };
+
+        if !lhs_type.is_assignable_from(&result_type) {
+            self.report(
+                Diagnostic::error(compound_expr.span, format!("incompatible type for {}", operator))
+                    .with_code("*6")
+                    .with_primary_label(format!(
+                        "cannot assign {} to {}",
+                        result_type, lhs_type
+                    )),
+            );
+            return Type::Error;
+        }
+
+        lhs_type
+    }
+
+    // ++ and --, prefix or postfix: the operand must be a numeric variable or array element.
+    fn visit_increment(&mut self, operand: &ExprType, operator: &str, span: Span) -> Type {
+        let operand_type = self.visit_expr(operand);
+
+        if !is_lvalue(operand) || operand_type.is_array() {
+            self.report_invalid_lvalue(operand, span);
+            return Type::Error;
+        }
+        if self.report_const_assignment(operand, span) {
+            return Type::Error;
+        }
+        if operand_type.is_error() {
+            return Type::Error;
+        }
+        if !operand_type.promoted().is_numeric() {
+            self.report(
+                Diagnostic::error(
+                    span,
+                    format!("incompatible type for this unary operator: {}", operator),
+                )
+                .with_code("*10")
+                .with_primary_label(format!("operand is {}", operand_type)),
+            );
+            return Type::Error;
+        }
+
+        operand_type
+    }
+
+    // The value of an int expression built only from literals and const ints, e.g. the label in
+    // case -(2 * 3): or the size in int a[N + 1].
+    fn constant_int(&self, expr: &ExprType) -> Option<i64> {
+        match expr {
+            ExprType::IntExpr(int_expr) => int_expr
+                .int_literal
+                .value
+                .and_then(|value| i64::try_from(value).ok()),
+            ExprType::CharExpr(char_expr) => char_expr
+                .char_literal
+                .value()
+                .map(|c| i64::from(u32::from(c))),
+            ExprType::VarExpr(var_expr) => self
+                .symbol_table
+                .lookup(&var_expr.var.ident.spelling)
+                .and_then(|symbol| symbol.value),
+            ExprType::ParenExpr(paren_expr) => self.constant_int(&paren_expr.expr),
+            // The length of an array whose size is known; a parameter's is only known at run time.
+            ExprType::CallExpr(call_expr)
+                if call_expr.ident.spelling == LENGTH_BUILTIN.0
+                    && self.symbol_table.lookup(LENGTH_BUILTIN.0).is_none()
+                    && !self.functions.contains_key(LENGTH_BUILTIN.0) =>
+            {
+                match argument_exprs(&call_expr.argument_list)[..] {
+                    [array] => self.array_size(array).and_then(|size| i64::try_from(size).ok()),
+                    _ => None,
+                }
+            }
+            ExprType::CastExpr(cast_expr)
+                if matches!(*cast_expr.cast_type, AstTypes::IntType(_)) =>
+            {
+                self.constant_int(&cast_expr.expression)
+            }
+            ExprType::UnaryExpr(unary_expr) => {
+                let operand = self.constant_int(&unary_expr.expression)?;
+                match unary_expr.operator.spelling.as_str() {
+                    "+" => Some(operand),
+                    "-" => operand.checked_neg(),
+                    "~" => Some(!operand),
+                    _ => None,
+                }
+            }
+            ExprType::BinaryExpr(binary_expr) => {
+                let lhs = self.constant_int(&binary_expr.expression_one)?;
+                let rhs = self.constant_int(&binary_expr.expression_two)?;
+                match binary_expr.operator.spelling.as_str() {
+                    "+" => lhs.checked_add(rhs),
+                    "-" => lhs.checked_sub(rhs),
+                    "*" => lhs.checked_mul(rhs),
+                    "/" => lhs.checked_div(rhs),
+                    "%" => lhs.checked_rem(rhs),
+                    "&" => Some(lhs & rhs),
+                    "|" => Some(lhs | rhs),
+                    "^" => Some(lhs ^ rhs),
+                    "<<" => lhs.checked_shl(u32::try_from(rhs).ok()?),
+                    ">>" => lhs.checked_shr(u32::try_from(rhs).ok()?),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    // The declared size of an array variable, or of a row of one, without visiting it.
+    fn array_size(&self, expr: &ExprType) -> Option<usize> {
+        match self.declared_type(expr)? {
+            Type::Array { size, .. } => size,
+            _ => None,
+        }
+    }
+
+    fn declared_type(&self, expr: &ExprType) -> Option<Type> {
+        match expr {
+            ExprType::VarExpr(var_expr) => self
+                .symbol_table
+                .lookup(&var_expr.var.ident.spelling)
+                .filter(|symbol| !symbol.is_function())
+                .map(|symbol| symbol.symbol_type.clone()),
+            ExprType::ArrayExpr(array_expr) => {
+                self.declared_type(&array_expr.array)?.element_type().cloned()
+            }
+            ExprType::Arg(arg) => self.declared_type(&arg.expr),
+            ExprType::ParenExpr(paren_expr) => self.declared_type(&paren_expr.expr),
+            _ => None,
+        }
+    }
+
+    fn report_invalid_lvalue(&mut self, expr: &ExprType, span: Span) {
+        let message = match variable_name(expr) {
+            Some(name) => format!("invalid lvalue in assignment: {}", name),
+            None => String::from("invalid lvalue in assignment"),
+        };
+        self.report(Diagnostic::error(span, message).with_code("*7"));
+    }
+
+    // Reports a write to a const variable, or to an element or field of one.
+    fn report_const_assignment(&mut self, target: &ExprType, span: Span) -> bool {
+        let Some(symbol) = self.const_variable(target) else {
+            return false;
+        };
+        self.report(const_assignment_diagnostic(&symbol, span));
+        true
+    }
+
+    fn const_variable(&self, target: &ExprType) -> Option<Symbol> {
+        assigned_variable(target)
+            .and_then(|ident| self.symbol_table.lookup(&ident.spelling))
+            .filter(|symbol| symbol.is_const)
+            .cloned()
+    }
+
+    fn visit_conditional_expr(&mut self, conditional_expr: &ConditionalExpr) -> Type {
+        self.visit_condition(&conditional_expr.condition, "?:");
+        let true_type = self.visit_scalar_expr(&conditional_expr.expression_one);
+        let false_type = self.visit_scalar_expr(&conditional_expr.expression_two);
+        if true_type.is_error() || false_type.is_error() {
+            return Type::Error;
+        }
+
+        let (true_promoted, false_promoted) = (true_type.promoted(), false_type.promoted());
+        if true_type == false_type {
+            true_type
+        } else if true_promoted == false_promoted {
+            true_promoted
+        } else if true_promoted.is_numeric() && false_promoted.is_numeric() {
+            Type::Float
+        } else {
+            self.report(
+                Diagnostic::error(
+                    conditional_expr.span,
+                    "incompatible types for the branches of ?:",
+                )
+                .with_code("*36")
+                .with_label(
+                    *conditional_expr.expression_one.get_span(),
+                    format!("this is {}", true_type),
+                )
+                .with_label(
+                    *conditional_expr.expression_two.get_span(),
+                    format!("this is {}", false_type),
+                ),
+            );
+            Type::Error
+        }
+    }
+
+    fn visit_binary_expr(&mut self, binary_expr: &BinaryExpr) -> Type {
+        let lhs_type = self.visit_scalar_expr(&binary_expr.expression_one);
+        let rhs_type = self.visit_scalar_expr(&binary_expr.expression_two);
+        if lhs_type.is_error() || rhs_type.is_error() {
+            return Type::Error;
+        }
+
+        let operator = &binary_expr.operator.spelling;
+        match binary_result_type(operator, &lhs_type, &rhs_type) {
+            Some(result_type) => result_type,
+            None => {
+                self.report(
+                    Diagnostic::error(
+                        binary_expr.span,
+                        format!("incompatible type for this binary operator: {}", operator),
+                    )
+                    .with_code("*9")
+                    .with_label(
+                        binary_expr.operator.span,
+                        format!("{} {} {}", lhs_type, operator, rhs_type),
+                    ),
+                );
+                Type::Error
+            }
+        }
+    }
+
+    fn visit_unary_expr(&mut self, unary_expr: &UnaryExpr) -> Type {
+        let operator = &unary_expr.operator.spelling;
+        if operator == "++" || operator == "--" {
+            return self.visit_increment(&unary_expr.expression, operator, unary_expr.span);
+        }
+
+        let operand_type = match unary_expr.expression.as_ref() {
+            ExprType::IntExpr(int_expr) if operator == "-" => {
+                self.visit_int_literal(&int_expr.int_literal, true)
+            }
+            expression => self.visit_scalar_expr(expression),
+        };
+        if operand_type.is_error() {
+            return Type::Error;
+        }
+
+        let promoted = operand_type.promoted();
+        let result_type = match operator.as_str() {
+            "+" | "-" if promoted.is_numeric() => Some(promoted),
+            "!" if operand_type == Type::Boolean => Some(Type::Boolean),
+            "~" if promoted == Type::Int => Some(Type::Int),
+            _ => None,
+        };
+
+        result_type.unwrap_or_else(|| {
+            self.report(
+                Diagnostic::error(
+                    unary_expr.span,
+                    format!("incompatible type for this unary operator: {}", operator),
+                )
+                .with_code("*10")
+                .with_primary_label(format!("operand is {}", operand_type)),
+            );
+            Type::Error
+        })
+    }
+
+    // length(a) takes an array of any element type, which no parameter type can say, so it is
+    // checked here rather than declared with the other built-ins.
+    fn visit_length_call(&mut self, call_expr: &CallExpr, arguments: &[&ExprType]) -> Type {
+        let (name, parameters) = LENGTH_BUILTIN;
+        let takes = format!("{} takes {} parameter(s)", name, parameters.len());
+        let Some((array, extras)) = arguments.split_first() else {
+            self.report(
+                Diagnostic::error(call_expr.span, "too few actual parameters")
+                    .with_code("*26")
+                    .with_note(takes),
+            );
+            return Type::Int;
+        };
+
+        let array_type = self.visit_expr(array);
+        if !array_type.is_array() && !array_type.is_error() {
+            self.report(
+                Diagnostic::error(
+                    *array.get_span(),
+                    format!("wrong type for actual parameter: {}", parameters[0]),
+                )
+                .with_code("*27")
+                .with_primary_label(format!("expected an array, found {}", array_type)),
+            );
+        }
+        if let Some(extra) = extras.first() {
+            self.report(
+                Diagnostic::error(*extra.get_span(), "too many actual parameters")
+                    .with_code("*25")
+                    .with_note(takes),
+            );
+        }
+        for extra in extras {
+            self.visit_expr(extra);
+        }
+        Type::Int
+    }
+
+    fn visit_call_expr(&mut self, call_expr: &CallExpr) -> Type {
+        let ident = &call_expr.ident;
+        let arguments = argument_exprs(&call_expr.argument_list);
+
+        let symbol = self
+            .symbol_table
+            .lookup(&ident.spelling)
+            .or_else(|| self.functions.get(&ident.spelling))
+            .cloned();
+        if symbol.is_none() && ident.spelling == LENGTH_BUILTIN.0 {
+            return self.visit_length_call(call_expr, &arguments);
+        }
+        if let Some(symbol) = &symbol {
+            self.resolve(ident, symbol);
+        }
+        let (parameters, return_type) = match symbol {
+            Some(Symbol {
+                kind: SymbolKind::Function { parameters },
+                symbol_type,
+                ..
+            }) => (parameters, symbol_type),
+            Some(symbol) => {
+                self.report(
+                    Diagnostic::error(
+                        ident.span,
+                        format!(
+                            "attempt to reference a scalar/array as a function: {}",
+                            ident.spelling
+                        ),
+                    )
+                    .with_code("*19")
+                    .with_label_if_declared(&symbol),
+                );
+                arguments.iter().for_each(|argument| {
+                    self.visit_expr(argument);
+                });
+                return Type::Error;
+            }
+            None => {
+                self.report_undeclared(ident);
+                arguments.iter().for_each(|argument| {
+                    self.visit_expr(argument);
+                });
+                return Type::Error;
+            }
+        };
+
+        for (index, argument) in arguments.iter().enumerate() {
+            let argument_type = self.visit_expr(argument);
+            let Some(parameter) = parameters.get(index) else {
+                self.report(
+                    Diagnostic::error(
+                        *argument.get_span(),
+                        "too many actual parameters",
+                    )
+                    .with_code("*25")
+                    .with_note(format!(
+                        "{} takes {} parameter(s)",
+                        ident.spelling,
+                        parameters.len()
+                    )),
+                );
+                break;
+            };
+
+            let parameter_type = &parameter.parameter_type;
+            let compatible = argument_type.is_array() == parameter_type.is_array()
+                && parameter_type.is_assignable_from(&argument_type);
+            if !compatible && !argument_type.is_error() && !parameter_type.is_error() {
+                self.report(
+                    Diagnostic::error(
+                        *argument.get_span(),
+                        format!("wrong type for actual parameter: {}", parameter.name),
+                    )
+                    .with_code("*27")
+                    .with_primary_label(format!(
+                        "expected {}, found {}",
+                        parameter_type, argument_type
+                    )),
+                );
+            } else if compatible && parameter_type.is_array() && !parameter.is_const {
+                // Arrays are passed by reference, so the callee could write to a const one.
+                if let Some(symbol) = self.const_variable(argument) {
+                    self.report(
+                        const_assignment_diagnostic(&symbol, *argument.get_span()).with_note(
+                            format!(
+                                "parameter {} of {} is not const",
+                                parameter.name, ident.spelling
+                            ),
+                        ),
+                    );
+                }
+            }
+        }
+
+        if arguments.len() < parameters.len() {
+            self.report(
+                Diagnostic::error(call_expr.span, "too few actual parameters")
+                    .with_code("*26")
+                    .with_note(format!(
+                        "{} takes {} parameter(s)",
+                        ident.spelling,
+                        parameters.len()
+                    )),
+            );
+        }
+
+        return_type
+    }
+
+    fn report_undeclared(&mut self, ident: &Ident) {
+        self.report(
+            Diagnostic::error(
+                ident.span,
+                format!("identifier undeclared: {}", ident.spelling),
+            )
+            .with_code("*5"),
+        );
+    }
+}
+
+trait DeclaredHereLabel {
+    fn with_label_if_declared(self, symbol: &Symbol) -> Self;
+}
+
+impl DeclaredHereLabel for Diagnostic {
+    fn with_label_if_declared(self, symbol: &Symbol) -> Self {
+        match symbol.position {
+            Some(position) => self.with_label(
+                position,
+                format!(
+                    "{} declared here as {}",
+                    symbol.name,
+                    describe_symbol(symbol)
+                ),
+            ),
+            None => self,
+        }
+    }
+}
+
+fn describe_symbol(symbol: &Symbol) -> String {
+    match symbol.kind {
+        SymbolKind::Function { .. } => format!("a function returning {}", symbol.symbol_type),
+        _ => symbol.symbol_type.to_string(),
+    }
+}
+
+fn void_declaration_error(declared_type: &Type, ident: &Ident) -> Option<Diagnostic> {
+    let (code, message) = match declared_type {
+        Type::Void => (
+            "*3",
+            format!("identifier declared void: {}", ident.spelling),
+        ),
+        Type::Array { element, .. } if **element == Type::Void => (
+            "*4",
+            format!("identifier declared void[]: {}", ident.spelling),
+        ),
+        _ => return None,
+    };
+    Some(Diagnostic::error(ident.span, message).with_code(code))
+}
+
+// VC's arithmetic, relational, equality and logical operators.  None marks an illegal pairing.
+fn binary_result_type(operator: &str, lhs_type: &Type, rhs_type: &Type) -> Option<Type> {
+    let (lhs_type, rhs_type) = (&lhs_type.promoted(), &rhs_type.promoted());
+    let both_numeric = lhs_type.is_numeric() && rhs_type.is_numeric();
+    let both_boolean = *lhs_type == Type::Boolean && *rhs_type == Type::Boolean;
+    let both_int = *lhs_type == Type::Int && *rhs_type == Type::Int;
+    let both_string = *lhs_type == Type::String && *rhs_type == Type::String;
+
+    match operator {
+        "+" if both_string => Some(Type::String),
+        "+" | "-" | "*" | "/" if both_numeric => {
+            if *lhs_type == Type::Int && *rhs_type == Type::Int {
+                Some(Type::Int)
+            } else {
+                Some(Type::Float)
+            }
+        }
+        "<" | "<=" | ">" | ">=" if both_numeric => Some(Type::Boolean),
+        "==" | "!=" if both_numeric || both_boolean || both_string => Some(Type::Boolean),
+        "&&" | "||" if both_boolean => Some(Type::Boolean),
+        "%" | "&" | "|" | "^" | "<<" | ">>" if both_int => Some(Type::Int),
+        _ => None,
+    }
+}
+
+// The number of elements in an array initialiser, counting each row of a matrix as one.
+fn initialiser_length(init_expr: &ExprType) -> Option<usize> {
+    let ExprType::ArrayInitExpr(array_init) = init_expr else {
+        return None;
+    };
+    let mut length = 0;
+    let mut current = &*array_init.init_list;
+    while let ListType::ArrayExprList(list) = current {
+        length += 1;
+        current = &list.expr_list;
+    }
+    Some(length)
+}
+
+fn const_assignment_diagnostic(symbol: &Symbol, span: Span) -> Diagnostic {
+    let diagnostic = Diagnostic::error(
+        span,
+        format!("attempt to assign to a const: {}", symbol.name),
+    )
+    .with_code("*43");
+    match symbol.position {
+        Some(position) => {
+            diagnostic.with_label(position, format!("{} declared const here", symbol.name))
+        }
+        None => diagnostic,
+    }
+}
+
+// The variable an lvalue, or an array argument, writes to or into.
+fn assigned_variable(expr: &ExprType) -> Option<&Ident> {
+    match expr {
+        ExprType::Arg(arg) => assigned_variable(&arg.expr),
+        ExprType::VarExpr(var_expr) => Some(&var_expr.var.ident),
+        ExprType::ArrayExpr(array_expr) => assigned_variable(&array_expr.array),
+        ExprType::FieldExpr(field_expr) => assigned_variable(&field_expr.expression),
+        ExprType::ParenExpr(paren_expr) => assigned_variable(&paren_expr.expr),
+        _ => None,
+    }
+}
+
+fn is_lvalue(expr: &ExprType) -> bool {
+    match expr {
+        ExprType::VarExpr(_) | ExprType::ArrayExpr(_) => true,
+        ExprType::ParenExpr(paren_expr) => is_lvalue(&paren_expr.expr),
+        ExprType::FieldExpr(field_expr) => is_lvalue(&field_expr.expression),
+        _ => false,
+    }
+}
+
+fn variable_name(expr: &ExprType) -> Option<&str> {
+    match expr {
+        ExprType::VarExpr(var_expr) => Some(&var_expr.var.ident.spelling),
+        ExprType::ParenExpr(paren_expr) => variable_name(&paren_expr.expr),
+        _ => None,
+    }
+}
+
+// A return on every path through the statement.
+fn always_returns(stmt: &StmtType) -> bool {
+    match stmt {
+        StmtType::ReturnStmt(_) => true,
+        StmtType::IfStmt(if_stmt) => {
+            always_returns(&if_stmt.stmt_1) && always_returns(&if_stmt.stmt_2)
+        }
+        // The body of a do-while runs at least once.
+        StmtType::DoWhileStmt(do_while_stmt) => always_returns(&do_while_stmt.stmt),
+        // Every value is handled, cases fall through to the last one, and nothing breaks out.
+        StmtType::SwitchStmt(switch_stmt) => {
+            let mut has_default = false;
+            let mut last_returns = false;
+            let mut current = &*switch_stmt.case_list;
+            while let ListType::CaseList(list) = current {
+                let stmt_list = &list.case_stmt.stmt_list;
+                if statements(stmt_list).into_iter().any(breaks_out) {
+                    return false;
+                }
+                has_default |= list.case_stmt.is_default();
+                last_returns = stmt_list_returns(stmt_list);
+                current = &list.case_list;
+            }
+            has_default && last_returns
+        }
+        StmtType::CompoundStmt(compound_stmt) => stmt_list_returns(&compound_stmt.stmt_list),
+        _ => false,
+    }
+}
+
+fn stmt_list_returns(stmt_list: &ListType) -> bool {
+    statements(stmt_list).into_iter().any(always_returns)
+}
+
+// Whether the statement can break out of the switch it is in.  Loops and switches inside it take
+// their own breaks.
+fn breaks_out(stmt: &StmtType) -> bool {
+    match stmt {
+        StmtType::BreakStmt(_) => true,
+        StmtType::CompoundStmt(compound_stmt) => statements(&compound_stmt.stmt_list)
+            .into_iter()
+            .any(breaks_out),
+        StmtType::IfStmt(if_stmt) => breaks_out(&if_stmt.stmt_1) || breaks_out(&if_stmt.stmt_2),
+        _ => false,
+    }
+}
+
+fn statements(stmt_list: &ListType) -> Vec<&StmtType> {
+    let mut stmts = Vec::new();
+    let mut current = stmt_list;
+    while let ListType::StmtList(list) = current {
+        stmts.push(&*list.stmt);
+        current = &list.stmt_list;
+    }
+    stmts
+}
+
+fn last_stmt_span(stmt_list: &ListType) -> Span {
+    let mut current = stmt_list;
+    let mut last = Span::default();
+    while let ListType::StmtList(list) = current {
+        last = *list.stmt.get_span();
+        current = &list.stmt_list;
+    }
+    last
+}
+
+// The parameters and return type of a function, as its declaration spells them.
+fn function_signature(func_decl: &FuncDecl) -> (Vec<Parameter>, Type) {
+    let parameters = parameter_decls(&func_decl.param_list)
+        .into_iter()
+        .map(|para_decl| Parameter {
+            name: para_decl.ident.spelling.clone(),
+            parameter_type: Type::from_type_variant(&para_decl.declaration_type),
+            is_const: para_decl.is_const,
+        })
+        .collect();
+    (parameters, Type::from_type_variant(&func_decl.function_type))
+}
+
+fn parameter_decls(param_list: &ListType) -> Vec<&ParaDecl> {
+    let mut para_decls = Vec::new();
+    let mut current = param_list;
+    while let ListType::ParamList(list) = current {
+        para_decls.push(&list.param);
+        current = &list.param_list;
+    }
+    para_decls
+}
+
+fn argument_exprs(argument_list: &ListType) -> Vec<&ExprType> {
+    let mut arguments = Vec::new();
+    let mut current = argument_list;
+    while let ListType::ArgList(list) = current {
+        arguments.push(&list.arg);
+        current = &list.arg_list;
+    }
+    arguments
+}
@@ -0,0 +1,166 @@
+use std::fmt;
+
+use crate::ast::array_type::AstTypeVariant;
+use crate::ast::expression::ExprType;
+use crate::ast::primitive_types::AstTypes;
+
+/*
+   Semantic types
+   The AST records types as written (AstTypes/AstTypeVariant, each with a position).  The checker
+   works with Type, which has no positions and can be compared and promoted directly.
+*/
+#[derive(Clone, Debug, PartialEq)]
+pub enum Type {
+    Int,
+    Float,
+    Boolean,
+    Char,
+    // A string is an immutable sequence of chars, held as its length and its bytes, with no
+    // terminator.  Assigning or passing one shares the value; + builds a new string, and == and
+    // != compare contents.  charAt(s, i) and substring(s, start, end) index from 0, end exclusive,
+    // and an index outside the string is a runtime error.
+    String,
+    // Structs are compared by name; the checker keeps their fields.
+    Struct(String),
+    Void,
+    Array {
+        element: Box<Type>,
+        size: Option<usize>,
+    },
+    // Given to an expression that has already been reported, so one mistake is reported once.
+    Error,
+}
+
+// The explicit conversions a cast may make, from the first type to the second.  A float cast to
+// int truncates towards zero; a boolean is 0 or 1 as an int, and an int is true unless it is 0.
+// Every type may also be cast to itself.
+const LEGAL_CASTS: [(Type, Type); 6] = [
+    (Type::Int, Type::Float),
+    (Type::Float, Type::Int),
+    (Type::Int, Type::Boolean),
+    (Type::Boolean, Type::Int),
+    (Type::Int, Type::Char),
+    (Type::Char, Type::Int),
+];
+
+impl Type {
+    pub fn from_ast_type(ast_type: &AstTypes) -> Self {
+        match ast_type {
+            AstTypes::IntType(_) => Type::Int,
+            AstTypes::FloatType(_) => Type::Float,
+            AstTypes::BooleanType(_) => Type::Boolean,
+            AstTypes::CharType(_) => Type::Char,
+            AstTypes::StringType(_) => Type::String,
+            AstTypes::StructType(struct_type) => Type::Struct(struct_type.ident.spelling.clone()),
+            AstTypes::VoidType(_) => Type::Void,
+            AstTypes::ErrorType(_) => Type::Error,
+        }
+    }
+
+    pub fn from_type_variant(type_variant: &AstTypeVariant) -> Self {
+        match type_variant {
+            AstTypeVariant::Primitive(primitive_type) => Type::from_ast_type(primitive_type),
+            AstTypeVariant::Array(array_type) => {
+                let size = match &array_type.expression {
+                    ExprType::IntExpr(int_expr) => int_expr
+                        .int_literal
+                        .value
+                        .and_then(|value| usize::try_from(value).ok()),
+                    _ => None,
+                };
+                Type::Array {
+                    element: Box::new(Type::from_type_variant(&array_type.array_type)),
+                    size,
+                }
+            }
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        matches!(self, Type::Error)
+    }
+
+    pub fn is_numeric(&self) -> bool {
+        matches!(self, Type::Int | Type::Float)
+    }
+
+    // Chars take part in arithmetic and comparisons as their int codes.
+    pub fn promoted(&self) -> Type {
+        match self {
+            Type::Char => Type::Int,
+            other => other.clone(),
+        }
+    }
+
+    pub fn is_array(&self) -> bool {
+        matches!(self, Type::Array { .. })
+    }
+
+    pub fn element_type(&self) -> Option<&Type> {
+        match self {
+            Type::Array { element, .. } => Some(element),
+            _ => None,
+        }
+    }
+
+    // The scalar type at the bottom of any array dimensions.
+    pub fn base_type(&self) -> &Type {
+        match self {
+            Type::Array { element, .. } => element.base_type(),
+            other => other,
+        }
+    }
+
+    // The size of each dimension, outermost first; empty for a scalar.
+    pub fn dimensions(&self) -> Vec<Option<usize>> {
+        let mut dimensions = Vec::new();
+        let mut current = self;
+        while let Type::Array { element, size } = current {
+            dimensions.push(*size);
+            current = element;
+        }
+        dimensions
+    }
+
+    // The "[3][4]" that follows a declared name, as C writes array types.
+    pub fn dimension_suffix(&self) -> String {
+        self.dimensions()
+            .iter()
+            .map(|size| match size {
+                Some(size) => format!("[{}]", size),
+                None => String::from("[]"),
+            })
+            .collect()
+    }
+
+    pub fn can_cast_to(&self, target: &Type) -> bool {
+        self == target || LEGAL_CASTS.contains(&(self.clone(), target.clone()))
+    }
+
+    // VC allows identical types, int to float promotion, and conversion between int and char.
+    pub fn is_assignable_from(&self, other: &Type) -> bool {
+        match (self, other) {
+            (Type::Error, _) | (_, Type::Error) => true,
+            (Type::Float, Type::Int) => true,
+            (Type::Int, Type::Char) | (Type::Char, Type::Int) => true,
+            (Type::Array { element: to, .. }, Type::Array { element: from, .. }) => to == from,
+            (to, from) => to == from,
+        }
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::Int => write!(f, "int"),
+            Type::Float => write!(f, "float"),
+            Type::Boolean => write!(f, "boolean"),
+            Type::Char => write!(f, "char"),
+            Type::String => write!(f, "string"),
+            Type::Struct(name) => write!(f, "struct {}", name),
+            Type::Void => write!(f, "void"),
+            Type::Array { .. } => write!(f, "{}{}", self.base_type(), self.dimension_suffix()),
+            Type::Error => write!(f, "error"),
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+
+use crate::checker::semantic_types::Type;
+use crate::source_map::Span;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SymbolKind {
+    Variable,
+    Parameter,
+    // The parameters in declaration order.
+    Function { parameters: Vec<Parameter> },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Parameter {
+    pub name: String,
+    pub parameter_type: Type,
+    // A const array parameter promises not to write through to the caller's array.
+    pub is_const: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Symbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    // For a function this is its return type.
+    pub symbol_type: Type,
+    // None for the built-in functions, which have no declaration in the source.
+    pub position: Option<Span>,
+    // A const variable or parameter may not be assigned to after its declaration.
+    pub is_const: bool,
+    // The value of a const int initialised by a constant expression, which may size an array.
+    pub value: Option<i64>,
+}
+
+impl Symbol {
+    pub fn is_function(&self) -> bool {
+        matches!(self.kind, SymbolKind::Function { .. })
+    }
+}
+
+// A built-in function's name, return type and parameters.
+type Builtin<'a> = (&'a str, Type, &'a [(&'a str, Type)]);
+
+/*
+   SymbolTable
+   A stack of scopes.  Scope 0 holds the VC standard environment, scope 1 the program's globals,
+   and every function body and nested compound statement pushes another.
+*/
+#[derive(Debug)]
+pub struct SymbolTable {
+    scopes: Vec<HashMap<String, Symbol>>,
+}
+
+impl SymbolTable {
+    pub fn new() -> Self {
+        let mut symbol_table = Self {
+            scopes: vec![HashMap::new()],
+        };
+        symbol_table.declare_builtins();
+        symbol_table.open_scope();
+        symbol_table
+    }
+
+    pub fn open_scope(&mut self) {
+        self.scopes.push(HashMap::new());
+    }
+
+    pub fn close_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    // Declares into the innermost scope, returning any symbol it replaces from that same scope.
+    pub fn insert(&mut self, symbol: Symbol) -> Option<Symbol> {
+        self.scopes
+            .last_mut()
+            .expect("Symbol table always has a scope open.")
+            .insert(symbol.name.clone(), symbol)
+    }
+
+    pub fn lookup(&self, name: &str) -> Option<&Symbol> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(name))
+    }
+
+    fn declare_builtins(&mut self) {
+        let builtins: [Builtin; 17] = [
+            ("getInt", Type::Int, &[]),
+            ("putInt", Type::Void, &[("i", Type::Int)]),
+            ("putIntLn", Type::Void, &[("i", Type::Int)]),
+            ("getFloat", Type::Float, &[]),
+            ("putFloat", Type::Void, &[("f", Type::Float)]),
+            ("putFloatLn", Type::Void, &[("f", Type::Float)]),
+            ("putBool", Type::Void, &[("b", Type::Boolean)]),
+            ("putBoolLn", Type::Void, &[("b", Type::Boolean)]),
+            ("getChar", Type::Char, &[]),
+            ("putChar", Type::Void, &[("c", Type::Char)]),
+            ("getString", Type::String, &[]),
+            ("putString", Type::Void, &[("s", Type::String)]),
+            ("putStringLn", Type::Void, &[("s", Type::String)]),
+            ("strlen", Type::Int, &[("s", Type::String)]),
+            ("charAt", Type::Char, &[("s", Type::String), ("i", Type::Int)]),
+            (
+                "substring",
+                Type::String,
+                &[("s", Type::String), ("start", Type::Int), ("end", Type::Int)],
+            ),
+            ("putLn", Type::Void, &[]),
+        ];
+
+        for (name, return_type, parameters) in builtins {
+            let parameters = parameters
+                .iter()
+                .map(|(parameter_name, parameter_type)| Parameter {
+                    name: parameter_name.to_string(),
+                    parameter_type: parameter_type.clone(),
+                    is_const: false,
+                })
+                .collect();
+            self.insert(Symbol {
+                name: name.to_string(),
+                kind: SymbolKind::Function { parameters },
+                symbol_type: return_type,
+                position: None,
+                is_const: false,
+                value: None,
+            });
+        }
+    }
+}
+
+impl Default for SymbolTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
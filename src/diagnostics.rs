@@ -0,0 +1,664 @@
+use std::fmt;
+use std::io::{IsTerminal, Write};
+use std::path::Path;
+
+use serde_json::{json, Value};
+
+use crate::globals::TAB_SIZE;
+use crate::source_map::{ColumnMode, SourceFile, SourceMap, Span};
+
+/*
+   Diagnostics
+   Every phase (scanner, parser, checker) describes what went wrong as a Diagnostic.
+   DiagnosticEmitter is the single place diagnostics are turned into text.  By default that is
+   the human format, e.g.
+
+   error[*2]: identifier redeclared: x
+     --> t1.vc:29:5
+      |
+   28 | int x;
+      |     - previously declared here
+   29 | int x;
+      |     ^
+      = note: ...
+
+   while --error-format=json writes one JSON object per diagnostic and --error-format=sarif writes
+   a single SARIF 2.1.0 log once the run is finished.  Both go to stderr, like the human format.
+
+   Every diagnostic carries a stable code.  The checker uses the VC reference compiler's "*N"
+   numbering, and the earlier phases use their own short series; see ERROR_CODES.
+*/
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+// A secondary span shown alongside the primary one, e.g. "previously declared here".
+#[derive(Clone, Debug, PartialEq)]
+pub struct Label {
+    pub span: Span,
+    pub message: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+    pub code: Option<&'static str>,
+    pub span: Span,
+    pub primary_label: Option<String>,
+    pub secondary_labels: Vec<Label>,
+    pub notes: Vec<String>,
+}
+
+impl Diagnostic {
+    pub fn new(severity: Severity, span: Span, message: impl Into<String>) -> Self {
+        Self {
+            severity,
+            message: message.into(),
+            code: None,
+            span,
+            primary_label: None,
+            secondary_labels: Vec::new(),
+            notes: Vec::new(),
+        }
+    }
+
+    pub fn error(span: Span, message: impl Into<String>) -> Self {
+        Self::new(Severity::Error, span, message)
+    }
+
+    pub fn warning(span: Span, message: impl Into<String>) -> Self {
+        Self::new(Severity::Warning, span, message)
+    }
+
+    pub fn with_code(mut self, code: &'static str) -> Self {
+        self.code = Some(code);
+        self
+    }
+
+    pub fn with_primary_label(mut self, message: impl Into<String>) -> Self {
+        self.primary_label = Some(message.into());
+        self
+    }
+
+    pub fn with_label(mut self, span: Span, message: impl Into<String>) -> Self {
+        self.secondary_labels.push(Label {
+            span,
+            message: message.into(),
+        });
+        self
+    }
+
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.notes.push(note.into());
+        self
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum ColorChoice {
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    // Auto colours only when stderr is a terminal and NO_COLOR is unset.
+    pub fn should_color_stderr(&self) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                std::env::var_os("NO_COLOR").is_none() && std::io::stderr().is_terminal()
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum ErrorFormat {
+    Human,
+    Json,
+    Sarif,
+}
+
+// How diagnostics are written out, as chosen on the command line.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DiagnosticConfig {
+    pub color: ColorChoice,
+    pub error_format: ErrorFormat,
+}
+
+impl Default for DiagnosticConfig {
+    fn default() -> Self {
+        Self {
+            color: ColorChoice::Auto,
+            error_format: ErrorFormat::Human,
+        }
+    }
+}
+
+// Every code vc can report, with a short description used for the SARIF rule table.
+pub const ERROR_CODES: &[(&str, &str)] = &[
+    ("S1", "illegal character"),
+    ("S2", "unterminated comment"),
+    ("S3", "unterminated string"),
+    ("S4", "illegal escape character"),
+    ("S5", "malformed directive"),
+    ("S6", "unterminated character literal"),
+    ("S7", "invalid character literal"),
+    ("S8", "malformed numeric literal"),
+    ("D1", "malformed preprocessor directive"),
+    ("D2", "macro redefined"),
+    ("D3", "unbalanced conditional directive"),
+    ("D4", "macro defined in an included file"),
+    ("P1", "syntax error"),
+    ("I1", "cannot read included file"),
+    ("I2", "include cycle"),
+    ("I3", "cannot read input file"),
+    ("*0", "main function is missing"),
+    ("*1", "return type of main is not int"),
+    ("*2", "identifier redeclared"),
+    ("*3", "identifier declared void"),
+    ("*4", "identifier declared void[]"),
+    ("*5", "identifier undeclared"),
+    ("*6", "incompatible type for ="),
+    ("*7", "invalid lvalue in assignment"),
+    ("*8", "incompatible type for return"),
+    ("*9", "incompatible type for this binary operator"),
+    ("*10", "incompatible type for this unary operator"),
+    ("*11", "attempt to use an array/function as a scalar"),
+    ("*12", "attempt to use a scalar/function as an array"),
+    ("*13", "wrong type for element in array initialiser"),
+    ("*14", "invalid initialiser: array initialiser for scalar"),
+    ("*15", "invalid initialiser: scalar initialiser for array"),
+    ("*16", "excess elements in array initialiser"),
+    ("*17", "array subscript is not an integer"),
+    ("*18", "array size missing"),
+    ("*19", "attempt to reference a scalar/array as a function"),
+    ("*20", "if conditional is not boolean"),
+    ("*21", "for conditional is not boolean"),
+    ("*22", "while conditional is not boolean"),
+    ("*23", "break must be in a loop or switch"),
+    ("*24", "continue must be in a while/for"),
+    ("*25", "too many actual parameters"),
+    ("*26", "too few actual parameters"),
+    ("*27", "wrong type for actual parameter"),
+    ("*30", "statement(s) not reached"),
+    ("*31", "missing return statement"),
+    ("*32", "switch expression is not int"),
+    ("*33", "case label is not an int constant"),
+    ("*34", "duplicate case label"),
+    ("*35", "?: conditional is not boolean"),
+    ("*36", "incompatible types for the branches of ?:"),
+    ("*37", "struct undeclared"),
+    ("*38", "attempt to access a field of a non-struct"),
+    ("*39", "no such field"),
+    ("*40", "declaration does not match prototype"),
+    ("*41", "function declared but never defined"),
+    ("*42", "const declaration without an initialiser"),
+    ("*43", "attempt to assign to a const"),
+    ("*44", "array size is not an int constant"),
+    ("*45", "invalid cast"),
+    ("*46", "integer literal too large"),
+    ("*47", "float literal too large"),
+    ("*48", "array index out of bounds"),
+];
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "\x1b[1m";
+const RED: &str = "\x1b[1;31m";
+const YELLOW: &str = "\x1b[1;33m";
+const BLUE: &str = "\x1b[1;34m";
+
+// Columns shown to people and written to JSON and SARIF count characters.  Carets are placed
+// under tab-expanded source, so they count tab stops instead.
+const DISPLAY_COLUMNS: ColumnMode = ColumnMode::Utf8;
+const CARET_COLUMNS: ColumnMode = ColumnMode::Tabs(TAB_SIZE as usize);
+
+pub struct DiagnosticEmitter<'a> {
+    source_map: &'a SourceMap,
+    color: bool,
+    error_format: ErrorFormat,
+    // SARIF is a single document, so its results are held back until finish.
+    sarif_results: Vec<Value>,
+    error_count: usize,
+    warning_count: usize,
+}
+
+impl<'a> DiagnosticEmitter<'a> {
+    pub fn new(source_map: &'a SourceMap, config: DiagnosticConfig) -> Self {
+        Self {
+            source_map,
+            color: config.error_format == ErrorFormat::Human && config.color.should_color_stderr(),
+            error_format: config.error_format,
+            sarif_results: Vec::new(),
+            error_count: 0,
+            warning_count: 0,
+        }
+    }
+
+    pub fn emit(&mut self, diagnostic: &Diagnostic) {
+        match diagnostic.severity {
+            Severity::Error => self.error_count += 1,
+            Severity::Warning => self.warning_count += 1,
+        }
+        let rendered = match self.error_format {
+            ErrorFormat::Human => self.render(diagnostic),
+            ErrorFormat::Json => format!("{}\n", self.render_json(diagnostic)),
+            ErrorFormat::Sarif => {
+                let result = self.render_sarif_result(diagnostic);
+                self.sarif_results.push(result);
+                return;
+            }
+        };
+        let _ = std::io::stderr().write_all(rendered.as_bytes());
+    }
+
+    // Writes whatever is still owed once every diagnostic has been emitted: the human summary
+    // line, or the whole SARIF log.
+    pub fn finish(&self) {
+        let rendered = match self.error_format {
+            ErrorFormat::Human => self.render_summary(),
+            ErrorFormat::Json => String::new(),
+            ErrorFormat::Sarif => format!("{:#}\n", self.render_sarif_log()),
+        };
+        let _ = std::io::stderr().write_all(rendered.as_bytes());
+    }
+
+    pub fn emit_all(&mut self, diagnostics: &[Diagnostic]) {
+        for diagnostic in diagnostics {
+            self.emit(diagnostic);
+        }
+    }
+
+    pub fn error_count(&self) -> usize {
+        self.error_count
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.error_count > 0
+    }
+
+    pub fn render(&self, diagnostic: &Diagnostic) -> String {
+        let severity_color = match diagnostic.severity {
+            Severity::Error => RED,
+            Severity::Warning => YELLOW,
+        };
+
+        // Primary span first, then secondary labels, shown in source order.  Labels in another
+        // file get a snippet of their own.
+        let mut spans: Vec<(Span, Option<&str>, bool)> = vec![(
+            diagnostic.span,
+            diagnostic.primary_label.as_deref(),
+            true,
+        )];
+        for label in &diagnostic.secondary_labels {
+            spans.push((label.span, Some(label.message.as_str()), false));
+        }
+        spans.sort_by_key(|(span, _, _)| (span.file_id != diagnostic.span.file_id, span.file_id, span.lo));
+
+        let gutter_width = spans
+            .iter()
+            .map(|(span, _, _)| self.line_number(*span).to_string().len())
+            .max()
+            .unwrap_or(1);
+        let gutter = " ".repeat(gutter_width);
+
+        let mut out = String::new();
+        out.push_str(&format!(
+            "{}{}{}{}: {}{}{}\n",
+            self.paint(severity_color),
+            diagnostic.severity,
+            diagnostic
+                .code
+                .map(|code| format!("[{}]", code))
+                .unwrap_or_default(),
+            self.paint(RESET),
+            self.paint(BOLD),
+            diagnostic.message,
+            self.paint(RESET),
+        ));
+        out.push_str(&format!(
+            "{}{}-->{} {}\n",
+            gutter,
+            self.paint(BLUE),
+            self.paint(RESET),
+            self.location(diagnostic.span),
+        ));
+
+        let mut previous: Option<(Span, usize)> = None;
+        for (span, label, is_primary) in spans {
+            let file = self.source_map.file(span.file_id);
+            let line_number = self.line_number(span);
+            let Some(line_text) = file.line_text(line_number).map(expand_tabs) else {
+                continue;
+            };
+
+            match previous {
+                Some((previous_span, _)) if previous_span.file_id != span.file_id => {
+                    out.push_str(&format!(
+                        "{}{}:::{} {}\n",
+                        gutter,
+                        self.paint(BLUE),
+                        self.paint(RESET),
+                        self.location(span),
+                    ));
+                    out.push_str(&self.gutter_line(&gutter));
+                }
+                Some((_, previous_line)) if line_number > previous_line + 1 => {
+                    out.push_str(&format!("{}...{}\n", self.paint(BLUE), self.paint(RESET)))
+                }
+                None => out.push_str(&self.gutter_line(&gutter)),
+                _ => (),
+            }
+
+            let same_line = matches!(previous, Some((previous_span, previous_line))
+                if previous_span.file_id == span.file_id && previous_line == line_number);
+            if !same_line {
+                out.push_str(&format!(
+                    "{}{:>width$} |{} {}\n",
+                    self.paint(BLUE),
+                    line_number,
+                    self.paint(RESET),
+                    line_text,
+                    width = gutter_width,
+                ));
+            }
+            previous = Some((span, line_number));
+
+            let (marker, marker_color) = if is_primary {
+                ('^', severity_color)
+            } else {
+                ('-', BLUE)
+            };
+            let (start, length) = underline_range(file, span, &line_text);
+            out.push_str(&format!(
+                "{}{} |{} {}{}{}{}{}\n",
+                self.paint(BLUE),
+                gutter,
+                self.paint(RESET),
+                " ".repeat(start),
+                self.paint(marker_color),
+                marker.to_string().repeat(length),
+                label.map(|text| format!(" {}", text)).unwrap_or_default(),
+                self.paint(RESET),
+            ));
+        }
+
+        for note in &diagnostic.notes {
+            out.push_str(&format!(
+                "{}{} ={} {}note{}: {}\n",
+                self.paint(BLUE),
+                gutter,
+                self.paint(RESET),
+                self.paint(BOLD),
+                self.paint(RESET),
+                note
+            ));
+        }
+        out.push('\n');
+        out
+    }
+
+    pub fn render_summary(&self) -> String {
+        match (self.error_count, self.warning_count) {
+            (0, 0) => String::new(),
+            (0, warnings) => format!("{} warning(s) emitted\n", warnings),
+            (errors, 0) => format!("compilation failed: {} error(s)\n", errors),
+            (errors, warnings) => format!(
+                "compilation failed: {} error(s), {} warning(s)\n",
+                errors, warnings
+            ),
+        }
+    }
+
+    // One self-contained JSON object per diagnostic, e.g.
+    // {"severity":"error","code":"*5","message":"identifier undeclared: x","file":"t.vc",
+    //  "span":{...},"labels":[...],"notes":[]}
+    pub fn render_json(&self, diagnostic: &Diagnostic) -> Value {
+        let mut labels = vec![json!({
+            "span": self.span_json(diagnostic.span),
+            "message": diagnostic.primary_label,
+            "primary": true,
+        })];
+        for label in &diagnostic.secondary_labels {
+            labels.push(json!({
+                "span": self.span_json(label.span),
+                "message": label.message,
+                "primary": false,
+            }));
+        }
+
+        json!({
+            "severity": diagnostic.severity.to_string(),
+            "code": diagnostic.code,
+            "message": diagnostic.message,
+            "file": self.source_map.file_name(diagnostic.span),
+            "span": self.span_json(diagnostic.span),
+            "labels": labels,
+            "notes": diagnostic.notes,
+        })
+    }
+
+    pub fn render_sarif_result(&self, diagnostic: &Diagnostic) -> Value {
+        let mut message = diagnostic.message.clone();
+        for note in &diagnostic.notes {
+            message.push_str(&format!("\nnote: {}", note));
+        }
+
+        let related_locations: Vec<Value> = diagnostic
+            .secondary_labels
+            .iter()
+            .enumerate()
+            .map(|(index, label)| {
+                json!({
+                    "id": index,
+                    "physicalLocation": self.sarif_physical_location(label.span),
+                    "message": { "text": label.message },
+                })
+            })
+            .collect();
+
+        let mut result = json!({
+            "ruleId": diagnostic.code.unwrap_or("vc"),
+            "level": match diagnostic.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+            },
+            "message": { "text": message },
+            "locations": [{
+                "physicalLocation": self.sarif_physical_location(diagnostic.span),
+            }],
+        });
+        if let Some(rule_index) = diagnostic
+            .code
+            .and_then(|code| ERROR_CODES.iter().position(|(id, _)| *id == code))
+        {
+            result["ruleIndex"] = json!(rule_index);
+        }
+        if !related_locations.is_empty() {
+            result["relatedLocations"] = json!(related_locations);
+        }
+        result
+    }
+
+    pub fn render_sarif_log(&self) -> Value {
+        let rules: Vec<Value> = ERROR_CODES
+            .iter()
+            .map(|(id, description)| {
+                json!({
+                    "id": id,
+                    "shortDescription": { "text": description },
+                    "defaultConfiguration": { "level": "error" },
+                })
+            })
+            .collect();
+        let artifacts: Vec<Value> = self
+            .source_map
+            .files()
+            .map(|file| json!({ "location": sarif_artifact_location(file) }))
+            .collect();
+
+        json!({
+            "$schema": SARIF_SCHEMA,
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "vc",
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "rules": rules,
+                    }
+                },
+                "columnKind": "unicodeCodePoints",
+                "artifacts": artifacts,
+                "results": self.sarif_results,
+            }],
+        })
+    }
+
+    // SARIF regions are 1-based with an exclusive end column, and like the columns, charOffset and
+    // charLength count code points rather than bytes.
+    fn sarif_physical_location(&self, span: Span) -> Value {
+        let file = self.source_map.file(span.file_id);
+        let position = file.position(span, DISPLAY_COLUMNS);
+
+        let mut location = sarif_artifact_location(file);
+        location["index"] = json!(span.file_id.0);
+        json!({
+            "artifactLocation": location,
+            "region": {
+                "startLine": position.line_start,
+                "startColumn": position.char_start,
+                "endLine": position.line_finish,
+                "endColumn": position.char_end + 1,
+                "charOffset": file.char_count(0, span.lo),
+                "charLength": file.char_count(span.lo, span.hi),
+            },
+        })
+    }
+
+    fn span_json(&self, span: Span) -> Value {
+        let position = self.source_map.position(span, DISPLAY_COLUMNS);
+        json!({
+            "file": self.source_map.file_name(span),
+            "lo": span.lo,
+            "hi": span.hi,
+            "line_start": position.line_start,
+            "line_finish": position.line_finish,
+            "char_start": position.char_start,
+            "char_end": position.char_end,
+        })
+    }
+
+    fn line_number(&self, span: Span) -> usize {
+        self.source_map.file(span.file_id).line_number(span.lo)
+    }
+
+    // file:line:column of the start of span.
+    fn location(&self, span: Span) -> String {
+        let file = self.source_map.file(span.file_id);
+        let (line, column) = file.line_col(span.lo, DISPLAY_COLUMNS);
+        format!("{}:{}:{}", file.name, line, column)
+    }
+
+    fn gutter_line(&self, gutter: &str) -> String {
+        format!("{}{} |{}\n", self.paint(BLUE), gutter, self.paint(RESET))
+    }
+
+    fn paint(&self, code: &'static str) -> &'static str {
+        if self.color {
+            code
+        } else {
+            ""
+        }
+    }
+}
+
+// A relative path is resolved against %SRCROOT%, the directory vc was run from; an absolute path
+// becomes a file URI of its own.
+fn sarif_artifact_location(file: &SourceFile) -> Value {
+    let path = file.name.replace('\\', "/");
+    if !Path::new(&file.name).is_absolute() && !path.starts_with('/') {
+        return json!({
+            "uri": percent_encode(path.trim_start_matches("./")),
+            "uriBaseId": "%SRCROOT%",
+        });
+    }
+
+    // A Windows drive letter keeps its colon, as in file:///C:/src/gcd.vc.
+    let uri = match path.split_once(':') {
+        Some((drive, rest)) if drive.len() == 1 && drive.chars().all(|c| c.is_ascii_alphabetic()) => {
+            format!("file:///{}:{}", drive, percent_encode(rest))
+        }
+        _ => format!("file://{}", percent_encode(&path)),
+    };
+    json!({ "uri": uri })
+}
+
+// Escapes each byte of a path that may not appear in a URI as written, keeping the separators.
+fn percent_encode(path: &str) -> String {
+    let mut encoded = String::new();
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+// The source line is expanded to tab stops before carets are placed under it, so a tab in the
+// source lines up the same way in the snippet as in the caret line.
+fn expand_tabs(line: &str) -> String {
+    let tab_size = TAB_SIZE as usize;
+    let mut expanded = String::new();
+    let mut column = 0;
+
+    for c in line.chars() {
+        if c == '\t' {
+            let spaces = tab_size - (column % tab_size);
+            expanded.push_str(&" ".repeat(spaces));
+            column += spaces;
+        } else {
+            expanded.push(c);
+            column += 1;
+        }
+    }
+    expanded
+}
+
+// Returns the zero-based start column and the number of markers to draw on the first line of
+// the span.  Spans running onto later lines are underlined to the end of their first line.
+fn underline_range(file: &SourceFile, span: Span, expanded_line: &str) -> (usize, usize) {
+    let position = file.position(span, CARET_COLUMNS);
+    let line_length = expanded_line.chars().count();
+    let start = (position.char_start.max(1) - 1) as usize;
+    let end = if position.line_finish > position.line_start {
+        line_length.max(start + 1)
+    } else {
+        position.char_end.max(position.char_start) as usize
+    };
+    (start, end.saturating_sub(start).max(1))
+}
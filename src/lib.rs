@@ -7,27 +7,44 @@ use std::fs::File;
 use std::io;
 use std::io::Write;
 
+use crate::ast::program::Program;
 use crate::ast::{AstNode, PrintAST};
+use crate::checker::check_program;
+use crate::diagnostics::{Diagnostic, DiagnosticConfig, DiagnosticEmitter};
+use crate::loader::load_program;
 use crate::parser::{parse_code, ParserData};
+use crate::preprocessor::Define;
 use crate::scanner::Scanner;
+use crate::source_map::{FileId, SourceMap, Span};
 
 mod ast;
 pub mod checker;
+pub mod diagnostics;
 mod globals;
+pub mod loader;
+pub mod lsp;
 pub mod parser;
+pub mod preprocessor;
 pub mod scanner;
+pub mod source_map;
 pub mod stages;
 pub mod token;
 pub mod utils;
 
-pub fn print_tokens(input_filepath: &str) {
-    let input_filestring = std::fs::read_to_string(input_filepath).expect("File reading error.");
-
-    let mut my_scanner = Scanner::new(input_filestring);
-    // Passing clone creates local data in the object.
-    // Write a loop that prints all the tokens of the Scanner object, until a TokenKind::EOF is returned.
-
-    // let mut out_file = File::create(output_filepath).expect("Expected to create file.");
+// Each entry point returns false if any errors were reported.
+
+pub fn print_tokens(
+    input_filepath: &str,
+    config: DiagnosticConfig,
+    defines: &[Define],
+) -> bool {
+    let mut source_map = SourceMap::new();
+    let file_id = match load_input(&mut source_map, input_filepath) {
+        Ok(file_id) => file_id,
+        Err(diagnostics) => return report_unreadable(&source_map, config, &diagnostics),
+    };
+    let mut my_scanner = Scanner::with_defines(source_map.file(file_id).clone(), defines);
+    let mut emitter = DiagnosticEmitter::new(&source_map, config);
 
     loop {
         let mut token = my_scanner.get_next_token();
@@ -40,42 +57,111 @@ pub fn print_tokens(input_filepath: &str) {
             break;
         }
     }
-}
-
-pub fn parse_print_ast(input_filepath: &str) {
-    let contents_string = std::fs::read_to_string(input_filepath).expect("File reading error.");
 
-    let scanner = Scanner::new(contents_string);
-
-    let program = parse_code(scanner);
-
-    program.print_program();
+    emitter.emit_all(&my_scanner.take_diagnostics());
+    finish(&emitter)
 }
 
-pub fn parse_unparse(input_filepath: &str) {
-    let contents_string = std::fs::read_to_string(input_filepath).expect("File reading error.");
+pub fn parse_print_ast(
+    input_filepath: &str,
+    config: DiagnosticConfig,
+    defines: &[Define],
+) -> bool {
+    with_parsed_program(input_filepath, config, defines, |program, _| {
+        program.print_program()
+    })
+}
 
-    let scanner = Scanner::new(contents_string);
+pub fn parse_unparse(
+    input_filepath: &str,
+    config: DiagnosticConfig,
+    defines: &[Define],
+) -> bool {
+    with_parsed_program(input_filepath, config, defines, |program, _| {
+        program.print_unparsed_program()
+    })
+}
 
-    let program = parse_code(scanner);
+pub fn check_file(
+    input_filepath: &str,
+    config: DiagnosticConfig,
+    defines: &[Define],
+) -> bool {
+    with_parsed_program(input_filepath, config, defines, |program, emitter| {
+        emitter.emit_all(&check_program(program))
+    })
+}
 
-    program.print_unparsed_program();
+// Serves the Language Server Protocol over stdin and stdout until the client exits.
+pub fn run_language_server() -> bool {
+    lsp::serve(io::stdin().lock(), io::stdout().lock())
 }
 
-pub fn test_parser(input_filepath: &str) {
-    let contents_string = std::fs::read_to_string(input_filepath).expect("File reading error.");
+pub fn test_parser(
+    input_filepath: &str,
+    config: DiagnosticConfig,
+    defines: &[Define],
+) -> bool {
+    with_parsed_program(input_filepath, config, defines, |program, _| {
+        println!("{:?}", program);
+
+        program.print_program();
+        program.print_unparsed_program();
+    })
+}
 
-    let scanner = Scanner::new(contents_string);
+// Preprocesses, scans and parses the file and everything it includes, reporting any errors, and hands a
+// successfully parsed program on.
+fn with_parsed_program(
+    input_filepath: &str,
+    config: DiagnosticConfig,
+    defines: &[Define],
+    on_program: impl FnOnce(&Program, &mut DiagnosticEmitter),
+) -> bool {
+    let mut source_map = SourceMap::new();
+    let file_id = match load_input(&mut source_map, input_filepath) {
+        Ok(file_id) => file_id,
+        Err(diagnostics) => return report_unreadable(&source_map, config, &diagnostics),
+    };
+    let program = load_program(&mut source_map, file_id, defines);
+    let mut emitter = DiagnosticEmitter::new(&source_map, config);
+
+    match program {
+        Ok(program) => on_program(&program, &mut emitter),
+        Err(diagnostics) => emitter.emit_all(&diagnostics),
+    }
 
-    // Passing clone creates local data in the object.
-    // Write a loop that prints all the tokens of the Scanner object, until a TokenKind::EOF is returned.
+    finish(&emitter)
+}
 
-    let program = parse_code(scanner);
-    println!("{:?}", program);
+// An input file that cannot be read is added to the source map as an empty file, so the error can
+// point at it in every error format.
+fn load_input(
+    source_map: &mut SourceMap,
+    input_filepath: &str,
+) -> Result<FileId, Vec<Diagnostic>> {
+    source_map.load_file(input_filepath).map_err(|io_error| {
+        let file_id = source_map.add_file(input_filepath, String::new());
+        vec![Diagnostic::error(
+            Span::point(file_id, 0),
+            format!("cannot read input file: {}", input_filepath),
+        )
+        .with_code("I3")
+        .with_primary_label(io_error.to_string())]
+    })
+}
 
-    program.print_program();
-    program.print_unparsed_program();
+fn report_unreadable(
+    source_map: &SourceMap,
+    config: DiagnosticConfig,
+    diagnostics: &[Diagnostic],
+) -> bool {
+    let mut emitter = DiagnosticEmitter::new(source_map, config);
+    emitter.emit_all(diagnostics);
+    finish(&emitter)
+}
 
-    // For each layer of the AST, tab the node name in by depth + tab size.
-    // Then, print the entire AST.
+fn finish(emitter: &DiagnosticEmitter) -> bool {
+    emitter.finish();
+    !emitter.has_errors()
 }
@@ -0,0 +1,250 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::ast::decl::{DeclType, IncludeDecl};
+use crate::ast::list::{DeclList, EmptyDeclList, ListType};
+use crate::ast::program::Program;
+use crate::diagnostics::Diagnostic;
+use crate::parser::parse_code;
+use crate::preprocessor::{preprocess, Define};
+use crate::scanner::Scanner;
+use crate::source_map::{FileId, SourceMap, Span};
+
+/*
+   Loader
+   Turns a main file and everything it includes into one Program.  Each file is scanned and parsed
+   on its own, then every
+
+   #include "helpers/gcd.vc"
+
+   is replaced by the declarations of the file it names, resolved relative to the file containing
+   the directive.  A file is included at most once: later includes of it are dropped, so two files
+   can share a helper without redeclaring it.  A file that includes itself, directly or through
+   others, is reported as a cycle.
+
+   Every file goes into the SourceMap, and spans keep the FileId of the file they came from, so
+   diagnostics point into whichever file is at fault.  Each file is preprocessed on its own, with
+   only the command line's macros defined in advance.  A macro defined in an included file would
+   be invisible to the file including it, so an included file may not define any.
+*/
+pub fn load_program(
+    source_map: &mut SourceMap,
+    main_file: FileId,
+    defines: &[Define],
+) -> Result<Program, Vec<Diagnostic>> {
+    let main_path = canonical_path(&source_map.file(main_file).name);
+    let mut loader = Loader {
+        source_map,
+        defines,
+        included: HashSet::from([main_path.clone()]),
+        include_stack: vec![main_path],
+        diagnostics: Vec::new(),
+    };
+
+    let main_program = parse_file(loader.source_map, main_file, defines)?;
+    let program_span = main_program.declaration_list.span;
+    let decls = loader.resolve_includes(main_program.declaration_list);
+
+    if loader
+        .diagnostics
+        .iter()
+        .any(|diagnostic| diagnostic.is_error())
+    {
+        return Err(loader.diagnostics);
+    }
+
+    // The head keeps the main file's span, which is where a missing main is reported.
+    match build_decl_list(decls) {
+        ListType::DeclList(mut decl_list) => {
+            decl_list.span = program_span;
+            Ok(Program::new(decl_list))
+        }
+        _ => Err(vec![Diagnostic::error(
+            program_span.shrink_to_lo(),
+            "declaration expected here",
+        )
+        .with_code("P1")]),
+    }
+}
+
+struct Loader<'a> {
+    source_map: &'a mut SourceMap,
+    defines: &'a [Define],
+    // Every file read so far, including the one being read.
+    included: HashSet<PathBuf>,
+    // The chain of includes that led to the file being read, main file first.
+    include_stack: Vec<PathBuf>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Loader<'_> {
+    fn resolve_includes(&mut self, decl_list: DeclList) -> Vec<DeclType> {
+        let mut decls = Vec::new();
+        for decl in flatten_decl_list(decl_list) {
+            match decl {
+                DeclType::IncludeDecl(include_decl) => decls.extend(self.include(&include_decl)),
+                decl => decls.push(decl),
+            }
+        }
+        decls
+    }
+
+    fn include(&mut self, include_decl: &IncludeDecl) -> Vec<DeclType> {
+        let including_file = &self.source_map.file(include_decl.span.file_id).name;
+        let path = Path::new(including_file)
+            .parent()
+            .unwrap_or(Path::new(""))
+            .join(&include_decl.path);
+
+        let canonical = match fs::canonicalize(&path) {
+            Ok(canonical) => canonical,
+            Err(io_error) => {
+                self.report_unreadable(include_decl, &path, io_error);
+                return Vec::new();
+            }
+        };
+
+        if let Some(cycle_start) = self
+            .include_stack
+            .iter()
+            .position(|file| *file == canonical)
+        {
+            let cycle: Vec<String> = self.include_stack[cycle_start..]
+                .iter()
+                .chain([&canonical])
+                .map(|file| display_name(file))
+                .collect();
+            self.diagnostics.push(
+                Diagnostic::error(
+                    include_decl.span,
+                    format!("include cycle: {}", cycle.join(" -> ")),
+                )
+                .with_code("I2"),
+            );
+            return Vec::new();
+        }
+
+        if !self.included.insert(canonical.clone()) {
+            return Vec::new();
+        }
+
+        let file_id = match self.source_map.load_file(&path) {
+            Ok(file_id) => file_id,
+            Err(io_error) => {
+                self.report_unreadable(include_decl, &path, io_error);
+                return Vec::new();
+            }
+        };
+
+        self.report_definitions(file_id);
+        match parse_file(self.source_map, file_id, self.defines) {
+            Ok(program) => {
+                self.include_stack.push(canonical);
+                let decls = self.resolve_includes(program.declaration_list);
+                self.include_stack.pop();
+                decls
+            }
+            Err(mut diagnostics) => {
+                self.diagnostics.append(&mut diagnostics);
+                Vec::new()
+            }
+        }
+    }
+
+    fn report_definitions(&mut self, file_id: FileId) {
+        let source_file = self.source_map.file(file_id);
+        for name_span in preprocess(source_file, self.defines).definitions {
+            let name = &source_file.source[name_span.lo..name_span.hi];
+            self.diagnostics.push(
+                Diagnostic::error(
+                    name_span,
+                    format!("macro defined in an included file: {}", name),
+                )
+                .with_code("D4")
+                .with_note(
+                    "a macro is known only in the file that defines it, \
+                     so define it in the main file or with -D",
+                ),
+            );
+        }
+    }
+
+    fn report_unreadable(
+        &mut self,
+        include_decl: &IncludeDecl,
+        path: &Path,
+        io_error: std::io::Error,
+    ) {
+        self.diagnostics.push(
+            Diagnostic::error(
+                include_decl.span,
+                format!("cannot read included file: {}", path.display()),
+            )
+            .with_code("I1")
+            .with_primary_label(io_error.to_string()),
+        );
+    }
+}
+
+fn parse_file(
+    source_map: &SourceMap,
+    file_id: FileId,
+    defines: &[Define],
+) -> Result<Program, Vec<Diagnostic>> {
+    parse_code(Scanner::with_defines(
+        source_map.file(file_id).clone(),
+        defines,
+    ))
+}
+
+// Files that cannot be canonicalised, such as one given as a string, are compared by name.
+fn canonical_path(name: &str) -> PathBuf {
+    fs::canonicalize(name).unwrap_or_else(|_| PathBuf::from(name))
+}
+
+fn display_name(path: &Path) -> String {
+    path.file_name()
+        .map(|file_name| file_name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+fn flatten_decl_list(decl_list: DeclList) -> Vec<DeclType> {
+    let mut decls = vec![*decl_list.decl_type];
+    let mut current = *decl_list.decl_list;
+    while let ListType::DeclList(list) = current {
+        decls.push(*list.decl_type);
+        current = *list.decl_list;
+    }
+    decls
+}
+
+// Rebuilds the list back to front.  A node's span runs to the end of the list only while the
+// declarations after it come from the same file.
+fn build_decl_list(decls: Vec<DeclType>) -> ListType {
+    let end = decls
+        .last()
+        .map(|decl| decl.get_span().shrink_to_hi())
+        .unwrap_or_default();
+
+    decls.into_iter().rev().fold(
+        ListType::EmptyDeclList(EmptyDeclList::new(end)),
+        |rest, decl| {
+            let decl_span = *decl.get_span();
+            let span = match decl_list_span(&rest) {
+                Some(rest_span) if rest_span.file_id == decl_span.file_id => {
+                    decl_span.to(rest_span)
+                }
+                _ => decl_span,
+            };
+            ListType::DeclList(DeclList::new(span, Box::new(decl), Box::new(rest)))
+        },
+    )
+}
+
+fn decl_list_span(list: &ListType) -> Option<Span> {
+    match list {
+        ListType::DeclList(decl_list) => Some(decl_list.span),
+        _ => None,
+    }
+}
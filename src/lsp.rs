@@ -0,0 +1,512 @@
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+
+use log::{debug, error};
+use serde_json::{json, Value};
+
+use crate::ast::decl::DeclType;
+use crate::ast::list::ListType;
+use crate::checker::semantic_types::Type;
+use crate::checker::symbol_table::{Symbol, SymbolKind};
+use crate::diagnostics::{Diagnostic, Severity};
+use crate::lsp::document::Document;
+use crate::lsp::protocol::{
+    error_response, notification, read_message, response, write_message, INVALID_PARAMS,
+    INVALID_REQUEST, METHOD_NOT_FOUND, PARSE_ERROR,
+};
+use crate::token::{Token, TokenKind};
+
+pub mod document;
+pub mod protocol;
+
+/*
+   Language server
+   `vc lsp` speaks the Language Server Protocol over stdin and stdout, so editors get the
+   compiler's own diagnostics as you type, along with hover, go to definition, an outline of the
+   file and semantic highlighting.  Documents are synced in full on every change and re-analysed
+   with the same Scanner, parse_code and checker as `vc check`.
+
+   serve is generic over its streams so tests can script a whole session in memory.
+*/
+
+// Returns true if the client asked to shut down before exiting, as the protocol expects.
+pub fn serve(reader: impl BufRead, writer: impl Write) -> bool {
+    let mut server = LanguageServer::new(writer);
+    match server.run(reader) {
+        Ok(clean_exit) => clean_exit,
+        Err(io_error) => {
+            error!("language server stopped: {}", io_error);
+            false
+        }
+    }
+}
+
+// The order of this legend gives each semantic token type its index.
+const SEMANTIC_TOKEN_TYPES: [&str; 9] = [
+    "keyword",
+    "type",
+    "function",
+    "parameter",
+    "variable",
+    "number",
+    "string",
+    "operator",
+    "macro",
+];
+
+// LSP's SymbolKind values for the declarations listed as document symbols.
+const FUNCTION_SYMBOL: u32 = 12;
+const VARIABLE_SYMBOL: u32 = 13;
+const STRUCT_SYMBOL: u32 = 23;
+
+type RequestResult = Result<Value, (i64, String)>;
+
+struct LanguageServer<W: Write> {
+    writer: W,
+    documents: HashMap<String, Document>,
+    shutdown_requested: bool,
+}
+
+impl<W: Write> LanguageServer<W> {
+    fn new(writer: W) -> Self {
+        Self {
+            writer,
+            documents: HashMap::new(),
+            shutdown_requested: false,
+        }
+    }
+
+    fn run(&mut self, mut reader: impl BufRead) -> io::Result<bool> {
+        while let Some(body) = read_message(&mut reader)? {
+            let message: Value = match serde_json::from_str(&body) {
+                Ok(message) => message,
+                Err(parse_error) => {
+                    self.send(error_response(
+                        Value::Null,
+                        PARSE_ERROR,
+                        parse_error.to_string(),
+                    ))?;
+                    continue;
+                }
+            };
+
+            let params = &message["params"];
+            match (message.get("id"), message["method"].as_str()) {
+                (_, Some("exit")) => return Ok(self.shutdown_requested),
+                (Some(id), Some(method)) => {
+                    debug!("request {}: {}", id, method);
+                    let reply = match self.handle_request(method, params) {
+                        Ok(result) => response(id.clone(), result),
+                        Err((code, message)) => error_response(id.clone(), code, message),
+                    };
+                    self.send(reply)?;
+                }
+                (None, Some(method)) => {
+                    debug!("notification: {}", method);
+                    self.handle_notification(method, params)?;
+                }
+                // A response to a request of ours; the server never sends any.
+                (Some(_), None) => (),
+                (None, None) => self.send(error_response(
+                    Value::Null,
+                    INVALID_REQUEST,
+                    "message has neither an id nor a method",
+                ))?,
+            }
+        }
+
+        // The client went away without saying exit.
+        Ok(false)
+    }
+
+    fn send(&mut self, message: Value) -> io::Result<()> {
+        write_message(&mut self.writer, &message)
+    }
+
+    // ======================================= Requests ============================================
+
+    fn handle_request(&mut self, method: &str, params: &Value) -> RequestResult {
+        if self.shutdown_requested {
+            return Err((INVALID_REQUEST, String::from("server is shutting down")));
+        }
+
+        match method {
+            "initialize" => Ok(initialize_result()),
+            "shutdown" => {
+                self.shutdown_requested = true;
+                Ok(Value::Null)
+            }
+            "textDocument/hover" => self.hover(params),
+            "textDocument/definition" => self.definition(params),
+            "textDocument/documentSymbol" => self.document_symbols(params),
+            "textDocument/semanticTokens/full" => self.semantic_tokens(params),
+            _ => Err((METHOD_NOT_FOUND, format!("unhandled method: {}", method))),
+        }
+    }
+
+    fn document(&self, params: &Value) -> Result<&Document, (i64, String)> {
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+        self.documents
+            .get(uri)
+            .ok_or_else(|| (INVALID_PARAMS, format!("document is not open: {}", uri)))
+    }
+
+    fn hover(&self, params: &Value) -> RequestResult {
+        let document = self.document(params)?;
+        let Some(resolution) = document
+            .offset(&params["position"])
+            .and_then(|offset| document.analysis.resolution_at(document.file_id, offset))
+        else {
+            return Ok(Value::Null);
+        };
+
+        let symbol = &resolution.symbol;
+        let mut contents = format!("```vc\n{}\n```", signature(symbol));
+        if symbol.position.is_none() {
+            contents.push_str("\n\nBuilt in to the VC standard environment.");
+        }
+
+        Ok(json!({
+            "contents": { "kind": "markdown", "value": contents },
+            "range": document.range(resolution.span),
+        }))
+    }
+
+    fn definition(&self, params: &Value) -> RequestResult {
+        let document = self.document(params)?;
+        let declaration = document
+            .offset(&params["position"])
+            .and_then(|offset| document.analysis.resolution_at(document.file_id, offset))
+            .and_then(|resolution| resolution.symbol.position);
+
+        Ok(match declaration {
+            Some(span) => document.location(span),
+            None => Value::Null,
+        })
+    }
+
+    fn document_symbols(&self, params: &Value) -> RequestResult {
+        let document = self.document(params)?;
+        let Some(program) = &document.program else {
+            return Ok(json!([]));
+        };
+
+        let declaration_list = &program.declaration_list;
+        let mut decls = vec![&declaration_list.decl_type];
+        let mut current = &*declaration_list.decl_list;
+        while let ListType::DeclList(list) = current {
+            decls.push(&list.decl_type);
+            current = &list.decl_list;
+        }
+
+        let symbols = decls
+            .into_iter()
+            .filter_map(|decl| match &**decl {
+                DeclType::FuncDecl(func_decl) => {
+                    Some((&func_decl.ident, func_decl.span, FUNCTION_SYMBOL))
+                }
+                DeclType::GlobalVarDecl(var_decl) => {
+                    Some((&var_decl.ident, var_decl.span, VARIABLE_SYMBOL))
+                }
+                DeclType::StructDecl(struct_decl) => {
+                    Some((&struct_decl.ident, struct_decl.span, STRUCT_SYMBOL))
+                }
+                _ => None,
+            })
+            // Declarations from included files belong to their own outlines.
+            .filter(|(ident, _, _)| ident.span.file_id == document.file_id)
+            .map(|(ident, span, kind)| {
+                let detail = document
+                    .analysis
+                    .resolutions
+                    .iter()
+                    .find(|resolution| resolution.span == ident.span)
+                    .map(|resolution| signature(&resolution.symbol));
+                json!({
+                    "name": ident.spelling,
+                    "detail": detail,
+                    "kind": kind,
+                    "range": document.range(ident.span.merge(span)),
+                    "selectionRange": document.range(ident.span),
+                })
+            })
+            .collect();
+
+        Ok(Value::Array(symbols))
+    }
+
+    // Each token is five integers: its line and start relative to the previous token, its length,
+    // its index in SEMANTIC_TOKEN_TYPES and its modifiers, of which there are none.  A macro's
+    // expansion shares the span of its use, so only its first token is sent, as one macro token.
+    fn semantic_tokens(&self, params: &Value) -> RequestResult {
+        let document = self.document(params)?;
+        let mut data = Vec::new();
+        let (mut previous_line, mut previous_start) = (0, 0);
+        let mut previous_span = None;
+
+        for token in &document.tokens {
+            if previous_span == Some(token.span) {
+                continue;
+            }
+            let Some(token_type) = semantic_token_type(token, document) else {
+                continue;
+            };
+
+            let position = document.position(token.span.file_id, token.span.lo);
+            let line = position["line"].as_u64().unwrap_or_default();
+            let start = position["character"].as_u64().unwrap_or_default();
+            // Tokens must be sent in order, and the deltas cannot go backwards.
+            if (line, start) < (previous_line, previous_start) {
+                continue;
+            }
+            let delta_start = if line == previous_line {
+                start - previous_start
+            } else {
+                start
+            };
+
+            data.extend([
+                line - previous_line,
+                delta_start,
+                document.utf16_len(token.span) as u64,
+                token_type as u64,
+                0,
+            ]);
+            (previous_line, previous_start) = (line, start);
+            previous_span = Some(token.span);
+        }
+
+        Ok(json!({ "data": data }))
+    }
+
+    // ==================================== Notifications ==========================================
+
+    fn handle_notification(&mut self, method: &str, params: &Value) -> io::Result<()> {
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+
+        match method {
+            "textDocument/didOpen" => {
+                let text = params["textDocument"]["text"].as_str().unwrap_or_default();
+                self.open(uri, text)
+            }
+            // Only full syncs are asked for, so the last change holds the whole text.
+            "textDocument/didChange" => {
+                let changes = params["contentChanges"].as_array();
+                match changes.and_then(|changes| changes.last()) {
+                    Some(change) => self.open(uri, change["text"].as_str().unwrap_or_default()),
+                    None => Ok(()),
+                }
+            }
+            "textDocument/didClose" => match self.documents.remove(uri) {
+                Some(document) => document.file_ids().try_for_each(|file_id| {
+                    self.publish_diagnostics(&document.uri_of(file_id), Vec::new())
+                }),
+                None => Ok(()),
+            },
+            // initialized, $/cancelRequest and anything else need no reply.
+            _ => Ok(()),
+        }
+    }
+
+    // Diagnostics are published for the file itself and for each file it includes.  Files it no
+    // longer includes have theirs cleared.
+    fn open(&mut self, uri: &str, text: &str) -> io::Result<()> {
+        let document = Document::new(uri, text.to_string());
+
+        let mut published: Vec<(String, Vec<Value>)> = document
+            .file_ids()
+            .map(|file_id| {
+                let diagnostics = document
+                    .analysis
+                    .diagnostics
+                    .iter()
+                    .filter(|diagnostic| diagnostic.span.file_id == file_id)
+                    .map(|diagnostic| lsp_diagnostic(&document, diagnostic))
+                    .collect();
+                (document.uri_of(file_id), diagnostics)
+            })
+            .collect();
+
+        if let Some(previous) = self.documents.insert(uri.to_string(), document) {
+            for file_id in previous.file_ids() {
+                let previous_uri = previous.uri_of(file_id);
+                if published.iter().all(|(uri, _)| *uri != previous_uri) {
+                    published.push((previous_uri, Vec::new()));
+                }
+            }
+        }
+
+        for (file_uri, diagnostics) in published {
+            self.publish_diagnostics(&file_uri, diagnostics)?;
+        }
+        Ok(())
+    }
+
+    fn publish_diagnostics(&mut self, uri: &str, diagnostics: Vec<Value>) -> io::Result<()> {
+        self.send(notification(
+            "textDocument/publishDiagnostics",
+            json!({ "uri": uri, "diagnostics": diagnostics }),
+        ))
+    }
+}
+
+fn initialize_result() -> Value {
+    json!({
+        "capabilities": {
+            // Full document sync.
+            "textDocumentSync": 1,
+            "hoverProvider": true,
+            "definitionProvider": true,
+            "documentSymbolProvider": true,
+            "semanticTokensProvider": {
+                "legend": { "tokenTypes": SEMANTIC_TOKEN_TYPES, "tokenModifiers": [] },
+                "full": true,
+            },
+        },
+        "serverInfo": { "name": "vc", "version": env!("CARGO_PKG_VERSION") },
+    })
+}
+
+// The primary label and notes have nowhere else to go, so they are added to the message.
+fn lsp_diagnostic(document: &Document, diagnostic: &Diagnostic) -> Value {
+    let mut message = diagnostic.message.clone();
+    if let Some(primary_label) = &diagnostic.primary_label {
+        message.push_str(&format!("\n{}", primary_label));
+    }
+    for note in &diagnostic.notes {
+        message.push_str(&format!("\nnote: {}", note));
+    }
+
+    let related_information: Vec<Value> = diagnostic
+        .secondary_labels
+        .iter()
+        .map(|label| json!({ "location": document.location(label.span), "message": label.message }))
+        .collect();
+
+    let mut lsp_diagnostic = json!({
+        "range": document.range(diagnostic.span),
+        "severity": match diagnostic.severity {
+            Severity::Error => 1,
+            Severity::Warning => 2,
+        },
+        "source": "vc",
+        "message": message,
+        "relatedInformation": related_information,
+    });
+    if let Some(code) = diagnostic.code {
+        lsp_diagnostic["code"] = json!(code);
+    }
+    lsp_diagnostic
+}
+
+// A declaration as it would be written in VC, e.g. "int gcd(int a, int b)" or "float v[10]".
+fn signature(symbol: &Symbol) -> String {
+    match &symbol.kind {
+        SymbolKind::Function { parameters } => {
+            let parameters: Vec<String> = parameters
+                .iter()
+                .map(|parameter| {
+                    let declarator = declarator(&parameter.parameter_type, &parameter.name);
+                    if parameter.is_const {
+                        format!("const {}", declarator)
+                    } else {
+                        declarator
+                    }
+                })
+                .collect();
+            format!(
+                "{} {}({})",
+                symbol.symbol_type,
+                symbol.name,
+                parameters.join(", ")
+            )
+        }
+        _ if symbol.is_const => format!("const {}", declarator(&symbol.symbol_type, &symbol.name)),
+        _ => declarator(&symbol.symbol_type, &symbol.name),
+    }
+}
+
+fn declarator(declared_type: &Type, name: &str) -> String {
+    format!(
+        "{} {}{}",
+        declared_type.base_type(),
+        name,
+        declared_type.dimension_suffix()
+    )
+}
+
+// Index into SEMANTIC_TOKEN_TYPES.  Identifiers are told apart by what they resolved to;
+// separators and error tokens are left unhighlighted.  Whatever a macro expands to is shown as
+// the macro.
+fn semantic_token_type(token: &Token, document: &Document) -> Option<usize> {
+    let token_type = match token.token_kind {
+        _ if token.expanded => "macro",
+        TokenKind::BREAK
+        | TokenKind::CASE
+        | TokenKind::CONST
+        | TokenKind::CONTINUE
+        | TokenKind::DEFAULT
+        | TokenKind::DO
+        | TokenKind::ELSE
+        | TokenKind::FOR
+        | TokenKind::IF
+        | TokenKind::RETURN
+        | TokenKind::STRUCT
+        | TokenKind::SWITCH
+        | TokenKind::WHILE
+        | TokenKind::BOOLEANLITERAL => "keyword",
+        TokenKind::BOOLEAN
+        | TokenKind::CHAR
+        | TokenKind::FLOAT
+        | TokenKind::INT
+        | TokenKind::STRING
+        | TokenKind::VOID => "type",
+        TokenKind::PLUS
+        | TokenKind::MINUS
+        | TokenKind::MULT
+        | TokenKind::DIV
+        | TokenKind::NOT
+        | TokenKind::NOTEQ
+        | TokenKind::EQ
+        | TokenKind::EQEQ
+        | TokenKind::LT
+        | TokenKind::LTEQ
+        | TokenKind::GT
+        | TokenKind::GTEQ
+        | TokenKind::ANDAND
+        | TokenKind::OROR
+        | TokenKind::PLUSEQ
+        | TokenKind::MINUSEQ
+        | TokenKind::MULTEQ
+        | TokenKind::DIVEQ
+        | TokenKind::PLUSPLUS
+        | TokenKind::MINUSMINUS
+        | TokenKind::MOD
+        | TokenKind::AND
+        | TokenKind::OR
+        | TokenKind::XOR
+        | TokenKind::TILDE
+        | TokenKind::LTLT
+        | TokenKind::GTGT => "operator",
+        TokenKind::INTLITERAL | TokenKind::FLOATLITERAL => "number",
+        TokenKind::CHARLITERAL | TokenKind::STRINGLITERAL => "string",
+        TokenKind::INCLUDE => "macro",
+        TokenKind::ID => {
+            let resolution = document
+                .analysis
+                .resolutions
+                .iter()
+                .find(|resolution| resolution.span == token.span);
+            match resolution.map(|resolution| &resolution.symbol.kind) {
+                Some(SymbolKind::Function { .. }) => "function",
+                Some(SymbolKind::Parameter) => "parameter",
+                _ => "variable",
+            }
+        }
+        _ => return None,
+    };
+
+    SEMANTIC_TOKEN_TYPES
+        .iter()
+        .position(|legend_type| *legend_type == token_type)
+}
@@ -0,0 +1,163 @@
+use serde_json::{json, Value};
+
+use crate::ast::program::Program;
+use crate::checker::{analyse_program, Analysis};
+use crate::loader::load_program;
+use crate::scanner::Scanner;
+use crate::source_map::{ColumnMode, FileId, SourceMap, Span};
+use crate::token::{Token, TokenKind};
+
+/*
+   Document
+   One open file and everything worked out from its current text.  A document is analysed from
+   scratch each time it changes: VC files are small, and this keeps the server stateless between
+   edits.  If the file does not parse there is no program, and only the diagnostics and tokens are
+   available.
+
+   Files it includes are read from disk into the same SourceMap, so spans may point into them;
+   location and range work for any span, while positions from the client are always in the open
+   file itself.
+*/
+pub struct Document {
+    pub uri: String,
+    pub source_map: SourceMap,
+    pub file_id: FileId,
+    pub tokens: Vec<Token>,
+    pub program: Option<Program>,
+    pub analysis: Analysis,
+}
+
+impl Document {
+    pub fn new(uri: &str, text: String) -> Self {
+        // Includes are resolved relative to the file's path, so name it by that where possible.
+        let name = uri_to_path(uri).unwrap_or_else(|| uri.to_string());
+        let mut source_map = SourceMap::new();
+        let file_id = source_map.add_file(name, text);
+        let tokens = scan_tokens(&source_map, file_id);
+
+        let (program, analysis) = match load_program(&mut source_map, file_id, &[]) {
+            Ok(program) => {
+                let analysis = analyse_program(&program);
+                (Some(program), analysis)
+            }
+            Err(diagnostics) => (
+                None,
+                Analysis {
+                    diagnostics,
+                    ..Analysis::default()
+                },
+            ),
+        };
+
+        Self {
+            uri: uri.to_string(),
+            source_map,
+            file_id,
+            tokens,
+            program,
+            analysis,
+        }
+    }
+
+    // The open file is known by the client's URI; anything it includes by its path.
+    pub fn uri_of(&self, file_id: FileId) -> String {
+        if file_id == self.file_id {
+            self.uri.clone()
+        } else {
+            path_to_uri(&self.source_map.file(file_id).name)
+        }
+    }
+
+    // Every file the document is made of, the open one first.
+    pub fn file_ids(&self) -> impl Iterator<Item = FileId> + '_ {
+        self.source_map.files().map(|file| file.file_id)
+    }
+
+    // LSP positions are 0-based, with columns counted in UTF-16 code units.
+    pub fn position(&self, file_id: FileId, offset: usize) -> Value {
+        let (line, column) = self
+            .source_map
+            .file(file_id)
+            .line_col(offset, ColumnMode::Utf16);
+        json!({ "line": line - 1, "character": column - 1 })
+    }
+
+    pub fn range(&self, span: Span) -> Value {
+        json!({
+            "start": self.position(span.file_id, span.lo),
+            "end": self.position(span.file_id, span.hi),
+        })
+    }
+
+    pub fn location(&self, span: Span) -> Value {
+        json!({ "uri": self.uri_of(span.file_id), "range": self.range(span) })
+    }
+
+    // The byte offset in the open file of an LSP position, if it lies within the file.
+    pub fn offset(&self, position: &Value) -> Option<usize> {
+        let line = position["line"].as_u64()? as usize;
+        let character = position["character"].as_u64()? as usize;
+        self.source_map
+            .file(self.file_id)
+            .offset(line + 1, character + 1, ColumnMode::Utf16)
+    }
+
+    // Length of span in UTF-16 code units.
+    pub fn utf16_len(&self, span: Span) -> usize {
+        self.source_map.text(span).encode_utf16().count()
+    }
+}
+
+fn scan_tokens(source_map: &SourceMap, file_id: FileId) -> Vec<Token> {
+    let mut scanner = Scanner::from_source_file(source_map.file(file_id).clone());
+    let mut tokens = Vec::new();
+
+    loop {
+        let token = scanner.get_next_token();
+        if token.token_kind == TokenKind::EOF {
+            return tokens;
+        }
+        tokens.push(token);
+    }
+}
+
+// file:///home/me/gcd%20v2.vc is /home/me/gcd v2.vc.  Other schemes have no path.
+fn uri_to_path(uri: &str) -> Option<String> {
+    let encoded = uri.strip_prefix("file://")?;
+    let mut bytes = Vec::new();
+    let mut rest = encoded.as_bytes();
+
+    while let Some((&byte, tail)) = rest.split_first() {
+        let escaped = (byte == b'%')
+            .then(|| tail.get(..2))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+        match escaped {
+            Some(decoded) => {
+                bytes.push(decoded);
+                rest = &tail[2..];
+            }
+            None => {
+                bytes.push(byte);
+                rest = tail;
+            }
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+fn path_to_uri(path: &str) -> String {
+    let absolute = std::fs::canonicalize(path)
+        .map(|canonical| canonical.to_string_lossy().into_owned())
+        .unwrap_or_else(|_| path.to_string());
+    let mut uri = String::from("file://");
+    for byte in absolute.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                uri.push(byte as char)
+            }
+            _ => uri.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    uri
+}
@@ -11,9 +11,13 @@ use clap::{
 use log::error;
 use regex::internal::Compiler;
 
+use vc::diagnostics::{ColorChoice, DiagnosticConfig, ErrorFormat};
 use vc::parser::{parse_code, ParserData};
+use vc::preprocessor::Define;
 use vc::scanner::Scanner;
-use vc::{parse_print_ast, parse_unparse, print_tokens, test_parser};
+use vc::{
+    check_file, parse_print_ast, parse_unparse, print_tokens, run_language_server, test_parser,
+};
 
 #[derive(Parser)]
 #[clap(author = "Hamish Poole", about = "A compiler for the VC language.")]
@@ -21,6 +25,18 @@ use vc::{parse_print_ast, parse_unparse, print_tokens, test_parser};
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    /// When to colour error and warning output.
+    #[arg(long, value_enum, global = true, default_value_t = ColorChoice::Auto)]
+    color: ColorChoice,
+
+    /// How to write errors and warnings: for people, as JSON lines, or as a SARIF 2.1.0 log.
+    #[arg(long, value_enum, global = true, default_value_t = ErrorFormat::Human)]
+    error_format: ErrorFormat,
+
+    /// Defines a macro before preprocessing, as if by #define NAME VALUE.  VALUE defaults to 1.
+    #[arg(short = 'D', value_name = "NAME[=VALUE]", global = true)]
+    define: Vec<Define>,
 }
 
 #[derive(Debug, Subcommand)]
@@ -36,6 +52,13 @@ enum Commands {
     /// Parses the input file, then unparses the AST and prints an identical result.
     #[command(arg_required_else_help = true)]
     Unparse { input_filepath: String },
+
+    /// Parses and type checks the input file, reporting any errors found.
+    #[command(arg_required_else_help = true)]
+    Check { input_filepath: String },
+
+    /// Runs a language server for editors, speaking LSP over stdin and stdout.
+    Lsp,
 }
 
 #[derive(Args)]
@@ -45,16 +68,21 @@ fn main() {
     pretty_env_logger::init();
     let cli = Cli::parse();
 
-    match cli.command {
-        Commands::Scan { input_filepath } => {
-            print_tokens(&input_filepath);
-        }
-        Commands::Parse { input_filepath } => {
-            parse_print_ast(&input_filepath);
-        }
-        Commands::Unparse { input_filepath } => {
-            parse_unparse(&input_filepath);
-        }
+    let config = DiagnosticConfig {
+        color: cli.color,
+        error_format: cli.error_format,
+    };
+
+    let succeeded = match cli.command {
+        Commands::Scan { input_filepath } => print_tokens(&input_filepath, config, &cli.define),
+        Commands::Parse { input_filepath } => parse_print_ast(&input_filepath, config, &cli.define),
+        Commands::Unparse { input_filepath } => parse_unparse(&input_filepath, config, &cli.define),
+        Commands::Check { input_filepath } => check_file(&input_filepath, config, &cli.define),
+        Commands::Lsp => run_language_server(),
+    };
+
+    if !succeeded {
+        std::process::exit(1);
     }
 }
 
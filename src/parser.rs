@@ -1,90 +1,132 @@
 // Can visualise using https://crates.io/crates/graphviz-rust https://docs.rs/petgraph/latest/petgraph/ if needed.
 // Arc<Mutex<T>> or RC or RefCell for references.
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use log::{debug, error};
 use once_cell::sync::Lazy;
 
 use crate::ast::array_type::{ArrayType, AstTypeVariant};
-use crate::ast::decl::{DeclType, FuncDecl, GlobalVarDecl, LocalVarDecl, ParaDecl};
+use crate::ast::decl::{
+    DeclType, FieldDecl, FuncDecl, GlobalVarDecl, IncludeDecl, LocalVarDecl, ParaDecl, StructDecl,
+};
 use crate::ast::expression::{
-    Arg, ArrayExpr, ArrayInitExpr, AssignExpr, BinaryExpr, BooleanExpr, CallExpr, EmptyExpr,
-    ExprType, FloatExpr, IntExpr, StringExpr, UnaryExpr, VarExpr,
+    Arg, ArrayExpr, ArrayInitExpr, AssignExpr, BinaryExpr, BooleanExpr, CallExpr, CastExpr,
+    CharExpr, CompoundAssignExpr, ConditionalExpr, EmptyExpr, ExprType, FieldExpr, FloatExpr,
+    IntExpr, ParenExpr, PostfixExpr, StringExpr, UnaryExpr, VarExpr,
 };
 use crate::ast::ident::Ident;
 use crate::ast::list::{
-    ArgList, ArrayExprList, DeclList, EmptyArgList, EmptyArrayExprList, EmptyParamList, ListType,
-    ParamList,
+    ArgList, ArrayExprList, CaseList, DeclList, EmptyArgList, EmptyArrayExprList, EmptyCaseList,
+    EmptyParamList, ListType, ParamList,
 };
 use crate::ast::list::{EmptyDeclList, EmptyStmtList, StmtList};
-use crate::ast::literals::{BooleanLiteral, FloatLiteral, IntLiteral, Operator, StringLiteral};
+use crate::ast::literals::{
+    BooleanLiteral, CharLiteral, FloatLiteral, IntLiteral, Operator, StringLiteral,
+};
 use crate::ast::primitive_types::{
-    AstTypes, BooleanType, FloatType, IntType, StringType, VoidType,
+    AstTypes, BooleanType, CharType, FloatType, IntType, StringType, StructType, VoidType,
 };
 use crate::ast::program::Program;
 use crate::ast::statement::{
-    BreakStmt, CompoundStmt, ContinueStmt, EmptyStmt, ExprStmt, ForStmt, IfStmt, ReturnStmt,
-    StmtType, WhileStmt,
+    BreakStmt, CaseStmt, CompoundStmt, ContinueStmt, DoWhileStmt, EmptyStmt, ExprStmt, ForStmt,
+    IfStmt, ReturnStmt, StmtType, SwitchStmt, WhileStmt,
 };
 use crate::ast::variable::VarUntyped;
 use crate::ast::AstNode;
+use crate::diagnostics::Diagnostic;
 use crate::scanner::Scanner;
 use crate::token::{Token, TokenKind};
-use crate::utils::SourcePosition;
+use crate::source_map::Span;
 
-// Option types excellent for handling recoverable errors.
-// For errors you shouldn't recover from, just panic!.
-pub enum ParserResult {
-    Ok(AstNode),
-    NoMatch { expect: &'static str },
-    Error { error_string: &'static str },
-}
+// Syntax errors are not recoverable: the first one stops the parse and is handed back to the
+// caller as a Diagnostic, which reports it alongside anything the scanner found.  It is boxed so
+// the Ok path of every parse function stays small.
+pub type ParseResult<T> = Result<T, Box<Diagnostic>>;
 
 #[derive(Debug)]
 pub struct ParserData {
     scanner: Scanner,
     current_token: Token,
-    current_position: SourcePosition,
+    current_position: Span,
+    previous_position: Span,
 }
 
-// NB.  No errors should be recoverable in this parser.
-// Recoverable errors imply the parser should handle it.  Syntax errors are not recoverable.
 impl ParserData {
     pub fn new(mut scanner: Scanner) -> Self {
         let current_token = scanner.get_next_token();
 
         Self {
             scanner,
+            current_position: current_token.span,
+            previous_position: current_token.span.shrink_to_lo(),
             current_token,
-            current_position: SourcePosition::new(0, 0, 0, 0),
         }
     }
 }
 
-pub fn parse_code(scanner: Scanner) -> Program {
+// Returns every error found while scanning and parsing, in the order they were found.
+pub fn parse_code(scanner: Scanner) -> Result<Program, Vec<Diagnostic>> {
     let mut parser_data = ParserData::new(scanner);
 
-    parse_program(&mut parser_data)
+    let program = parse_program(&mut parser_data);
+    let mut diagnostics = parser_data.scanner.take_diagnostics();
+
+    match program {
+        Ok(program) if diagnostics.iter().all(|diagnostic| !diagnostic.is_error()) => Ok(program),
+        Ok(_) => Err(diagnostics),
+        Err(syntax_error) => {
+            diagnostics.push(*syntax_error);
+            Err(diagnostics)
+        }
+    }
 }
 
-pub fn parse_program(parser_struct: &mut ParserData) -> Program {
-    let declaration_list = parse_declaration_list(parser_struct, true);
+pub fn parse_program(parser_struct: &mut ParserData) -> ParseResult<Program> {
+    let declaration_list = parse_declaration_list(parser_struct, true)?;
+
+    if parser_struct.current_token.token_kind != TokenKind::EOF {
+        return Err(syntax_error(parser_struct, "type expected here"));
+    }
 
     match declaration_list {
-        ListType::DeclList(decl_list) => Program::new(decl_list),
-        _ => panic!("Error: Expected declaration list."),
+        ListType::DeclList(decl_list) => Ok(Program::new(decl_list)),
+        _ => Err(syntax_error(parser_struct, "declaration expected here")),
     }
 }
 
-fn parse_declaration_list(parser_struct: &mut ParserData, is_global: bool) -> ListType {
+fn parse_declaration_list(
+    parser_struct: &mut ParserData,
+    is_global: bool,
+) -> ParseResult<ListType> {
     let start = parser_struct.current_position;
 
-    if token_is_primitive_type(&parser_struct.current_token.token_kind) {
-        let curr_type = parse_type(parser_struct);
-        let ident = parse_identifier(parser_struct);
+    if token_starts_declaration(&parser_struct.current_token.token_kind) {
+        let is_const = parser_struct.current_token.token_kind == TokenKind::CONST;
+        if is_const {
+            match_and_consume_next_token(parser_struct); // CONST
+        }
+        let curr_type = parse_type(parser_struct)?;
+        if let (AstTypes::StructType(struct_type), TokenKind::LBRACE) =
+            (&curr_type, parser_struct.current_token.token_kind)
+        {
+            if is_const {
+                return Err(syntax_error(parser_struct, "only variables may be declared const"));
+            }
+            if !is_global {
+                return Err(syntax_error(
+                    parser_struct,
+                    "struct types may only be declared at global scope",
+                ));
+            }
+            return parse_struct_decl_list(parser_struct, struct_type.ident.clone());
+        }
+        let ident = parse_identifier(parser_struct)?;
         if parser_struct.current_token.token_kind == TokenKind::LPAREN {
+            if is_const {
+                return Err(syntax_error(parser_struct, "only variables may be declared const"));
+            }
             parse_func_decl_list(Box::new(curr_type), ident, is_global, parser_struct)
         } else {
             parse_initial_declaration_list(
@@ -92,13 +134,99 @@ fn parse_declaration_list(parser_struct: &mut ParserData, is_global: bool) -> Li
                 Box::new(curr_type),
                 Box::new(ident),
                 is_global,
+                is_const,
             )
         }
+    } else if is_global && parser_struct.current_token.token_kind == TokenKind::INCLUDE {
+        parse_include_decl_list(parser_struct)
     } else {
-        ListType::EmptyDeclList(EmptyDeclList::new(start))
+        Ok(ListType::EmptyDeclList(EmptyDeclList::new(start)))
     }
 }
 
+// struct-decl -> "struct" identifier "{" field-decl* "}" ";"
+fn parse_struct_decl_list(parser_struct: &mut ParserData, ident: Ident) -> ParseResult<ListType> {
+    let start_pos = ident.span;
+
+    match_token(parser_struct, TokenKind::LBRACE)?;
+    let field_list = parse_field_decl_list(parser_struct)?;
+    match_token(parser_struct, TokenKind::RBRACE)?;
+    match_token(parser_struct, TokenKind::SEMICOLON)?;
+
+    let struct_pos = start_pos.to(parser_struct.previous_position);
+    let lhs_child = Box::new(DeclType::StructDecl(StructDecl::new(
+        struct_pos,
+        Box::new(ident),
+        Box::new(field_list),
+    )));
+    let rhs_child = Box::new(parse_declaration_list(parser_struct, true)?);
+
+    let final_pos = start_pos.to(parser_struct.previous_position);
+    Ok(ListType::DeclList(DeclList::new(
+        final_pos, lhs_child, rhs_child,
+    )))
+}
+
+// field-decl -> type declarator ( "," declarator )* ";"
+fn parse_field_decl_list(parser_struct: &mut ParserData) -> ParseResult<ListType> {
+    let start_pos = parser_struct.current_position;
+
+    if !token_starts_type(&parser_struct.current_token.token_kind) {
+        return Ok(ListType::EmptyDeclList(EmptyDeclList::new(start_pos)));
+    }
+    let field_type = parse_type(parser_struct)?;
+    parse_field_declarators(parser_struct, Box::new(field_type))
+}
+
+fn parse_field_declarators(
+    parser_struct: &mut ParserData,
+    field_type: Box<AstTypes>,
+) -> ParseResult<ListType> {
+    let start_pos = parser_struct.current_position;
+
+    let ident = parse_identifier(parser_struct)?;
+    let declaration_type = parse_declarator(parser_struct, (*field_type).clone())?;
+    let field_pos = ident.span.to(parser_struct.previous_position);
+    let lhs_child = Box::new(DeclType::FieldDecl(FieldDecl::new(
+        field_pos,
+        Box::new(declaration_type),
+        Box::new(ident),
+    )));
+
+    let rhs_child = if parser_struct.current_token.token_kind == TokenKind::COMMA {
+        match_and_consume_next_token(parser_struct); // COMMA
+        parse_field_declarators(parser_struct, field_type)?
+    } else {
+        match_token(parser_struct, TokenKind::SEMICOLON)?;
+        parse_field_decl_list(parser_struct)?
+    };
+
+    let final_pos = start_pos.to(parser_struct.previous_position);
+    Ok(ListType::DeclList(DeclList::new(
+        final_pos,
+        lhs_child,
+        Box::new(rhs_child),
+    )))
+}
+
+// include-decl -> "#include" STRINGLITERAL, scanned as a single INCLUDE token
+fn parse_include_decl_list(parser_struct: &mut ParserData) -> ParseResult<ListType> {
+    let start_pos = parser_struct.current_position;
+
+    let lhs_child = Box::new(DeclType::IncludeDecl(IncludeDecl::new(
+        parser_struct.current_position,
+        parser_struct.current_token.spelling.clone(),
+    )));
+    match_and_consume_next_token(parser_struct); // INCLUDE
+
+    let rhs_child = Box::new(parse_declaration_list(parser_struct, true)?);
+
+    let final_pos = start_pos.to(parser_struct.previous_position);
+    Ok(ListType::DeclList(DeclList::new(
+        final_pos, lhs_child, rhs_child,
+    )))
+}
+
 // func-decl -> identifier para-list compound-stmt
 // var-decl -> init-declarator-list ";"
 fn parse_func_decl_list(
@@ -106,206 +234,236 @@ fn parse_func_decl_list(
     ident: Ident,
     is_global: bool,
     parser_struct: &mut ParserData,
-) -> ListType {
+) -> ParseResult<ListType> {
     let start_pos = parser_struct.current_position;
 
     let lhs_child = Box::new(DeclType::FuncDecl(parse_func_decl(
         function_type.clone(),
         ident.copy_with_null_decl(),
         parser_struct,
-    )));
+    )?));
 
     let rhs_child = if parser_struct.current_token.token_kind != TokenKind::EOF {
-        Box::new(parse_declaration_list(parser_struct, is_global))
+        Box::new(parse_declaration_list(parser_struct, is_global)?)
     } else {
         Box::new(ListType::EmptyDeclList(EmptyDeclList::new(
             parser_struct.current_position,
         )))
     };
 
-    let final_pos = finish_position(&start_pos, &parser_struct.current_position);
-    ListType::DeclList(DeclList::new(final_pos, lhs_child, rhs_child))
+    let final_pos = start_pos.to(parser_struct.previous_position);
+    Ok(ListType::DeclList(DeclList::new(
+        final_pos, lhs_child, rhs_child,
+    )))
 }
 
-// func-decl -> identifier para-list compound-stmt
+// func-decl -> identifier para-list ( compound-stmt | ";" )
 fn parse_func_decl(
     function_type: Box<AstTypes>,
     ident: Ident,
     parser_struct: &mut ParserData,
-) -> FuncDecl {
+) -> ParseResult<FuncDecl> {
     let start_pos = parser_struct.current_position;
 
-    let function_parameter_list_ast = parse_parameter_list(parser_struct);
-    let compound_stmt_ast = parse_compound_stmt(parser_struct);
+    let function_parameter_list_ast = parse_parameter_list(parser_struct)?;
+    let compound_stmt_ast = if parser_struct.current_token.token_kind == TokenKind::SEMICOLON {
+        // A prototype, to be defined further down.
+        let semicolon_pos = parser_struct.current_position;
+        match_and_consume_next_token(parser_struct); // SEMICOLON
+        StmtType::EmptyStmt(EmptyStmt::new(semicolon_pos))
+    } else {
+        parse_compound_stmt(parser_struct)?
+    };
 
-    let final_pos = finish_position(&start_pos, &parser_struct.current_position);
+    let final_pos = start_pos.to(parser_struct.previous_position);
 
-    FuncDecl::new(
+    Ok(FuncDecl::new(
         final_pos,
         Box::new(AstTypeVariant::Primitive(*function_type)),
         Box::new(ident),
         Box::new(function_parameter_list_ast),
         Box::new(compound_stmt_ast),
-    )
+    ))
 }
 
 // init-declarator-list-> init-declarator ( "," init-declarator )*
+// A const qualifier applies to every declarator in the list.
 fn parse_initial_declaration_list(
     parser_struct: &mut ParserData,
     decl_type: Box<AstTypes>,
     identifier: Box<Ident>,
     is_global: bool,
-) -> ListType {
+    is_const: bool,
+) -> ParseResult<ListType> {
     let start_pos = parser_struct.current_position;
 
-    let ident_spelling = (*identifier).spelling.clone();
-    let ident_source_pos = (*identifier).source_position;
+    let ident_spelling = identifier.spelling.clone();
+    let ident_source_pos = identifier.span;
     let ident_clone = Box::new(Ident::new(ident_spelling, ident_source_pos, None));
 
     let lhs_child =
-        parse_initial_declarator(parser_struct, decl_type.clone(), ident_clone, is_global);
+        parse_initial_declarator(parser_struct, &decl_type, ident_clone, is_global, is_const)?;
     let mut rhs_child = ListType::EmptyDeclList(EmptyDeclList::new(parser_struct.current_position));
 
     if parser_struct.current_token.token_kind == TokenKind::COMMA {
-        // Array initialisation expression.
         match_and_consume_next_token(parser_struct); // COMMA
-
-        let rhs_child =
-            parse_initial_declaration_list(parser_struct, decl_type, identifier, is_global);
-        let final_pos = finish_position(&start_pos, &parser_struct.current_position);
-
-        return ListType::DeclList(DeclList::new(
+        let next_identifier = parse_identifier(parser_struct)?;
+
+        let rhs_child = parse_initial_declaration_list(
+            parser_struct,
+            decl_type,
+            Box::new(next_identifier),
+            is_global,
+            is_const,
+        )?;
+        let final_pos = start_pos.to(parser_struct.previous_position);
+
+        return Ok(ListType::DeclList(DeclList::new(
             final_pos,
             Box::new(lhs_child),
             Box::new(rhs_child),
-        ));
+        )));
     }
 
-    match_and_consume_next_token(parser_struct); // SEMICOLON.
+    match_token(parser_struct, TokenKind::SEMICOLON)?;
 
-    if token_is_primitive_type(&parser_struct.current_token.token_kind) {
-        rhs_child = parse_declaration_list(parser_struct, is_global);
+    let next_kind = parser_struct.current_token.token_kind;
+    if token_starts_declaration(&next_kind) || (is_global && next_kind == TokenKind::INCLUDE) {
+        rhs_child = parse_declaration_list(parser_struct, is_global)?;
     }
 
-    let final_pos = finish_position(&start_pos, &parser_struct.current_position);
+    let final_pos = start_pos.to(parser_struct.previous_position);
 
-    ListType::DeclList(DeclList::new(
+    Ok(ListType::DeclList(DeclList::new(
         final_pos,
         Box::new(lhs_child),
         Box::new(rhs_child),
-    ))
+    )))
 }
 
 // init-declarator -> declarator ( "=" initializer )?
 fn parse_initial_declarator(
     parser_struct: &mut ParserData,
-    decl_type: Box<AstTypes>,
+    decl_type: &AstTypes,
     identifier: Box<Ident>,
     is_global: bool,
-) -> DeclType {
-    let start_pos = parser_struct.current_position;
-    let var_type = parse_declarator(parser_struct, decl_type);
+    is_const: bool,
+) -> ParseResult<DeclType> {
+    let start_pos = identifier.span;
+    let var_type = parse_declarator(parser_struct, decl_type.clone())?;
     let mut init_expr = ExprType::EmptyExpr(EmptyExpr::new(parser_struct.current_position));
 
     if parser_struct.current_token.token_kind == TokenKind::EQ {
         match_and_consume_next_token(parser_struct); // EQ
-        init_expr = parse_initialiser(parser_struct);
+        init_expr = parse_initialiser(parser_struct)?;
     }
 
-    let final_source_pos = finish_position(&start_pos, &parser_struct.current_position);
+    let final_source_pos = start_pos.to(parser_struct.previous_position);
 
     if is_global {
-        DeclType::GlobalVarDecl(GlobalVarDecl::new(
+        Ok(DeclType::GlobalVarDecl(GlobalVarDecl::new(
             final_source_pos,
             Box::new(var_type),
             identifier,
             Box::new(init_expr),
-        ))
+            is_const,
+        )))
     } else {
-        DeclType::LocalVarDecl(LocalVarDecl::new(
+        Ok(DeclType::LocalVarDecl(LocalVarDecl::new(
             final_source_pos,
             Box::new(var_type),
             identifier,
             Box::new(init_expr),
-        ))
+            is_const,
+        )))
     }
 }
 
-// declarator -> identifier
-// | identifier "[" INTLITERAL? "]"
-fn parse_declarator(parser_struct: &mut ParserData, decl_type: Box<AstTypes>) -> AstTypeVariant {
-    let start_pos = parser_struct.current_position;
+// declarator -> identifier ( "[" expr? "]" )*
+fn parse_declarator(
+    parser_struct: &mut ParserData,
+    decl_type: AstTypes,
+) -> ParseResult<AstTypeVariant> {
+    let mut dimensions = Vec::new();
+
+    while parser_struct.current_token.token_kind == TokenKind::LBRACKET {
+        let start_pos = parser_struct.current_position;
+        match_and_consume_next_token(parser_struct); // LBRACKET
+        let expression = parse_array_type_expr(parser_struct)?;
+        match_token(parser_struct, TokenKind::RBRACKET)?;
+        dimensions.push((start_pos, expression));
+    }
 
-    match parser_struct.current_token.token_kind {
-        TokenKind::LBRACKET => {
-            match_and_consume_next_token(parser_struct); // LBRACKET
-            let expression = parse_array_type_expr(parser_struct);
-            match_and_consume_next_token(parser_struct); // RBRACKET
-            let final_position =
-                finish_position(&start_pos, &parser_struct.current_token.token_position);
-
-            AstTypeVariant::Array(ArrayType::new(
-                final_position,
-                Arc::new(decl_type),
-                expression,
-            ))
-        }
-        _ => AstTypeVariant::Primitive(*decl_type),
+    // The last dimension is the innermost: int m[3][4] is 3 arrays of 4 ints.
+    let final_position = parser_struct.previous_position;
+    let mut declared_type = AstTypeVariant::Primitive(decl_type);
+    for (start_pos, expression) in dimensions.into_iter().rev() {
+        declared_type = AstTypeVariant::Array(ArrayType::new(
+            start_pos.to(final_position),
+            Arc::new(declared_type),
+            expression,
+        ));
     }
+    Ok(declared_type)
 }
 
-fn parse_array_type_expr(parser_struct: &mut ParserData) -> ExprType {
+// The size may be any expression; the checker insists it is an int constant, such as a const int.
+fn parse_array_type_expr(parser_struct: &mut ParserData) -> ParseResult<ExprType> {
     let start_pos = parser_struct.current_position;
 
     match parser_struct.current_token.token_kind {
-        TokenKind::INTLITERAL => parse_expr(parser_struct),
-        _ => ExprType::EmptyExpr(EmptyExpr::new(start_pos)),
+        TokenKind::RBRACKET => Ok(ExprType::EmptyExpr(EmptyExpr::new(start_pos))),
+        _ => parse_expr(parser_struct),
     }
 }
 
 // initialiser -> expr
-// | "{" expr ( "," expr )* "}"
-fn parse_initialiser(parser_struct: &mut ParserData) -> ExprType {
+// | "{" initialiser ( "," initialiser )* "}"
+fn parse_initialiser(parser_struct: &mut ParserData) -> ParseResult<ExprType> {
     let start_pos = parser_struct.current_position;
 
     match parser_struct.current_token.token_kind {
         TokenKind::LBRACE => {
             match_and_consume_next_token(parser_struct); // Consume LBRACE.
-            let array_expr_list = parse_array_expr_list(parser_struct);
-            let finish_pos = finish_position(&start_pos, &parser_struct.current_position);
-            ExprType::ArrayInitExpr(ArrayInitExpr::new(finish_pos, Box::new(array_expr_list)))
+            let array_expr_list = parse_array_expr_list(parser_struct)?;
+            let finish_pos = start_pos.to(parser_struct.previous_position);
+            Ok(ExprType::ArrayInitExpr(ArrayInitExpr::new(
+                finish_pos,
+                Box::new(array_expr_list),
+            )))
         }
         _ => parse_expr(parser_struct),
     }
 }
 
-fn parse_array_expr_list(parser_struct: &mut ParserData) -> ListType {
+fn parse_array_expr_list(parser_struct: &mut ParserData) -> ParseResult<ListType> {
     let start_pos = parser_struct.current_position;
 
-    let lhs_expr = parse_expr(parser_struct);
+    // A nested brace initialises one row of a multidimensional array.
+    let lhs_expr = parse_initialiser(parser_struct)?;
 
     match parser_struct.current_token.token_kind {
         TokenKind::COMMA => {
             match_and_consume_next_token(parser_struct); // Consume COMMA.
-            let rhs_array_expr = parse_array_expr_list(parser_struct);
-            let finish_pos = finish_position(&start_pos, &parser_struct.current_position);
+            let rhs_array_expr = parse_array_expr_list(parser_struct)?;
+            let finish_pos = start_pos.to(parser_struct.previous_position);
 
-            ListType::ArrayExprList(ArrayExprList::new(
+            Ok(ListType::ArrayExprList(ArrayExprList::new(
                 finish_pos,
                 lhs_expr,
                 Box::new(rhs_array_expr),
-            ))
+            )))
         }
         _ => {
-            let finish_pos = finish_position(&start_pos, &parser_struct.current_position);
-            match_and_consume_next_token(parser_struct); // Consume RBRACE.
-            ListType::ArrayExprList(ArrayExprList::new(
+            let finish_pos = start_pos.to(parser_struct.previous_position);
+            match_token(parser_struct, TokenKind::RBRACE)?;
+            Ok(ListType::ArrayExprList(ArrayExprList::new(
                 finish_pos,
                 lhs_expr,
                 Box::new(ListType::EmptyArrayExprList(EmptyArrayExprList::new(
                     finish_pos,
                 ))),
-            ))
+            )))
         }
     }
 }
@@ -313,61 +471,63 @@ fn parse_array_expr_list(parser_struct: &mut ParserData) -> ListType {
 // ======================================== Statements =============================================
 
 // compound-stmt -> "{" var-decl* stmt* "}"
-fn parse_compound_stmt(parser_struct: &mut ParserData) -> StmtType {
-    let start_pos = parser_struct.current_position;
-    match_and_consume_next_token(parser_struct); // Consumes LBRACE.
+fn parse_compound_stmt(parser_struct: &mut ParserData) -> ParseResult<StmtType> {
+    let start_pos = parser_struct.current_token.span;
+    match_token(parser_struct, TokenKind::LBRACE)?;
     let mut declare_list = ListType::EmptyDeclList(EmptyDeclList::new(start_pos));
 
-    if token_is_primitive_type(&parser_struct.current_token.token_kind) {
-        declare_list = parse_declaration_list(parser_struct, false);
+    if token_starts_declaration(&parser_struct.current_token.token_kind) {
+        declare_list = parse_declaration_list(parser_struct, false)?;
     }
 
-    let stmt_list_ast = parse_stmt_list(parser_struct);
-    // Consumers RCURLY
-    match_and_consume_next_token(parser_struct);
-    let final_source_pos = finish_position(&start_pos, &parser_struct.current_position);
+    let stmt_list_ast = parse_stmt_list(parser_struct)?;
+    match_token(parser_struct, TokenKind::RBRACE)?;
+    let final_source_pos = start_pos.to(parser_struct.previous_position);
 
-    StmtType::CompoundStmt(CompoundStmt::new(
+    Ok(StmtType::CompoundStmt(CompoundStmt::new(
         Box::new(declare_list),
         Box::new(stmt_list_ast),
         final_source_pos,
-    ))
+    )))
 }
 
-// Note, need to implement multiple statements being recursively parsed.
-fn parse_stmt_list(parser_struct: &mut ParserData) -> ListType {
+fn parse_stmt_list(parser_struct: &mut ParserData) -> ParseResult<ListType> {
     let start_pos = parser_struct.current_position;
 
     if parser_struct.current_token.token_kind == TokenKind::RBRACE {
-        return ListType::EmptyStmtList(EmptyStmtList::new(start_pos));
+        return Ok(ListType::EmptyStmtList(EmptyStmtList::new(start_pos)));
     }
 
-    let lhs_single_stmt = parse_single_stmt(parser_struct);
-    let final_source_pos = finish_position(&start_pos, &parser_struct.current_position.clone());
+    let lhs_single_stmt = parse_single_stmt(parser_struct)?;
+    let final_source_pos = start_pos.to(parser_struct.previous_position);
 
     let rhs = StmtList::new(
         final_source_pos,
         Box::new(lhs_single_stmt),
-        Box::new(parse_stmt_list(parser_struct)),
+        Box::new(parse_stmt_list(parser_struct)?),
     );
 
-    ListType::StmtList(rhs)
+    Ok(ListType::StmtList(rhs))
 }
 
 // stmt -> compound-stmt
 // | if-stmt
 // | for-stmt
 // | while-stmt
+// | do-while-stmt
+// | switch-stmt
 // | break-stmt
 // | continue-stmt
 // | return-stmt
 // | expr-stmt
-fn parse_single_stmt(parser_struct: &mut ParserData) -> StmtType {
+fn parse_single_stmt(parser_struct: &mut ParserData) -> ParseResult<StmtType> {
     match parser_struct.current_token.token_kind {
         TokenKind::LBRACE => parse_compound_stmt(parser_struct),
         TokenKind::IF => parse_if_statement(parser_struct),
         TokenKind::FOR => parse_for_statement(parser_struct),
         TokenKind::WHILE => parse_while_stmt(parser_struct),
+        TokenKind::DO => parse_do_while_stmt(parser_struct),
+        TokenKind::SWITCH => parse_switch_stmt(parser_struct),
         TokenKind::BREAK => parse_break_stmt(parser_struct),
         TokenKind::CONTINUE => parse_continue_stmt(parser_struct),
         TokenKind::RETURN => parse_return_stmt(parser_struct),
@@ -375,16 +535,13 @@ fn parse_single_stmt(parser_struct: &mut ParserData) -> StmtType {
             if EXPR_FIRST_SET.contains(&parser_struct.current_token.token_kind) {
                 parse_expr_stmt(parser_struct)
             } else {
-                panic!(
-                    "Invalid token kind for single statement: {:?}, at position: {:?}",
-                    parser_struct.current_token.token_kind, parser_struct.current_position
-                );
+                Err(syntax_error(parser_struct, "statement expected here"))
             }
         }
     }
 }
 
-fn parse_single_or_multiple_statements(parser_struct: &mut ParserData) -> StmtType {
+fn parse_single_or_multiple_statements(parser_struct: &mut ParserData) -> ParseResult<StmtType> {
     if parser_struct.current_token.token_kind == TokenKind::LBRACE {
         parse_compound_stmt(parser_struct)
     } else {
@@ -393,762 +550,809 @@ fn parse_single_or_multiple_statements(parser_struct: &mut ParserData) -> StmtTy
 }
 
 // if-stmt -> if "(" expr ")" stmt ( else stmt )?
-fn parse_if_statement(parser_struct: &mut ParserData) -> StmtType {
-    let start_pos = parser_struct.current_position;
+fn parse_if_statement(parser_struct: &mut ParserData) -> ParseResult<StmtType> {
+    let start_pos = parser_struct.current_token.span;
 
-    match_and_consume_next_token(parser_struct); // Consume TokenKind::IF
-    match_and_consume_next_token(parser_struct); // Consume TokenKind::LPAREN
+    match_token(parser_struct, TokenKind::IF)?;
+    match_token(parser_struct, TokenKind::LPAREN)?;
 
-    let if_expr = parse_expr(parser_struct);
-    match_and_consume_next_token(parser_struct); // Consume TokenKind::RPAREN
+    let if_expr = parse_expr(parser_struct)?;
+    match_token(parser_struct, TokenKind::RPAREN)?;
 
-    let if_stmt = parse_single_or_multiple_statements(parser_struct);
+    let if_stmt = parse_single_or_multiple_statements(parser_struct)?;
 
     if parser_struct.current_token.token_kind != TokenKind::ELSE {
-        let final_source_pos = finish_position(&start_pos, &parser_struct.current_position);
-        return StmtType::IfStmt(IfStmt::new(
+        let final_source_pos = start_pos.to(parser_struct.previous_position);
+        return Ok(StmtType::IfStmt(IfStmt::new(
             final_source_pos,
             Box::new(if_expr),
             Box::new(if_stmt),
             Box::new(StmtType::EmptyStmt(EmptyStmt::new(final_source_pos))),
-        ));
+        )));
     }
 
-    match_and_consume_next_token(parser_struct); // Consume TokenKind::ELSE
-    let else_stmt = parse_single_or_multiple_statements(parser_struct);
-    let final_source_pos = finish_position(&start_pos, &parser_struct.current_position);
-    StmtType::IfStmt(IfStmt::new(
+    match_token(parser_struct, TokenKind::ELSE)?;
+    let else_stmt = parse_single_or_multiple_statements(parser_struct)?;
+    let final_source_pos = start_pos.to(parser_struct.previous_position);
+    Ok(StmtType::IfStmt(IfStmt::new(
         final_source_pos,
         Box::new(if_expr),
         Box::new(if_stmt),
         Box::new(else_stmt),
-    ))
+    )))
 }
 
 // for-stmt -> for "(" expr? ";" expr? ";" expr? ")" stmt
-fn parse_for_statement(parser_struct: &mut ParserData) -> StmtType {
-    let start_pos = parser_struct.current_position;
+fn parse_for_statement(parser_struct: &mut ParserData) -> ParseResult<StmtType> {
+    let start_pos = parser_struct.current_token.span;
 
-    match_and_consume_next_token(parser_struct); // Consume TokenKind::FOR
-    match_and_consume_next_token(parser_struct); // Consume TokenKind::LPAREN
+    match_token(parser_struct, TokenKind::FOR)?;
+    match_token(parser_struct, TokenKind::LPAREN)?;
 
-    let expr1 = parse_expr(parser_struct);
-    match_and_consume_next_token(parser_struct); // Consume TokenKind::SEMICOLON
+    let expr1 = parse_expr(parser_struct)?;
+    match_token(parser_struct, TokenKind::SEMICOLON)?;
 
-    let expr2 = parse_expr(parser_struct);
-    match_and_consume_next_token(parser_struct); // Consume TokenKind::SEMICOLON
+    let expr2 = parse_expr(parser_struct)?;
+    match_token(parser_struct, TokenKind::SEMICOLON)?;
 
     let mut expr3 = ExprType::EmptyExpr(EmptyExpr::new(parser_struct.current_position));
     if EXPR_FIRST_SET.contains(&parser_struct.current_token.token_kind) {
-        expr3 = parse_expr(parser_struct);
+        expr3 = parse_expr(parser_struct)?;
     }
-    match_and_consume_next_token(parser_struct); // Consume TokenKind::RPAREN
+    match_token(parser_struct, TokenKind::RPAREN)?;
 
-    let final_source_pos = finish_position(&start_pos, &parser_struct.current_position);
+    let body = parse_single_or_multiple_statements(parser_struct)?;
+    let final_source_pos = start_pos.to(parser_struct.previous_position);
 
-    StmtType::ForStmt(ForStmt::new(
+    Ok(StmtType::ForStmt(ForStmt::new(
         final_source_pos,
         Box::new(expr1),
         Box::new(expr2),
         Box::new(expr3),
-        Box::new(parse_single_or_multiple_statements(parser_struct)),
-    ))
+        Box::new(body),
+    )))
 }
 
 // while-stmt -> while "(" expr ")" stmt
-fn parse_while_stmt(parser_struct: &mut ParserData) -> StmtType {
-    let start_pos = parser_struct.current_token.token_position;
+fn parse_while_stmt(parser_struct: &mut ParserData) -> ParseResult<StmtType> {
+    let start_pos = parser_struct.current_token.span;
 
-    match_and_consume_next_token(parser_struct); // Consume TokenKind::WHILE
-    match_and_consume_next_token(parser_struct); // Consume TokenKind::LPAREN
+    match_token(parser_struct, TokenKind::WHILE)?;
+    match_token(parser_struct, TokenKind::LPAREN)?;
 
-    let while_condition_ast = Box::new(parse_expr(parser_struct));
+    let while_condition_ast = Box::new(parse_expr(parser_struct)?);
 
-    match_and_consume_next_token(parser_struct); // Consume TokenKind::RPAREN
+    match_token(parser_struct, TokenKind::RPAREN)?;
 
-    let while_stmt = parse_single_or_multiple_statements(parser_struct);
+    let while_stmt = parse_single_or_multiple_statements(parser_struct)?;
 
-    let final_position = finish_position(
-        &start_pos,
-        &parser_struct.current_token.token_position.clone(),
-    );
+    let final_position = start_pos.to(parser_struct.previous_position);
 
-    StmtType::WhileStmt(WhileStmt::new(
+    Ok(StmtType::WhileStmt(WhileStmt::new(
         final_position,
         while_condition_ast,
         Box::new(while_stmt),
+    )))
+}
+
+// do-while-stmt -> do stmt while "(" expr ")" ";"
+fn parse_do_while_stmt(parser_struct: &mut ParserData) -> ParseResult<StmtType> {
+    let start_pos = parser_struct.current_token.span;
+
+    match_token(parser_struct, TokenKind::DO)?;
+    let do_stmt = parse_single_or_multiple_statements(parser_struct)?;
+
+    match_token(parser_struct, TokenKind::WHILE)?;
+    match_token(parser_struct, TokenKind::LPAREN)?;
+    let while_condition_ast = Box::new(parse_expr(parser_struct)?);
+    match_token(parser_struct, TokenKind::RPAREN)?;
+    match_token(parser_struct, TokenKind::SEMICOLON)?;
+
+    let final_position = start_pos.to(parser_struct.previous_position);
+
+    Ok(StmtType::DoWhileStmt(DoWhileStmt::new(
+        final_position,
+        Box::new(do_stmt),
+        while_condition_ast,
+    )))
+}
+
+// switch-stmt -> switch "(" expr ")" "{" case-stmt* "}"
+fn parse_switch_stmt(parser_struct: &mut ParserData) -> ParseResult<StmtType> {
+    let start_pos = parser_struct.current_token.span;
+
+    match_token(parser_struct, TokenKind::SWITCH)?;
+    match_token(parser_struct, TokenKind::LPAREN)?;
+    let switch_expr = parse_expr(parser_struct)?;
+    match_token(parser_struct, TokenKind::RPAREN)?;
+
+    match_token(parser_struct, TokenKind::LBRACE)?;
+    let case_list = parse_case_list(parser_struct)?;
+    match_token(parser_struct, TokenKind::RBRACE)?;
+
+    let final_position = start_pos.to(parser_struct.previous_position);
+
+    Ok(StmtType::SwitchStmt(SwitchStmt::new(
+        final_position,
+        Box::new(switch_expr),
+        Box::new(case_list),
+    )))
+}
+
+fn parse_case_list(parser_struct: &mut ParserData) -> ParseResult<ListType> {
+    let start_pos = parser_struct.current_position;
+
+    if parser_struct.current_token.token_kind == TokenKind::RBRACE {
+        return Ok(ListType::EmptyCaseList(EmptyCaseList::new(start_pos)));
+    }
+
+    let case_stmt = parse_case_stmt(parser_struct)?;
+    let final_source_pos = start_pos.to(parser_struct.previous_position);
+
+    Ok(ListType::CaseList(CaseList::new(
+        final_source_pos,
+        case_stmt,
+        Box::new(parse_case_list(parser_struct)?),
+    )))
+}
+
+// case-stmt -> ( case expr | default ) ":" stmt*
+fn parse_case_stmt(parser_struct: &mut ParserData) -> ParseResult<CaseStmt> {
+    let start_pos = parser_struct.current_token.span;
+
+    let case_expr = match parser_struct.current_token.token_kind {
+        TokenKind::CASE => {
+            match_and_consume_next_token(parser_struct); // Consume CASE.
+            parse_expr(parser_struct)?
+        }
+        TokenKind::DEFAULT => {
+            match_and_consume_next_token(parser_struct); // Consume DEFAULT.
+            ExprType::EmptyExpr(EmptyExpr::new(start_pos))
+        }
+        _ => return Err(syntax_error(parser_struct, "case or default expected here")),
+    };
+    match_token(parser_struct, TokenKind::COLON)?;
+
+    let case_body = parse_case_body(parser_struct)?;
+    let final_position = start_pos.to(parser_struct.previous_position);
+
+    Ok(CaseStmt::new(
+        final_position,
+        Box::new(case_expr),
+        Box::new(case_body),
     ))
 }
 
+// The statements of a case run up to the next label or the end of the switch.
+fn parse_case_body(parser_struct: &mut ParserData) -> ParseResult<ListType> {
+    let start_pos = parser_struct.current_position;
+
+    if matches!(
+        parser_struct.current_token.token_kind,
+        TokenKind::CASE | TokenKind::DEFAULT | TokenKind::RBRACE
+    ) {
+        return Ok(ListType::EmptyStmtList(EmptyStmtList::new(start_pos)));
+    }
+
+    let lhs_single_stmt = parse_single_stmt(parser_struct)?;
+    let final_source_pos = start_pos.to(parser_struct.previous_position);
+
+    Ok(ListType::StmtList(StmtList::new(
+        final_source_pos,
+        Box::new(lhs_single_stmt),
+        Box::new(parse_case_body(parser_struct)?),
+    )))
+}
+
 // break-stmt -> break ";"
-fn parse_break_stmt(parser_struct: &mut ParserData) -> StmtType {
-    let start_pos = parser_struct.current_token.token_position;
+fn parse_break_stmt(parser_struct: &mut ParserData) -> ParseResult<StmtType> {
+    let start_pos = parser_struct.current_token.span;
 
-    match_and_consume_next_token(parser_struct); // Consume TokenKind::BREAK
-    match_and_consume_next_token(parser_struct); // Consume TokenKind::SEMICOLON
+    match_token(parser_struct, TokenKind::BREAK)?;
+    match_token(parser_struct, TokenKind::SEMICOLON)?;
 
-    let final_position = finish_position(
-        &start_pos,
-        &parser_struct.current_token.token_position.clone(),
-    );
+    let final_position = start_pos.to(parser_struct.previous_position);
 
-    StmtType::BreakStmt(BreakStmt::new(final_position))
+    Ok(StmtType::BreakStmt(BreakStmt::new(final_position)))
 }
 
 // continue-stmt       -> continue ";"
-fn parse_continue_stmt(parser_struct: &mut ParserData) -> StmtType {
-    let start_pos = parser_struct.current_token.token_position;
-    match_and_consume_next_token(parser_struct);
-    match_and_consume_next_token(parser_struct);
+fn parse_continue_stmt(parser_struct: &mut ParserData) -> ParseResult<StmtType> {
+    let start_pos = parser_struct.current_token.span;
+    match_token(parser_struct, TokenKind::CONTINUE)?;
+    match_token(parser_struct, TokenKind::SEMICOLON)?;
 
-    StmtType::ContinueStmt(ContinueStmt::new(start_pos))
+    let final_position = start_pos.to(parser_struct.previous_position);
+
+    Ok(StmtType::ContinueStmt(ContinueStmt::new(final_position)))
 }
 
 // return-stmt         -> return expr? ";"
-fn parse_return_stmt(parser_struct: &mut ParserData) -> StmtType {
-    let start_pos = parser_struct.current_token.token_position;
-
-    match_and_consume_next_token(parser_struct); // Consume TokenKind::RETURN
-
-    if EXPR_FIRST_SET.contains(&parser_struct.current_token.token_kind) {
-        let expr = Box::new(parse_expr(parser_struct));
-        let final_position = finish_position(
-            &start_pos,
-            &parser_struct.current_token.token_position.clone(),
-        );
-        return StmtType::ReturnStmt(ReturnStmt::new(final_position, expr));
+fn parse_return_stmt(parser_struct: &mut ParserData) -> ParseResult<StmtType> {
+    let start_pos = parser_struct.current_token.span;
+
+    match_token(parser_struct, TokenKind::RETURN)?;
+
+    if parser_struct.current_token.token_kind != TokenKind::SEMICOLON
+        && EXPR_FIRST_SET.contains(&parser_struct.current_token.token_kind)
+    {
+        let expr = Box::new(parse_expr(parser_struct)?);
+        match_token(parser_struct, TokenKind::SEMICOLON)?;
+        let final_position = start_pos.to(parser_struct.previous_position);
+        return Ok(StmtType::ReturnStmt(ReturnStmt::new(final_position, expr)));
     };
 
-    // Consume TokenKind::SEMICOLON
-    match_and_consume_next_token(parser_struct);
-    let final_position = finish_position(
-        &start_pos,
-        &parser_struct.current_token.token_position.clone(),
-    );
+    match_token(parser_struct, TokenKind::SEMICOLON)?;
+    let final_position = start_pos.to(parser_struct.previous_position);
     let empty_expr = ExprType::EmptyExpr(EmptyExpr::new(final_position));
 
-    StmtType::ReturnStmt(ReturnStmt::new(final_position, Box::new(empty_expr)))
+    Ok(StmtType::ReturnStmt(ReturnStmt::new(
+        final_position,
+        Box::new(empty_expr),
+    )))
 }
 
 // ====================================== Expressions ==============================================
 
 // expr-stmt -> expr? ";"
-fn parse_expr_stmt(parser_struct: &mut ParserData) -> StmtType {
-    // Doesn't consume )
-    let mut start_pos = parser_struct.current_position;
+fn parse_expr_stmt(parser_struct: &mut ParserData) -> ParseResult<StmtType> {
+    let start_pos = parser_struct.current_token.span;
 
     match parser_struct.current_token.token_kind {
-        kind if EXPR_FIRST_SET.contains(&kind) => {
-            let expr_ast = parse_expr(parser_struct);
-            match_and_consume_next_token(parser_struct); // Consume TokenKind::SEMICOLON
-            let final_source_pos = finish_position(&start_pos, &parser_struct.current_position);
-            let expr_stmt = ExprStmt::new(final_source_pos, expr_ast);
-            StmtType::ExprStmt(expr_stmt)
-        }
         TokenKind::SEMICOLON => {
             match_and_consume_next_token(parser_struct);
-            let final_source_pos = finish_position(
-                &start_pos,
-                &parser_struct.current_token.token_position.clone(),
-            );
+            let final_source_pos = start_pos.to(parser_struct.previous_position);
 
             let empty_expr = ExprType::EmptyExpr(EmptyExpr::new(final_source_pos));
-            StmtType::ExprStmt(ExprStmt::new(final_source_pos, empty_expr))
+            Ok(StmtType::ExprStmt(ExprStmt::new(
+                final_source_pos,
+                empty_expr,
+            )))
         }
-        _ => {
-            panic!("Error in expr statement.");
+        kind if EXPR_FIRST_SET.contains(&kind) => {
+            let expr_ast = parse_expr(parser_struct)?;
+            match_token(parser_struct, TokenKind::SEMICOLON)?;
+            let final_source_pos = start_pos.to(parser_struct.previous_position);
+            let expr_stmt = ExprStmt::new(final_source_pos, expr_ast);
+            Ok(StmtType::ExprStmt(expr_stmt))
         }
+        _ => Err(syntax_error(parser_struct, "illegal expression statement")),
     }
 }
 
 // expr -> assignment-expr
-fn parse_expr(parser_struct: &mut ParserData) -> ExprType {
+fn parse_expr(parser_struct: &mut ParserData) -> ParseResult<ExprType> {
     if let TokenKind::SEMICOLON = parser_struct.current_token.token_kind {
-        return ExprType::EmptyExpr(EmptyExpr::new(parser_struct.current_position));
-    }
-    parse_assign_expr(parser_struct)
-}
-
-// assignment-expr  -> cond-or-expr assignment-expr
-fn parse_assign_expr(parser_struct: &mut ParserData) -> ExprType {
-    let mut assign_expr_pos = parser_struct.current_token.token_position;
-    let mut lhs_expr = parse_conditional_or_expression(parser_struct);
-
-    match parser_struct.current_token.token_kind {
-        TokenKind::EQ => {
-            let eq = consume_operator(parser_struct);
-            let rhs = parse_assign_expr(parser_struct);
-            let assign_expr = AssignExpr::new(
-                finish_position(&assign_expr_pos, &rhs.get_source_position()),
-                Box::new(lhs_expr),
-                Box::new(rhs),
-            );
-            ExprType::AssignExpr(assign_expr)
-        }
-        _ => lhs_expr,
+        return Ok(ExprType::EmptyExpr(EmptyExpr::new(
+            parser_struct.current_position,
+        )));
     }
+    parse_expr_with_precedence(parser_struct, ASSIGNMENT_PRECEDENCE)
 }
 
-// cond-or-expr        -> cond-and-expr cond-or-expr'
-fn parse_conditional_or_expression(parser_struct: &mut ParserData) -> ExprType {
-    let mut cond_or_expr_pos = parser_struct.current_token.token_position.clone();
-    let main_expr = parse_conditional_and_expression(parser_struct);
-    parse_conditional_or_expression_tail(&cond_or_expr_pos, main_expr, parser_struct)
-}
-
-// cond-or-expr'      -> "||" cond-and-expr cond-or-expr'
-// |  ε
-fn parse_conditional_or_expression_tail(
-    start_pos: &SourcePosition,
-    first_expr: ExprType,
+// assignment-expr -> cond-expr ( ( "=" | "+=" | "-=" | "*=" | "/=" ) assignment-expr )?
+// cond-expr       -> cond-or-expr ( "?" expr ":" cond-expr )?
+// cond-or-expr    -> cond-and-expr ( "||" cond-and-expr )*
+// cond-and-expr   -> bit-or-expr ( "&&" bit-or-expr )*
+// bit-or-expr     -> bit-xor-expr ( "|" bit-xor-expr )*
+// bit-xor-expr    -> bit-and-expr ( "^" bit-and-expr )*
+// bit-and-expr    -> equality-expr ( "&" equality-expr )*
+// equality-expr   -> rel-expr ( ( "==" | "!=" ) rel-expr )*
+// rel-expr        -> shift-expr ( ( "<" | "<=" | ">" | ">=" ) shift-expr )*
+// shift-expr      -> additive-expr ( ( "<<" | ">>" ) additive-expr )*
+// additive-expr   -> multiplicative-expr ( ( "+" | "-" ) multiplicative-expr )*
+// multiplicative-expr -> unary-expr ( ( "*" | "/" | "%" ) unary-expr )*
+//
+// The ladder above is driven by INFIX_OPERATORS rather than one function per level.
+// Precedence climbing: parse a unary operand, then fold in every infix operator that binds at
+// least as tightly as min_precedence.
+fn parse_expr_with_precedence(
     parser_struct: &mut ParserData,
-) -> ExprType {
-    let mut cond_or_expr_tail_pos = start_pos.clone();
-    match parser_struct.current_token.token_kind {
-        TokenKind::OROR => {
-            let or = consume_operator(parser_struct);
-            let second_expr = parse_conditional_and_expression(parser_struct);
-            let binary_expr = BinaryExpr::new(
-                finish_position(start_pos, &second_expr.get_source_position()),
-                Box::new(first_expr),
-                or,
-                Box::new(second_expr),
-            );
-            parse_conditional_or_expression_tail(
-                &cond_or_expr_tail_pos,
-                ExprType::BinaryExpr(binary_expr),
-                parser_struct,
-            )
+    min_precedence: u8,
+) -> ParseResult<ExprType> {
+    let start_pos = parser_struct.current_token.span;
+    let mut lhs_expr = parse_unary_expression(parser_struct)?;
+
+    while let Some(infix_operator) = INFIX_OPERATORS
+        .get(&parser_struct.current_token.token_kind)
+        .copied()
+    {
+        if infix_operator.precedence < min_precedence {
+            break;
         }
-        _ => first_expr,
-    }
-}
-
-// *cond-and-expr* -> equality-expr cond-and-expr'
-fn parse_conditional_and_expression(parser_struct: &mut ParserData) -> ExprType {
-    let mut cond_and_expr_pos = parser_struct.current_token.token_position.clone();
-    let mut main_expr = parse_equality_expression(parser_struct);
-    parse_conditional_and_expression_tail(&cond_and_expr_pos, main_expr, parser_struct)
-}
-
-// cond-and-expr' -> "&&" equality-expr cond-and-expr' | epsilon
-fn parse_conditional_and_expression_tail(
-    start_pos: &SourcePosition,
-    first_expr: ExprType,
-    parser_struct: &mut ParserData,
-) -> ExprType {
-    let mut and_expr_tail_pos = start_pos.clone();
-
-    match parser_struct.current_token.token_kind {
-        TokenKind::ANDAND => {
-            let and = consume_operator(parser_struct);
-            let second_expr = parse_equality_expression(parser_struct);
-            let binary_expr = BinaryExpr::new(
-                finish_position(start_pos, &second_expr.get_source_position()),
-                Box::new(first_expr),
-                and,
-                Box::new(second_expr),
-            );
-            parse_conditional_and_expression_tail(
-                &and_expr_tail_pos,
-                ExprType::BinaryExpr(binary_expr),
-                parser_struct,
-            )
+        if infix_operator.node == InfixNode::Conditional {
+            lhs_expr = parse_conditional_expr(parser_struct, start_pos, lhs_expr)?;
+            continue;
         }
-        _ => first_expr,
-    }
-}
 
-// *equality-expr* -> rel-expr equality-expr'
-fn parse_equality_expression(parser_struct: &mut ParserData) -> ExprType {
-    let mut equality_expr_pos = parser_struct.current_token.token_position.clone();
-    let mut lhs_expr = parse_relational_expression(parser_struct);
-    parse_equality_expression_tail(&equality_expr_pos, lhs_expr, parser_struct)
-}
+        let op = consume_operator(parser_struct);
+        let rhs_min_precedence = match infix_operator.associativity {
+            Associativity::Left => infix_operator.precedence + 1,
+            Associativity::Right => infix_operator.precedence,
+        };
+        let rhs_expr = parse_expr_with_precedence(parser_struct, rhs_min_precedence)?;
+        let final_pos = start_pos.to(*rhs_expr.get_span());
 
-// equality-expr' -> "==" rel-expr equality-expr'
-// | "!=" rel-expr equality-expr'
-// | epsilon
-fn parse_equality_expression_tail(
-    start_pos: &SourcePosition,
-    lhs_expr: ExprType,
-    parser_struct: &mut ParserData,
-) -> ExprType {
-    let mut equality_expr_tail_pos = parser_struct.current_token.token_position.clone();
-    match parser_struct.current_token.token_kind {
-        TokenKind::EQEQ | TokenKind::NOTEQ => {
-            let op = consume_operator(parser_struct);
-            let rhs_expr = parse_relational_expression(parser_struct);
-            let binary_expr = BinaryExpr::new(
-                finish_position(start_pos, &rhs_expr.get_source_position()),
+        lhs_expr = match infix_operator.node {
+            InfixNode::Assign => ExprType::AssignExpr(AssignExpr::new(
+                final_pos,
                 Box::new(lhs_expr),
-                op,
                 Box::new(rhs_expr),
-            );
-            parse_equality_expression_tail(
-                &equality_expr_tail_pos,
-                ExprType::BinaryExpr(binary_expr),
-                parser_struct,
-            )
-        }
-        _ => lhs_expr,
-    }
-}
-
-// rel-expr* -> additive-expr rel-expr'
-fn parse_relational_expression(parser_struct: &mut ParserData) -> ExprType {
-    let mut rel_expr_pos = parser_struct.current_token.token_position.clone();
-    let lhs_expr = parse_additive_expression(parser_struct);
-    parse_relational_expression_tail(&rel_expr_pos, lhs_expr, parser_struct)
-}
-
-// rel-expr' -> "<" additive-expr rel-expr'
-// |  "<=" additive-expr rel-expr'
-// |  ">" additive-expr rel-expr'
-// |  ">=" additive-expr rel-expr'
-// |  ε
-fn parse_relational_expression_tail(
-    start_pos: &SourcePosition,
-    lhs_expr: ExprType,
-    parser_struct: &mut ParserData,
-) -> ExprType {
-    let mut pos = parser_struct.current_token.token_position.clone();
-    match parser_struct.current_token.token_kind {
-        TokenKind::LT | TokenKind::LTEQ | TokenKind::GT | TokenKind::GTEQ => {
-            let op = consume_operator(parser_struct);
-            let rhs_expr = parse_additive_expression(parser_struct);
-            let binary_expr = BinaryExpr::new(
-                finish_position(start_pos, &rhs_expr.get_source_position()),
+            )),
+            InfixNode::CompoundAssign => ExprType::CompoundAssignExpr(CompoundAssignExpr::new(
+                final_pos,
                 Box::new(lhs_expr),
                 op,
                 Box::new(rhs_expr),
-            );
-            parse_relational_expression_tail(&pos, ExprType::BinaryExpr(binary_expr), parser_struct)
-        }
-        _ => lhs_expr,
-    }
-}
-
-// additive-expr       -> multiplicative-expr additive-expr'
-fn parse_additive_expression(parser_struct: &mut ParserData) -> ExprType {
-    let mut pos = parser_struct.current_token.token_position.clone();
-    let lhs_expr = parse_multiplicative_expression(parser_struct);
-    parse_additive_expression_tail(&pos, lhs_expr, parser_struct)
-}
-
-// additive-expr'      | "+" multiplicative-expr additive-expr'
-// |  "-" multiplicative-expr additive-expr'
-// |  ε
-fn parse_additive_expression_tail(
-    start_pos: &SourcePosition,
-    lhs_expr: ExprType,
-    parser_struct: &mut ParserData,
-) -> ExprType {
-    let mut pos = parser_struct.current_token.token_position.clone();
-    match parser_struct.current_token.token_kind {
-        TokenKind::PLUS | TokenKind::MINUS => {
-            let op = consume_operator(parser_struct);
-            let rhs_expr = parse_multiplicative_expression(parser_struct);
-            let binary_expr = BinaryExpr::new(
-                finish_position(start_pos, &rhs_expr.get_source_position()),
+            )),
+            InfixNode::Binary => ExprType::BinaryExpr(BinaryExpr::new(
+                final_pos,
                 Box::new(lhs_expr),
                 op,
                 Box::new(rhs_expr),
-            );
-            parse_additive_expression_tail(&pos, ExprType::BinaryExpr(binary_expr), parser_struct)
-        }
-        _ => lhs_expr,
+            )),
+            InfixNode::Conditional => unreachable!("handled by parse_conditional_expr"),
+        };
     }
-}
 
-// multiplicative-expr -> unary-expr multiplicative-expr'
-fn parse_multiplicative_expression(parser_struct: &mut ParserData) -> ExprType {
-    let mut start_pos = parser_struct.current_token.token_position;
-    let lhs_expr = parse_unary_expression(parser_struct);
-    parse_multiplicative_expression_tail(&start_pos, lhs_expr, parser_struct)
+    Ok(lhs_expr)
 }
 
-// multiplicative-expr'-> "*" unary-expr multiplicative-expr'
-// | "/" unary-expr multiplicative-expr'
-// |  ε
-fn parse_multiplicative_expression_tail(
-    start_pos: &SourcePosition,
-    lhs_expr: ExprType,
+// The rest of a cond-expr once its condition has been parsed.  Any expression may sit between
+// ? and :, while the false branch nests to the right: a ? b : c ? d : e.
+fn parse_conditional_expr(
     parser_struct: &mut ParserData,
-) -> ExprType {
-    let mut pos = parser_struct.current_token.token_position;
-    match parser_struct.current_token.token_kind {
-        TokenKind::MULT | TokenKind::DIV => {
-            let op = consume_operator(parser_struct);
-            let rhs_expr = parse_unary_expression(parser_struct);
-            let binary_expr = BinaryExpr::new(
-                finish_position(start_pos, &rhs_expr.get_source_position()),
-                Box::new(lhs_expr),
-                op,
-                Box::new(rhs_expr),
-            );
-            ExprType::BinaryExpr(binary_expr)
-        }
-        _ => lhs_expr,
-    }
+    start_pos: Span,
+    condition: ExprType,
+) -> ParseResult<ExprType> {
+    match_token(parser_struct, TokenKind::QUESTION)?;
+    let true_expr = parse_expr_with_precedence(parser_struct, ASSIGNMENT_PRECEDENCE)?;
+    match_token(parser_struct, TokenKind::COLON)?;
+    let false_expr = parse_expr_with_precedence(parser_struct, CONDITIONAL_PRECEDENCE)?;
+
+    let final_pos = start_pos.to(*false_expr.get_span());
+    Ok(ExprType::ConditionalExpr(ConditionalExpr::new(
+        final_pos,
+        Box::new(condition),
+        Box::new(true_expr),
+        Box::new(false_expr),
+    )))
 }
 
-// unary-expr          -> "+" un
+// unary-expr -> "+" unary-expr
+// |  "-" unary-expr
 // |  "!" unary-expr
-// |  primary-expr
-fn parse_unary_expression(parser_struct: &mut ParserData) -> ExprType {
-    let mut start_pos = parser_struct.current_token.token_position; // assuming `new` is a constructor for `SourcePosition`
-
-    match parser_struct.current_token.token_kind {
-        TokenKind::PLUS | TokenKind::MINUS | TokenKind::NOT => {
+// |  "~" unary-expr
+// |  "++" unary-expr
+// |  "--" unary-expr
+// |  postfix-expr
+fn parse_unary_expression(parser_struct: &mut ParserData) -> ParseResult<ExprType> {
+    let start_pos = parser_struct.current_token.span;
+
+    match PREFIX_OPERATORS
+        .get(&parser_struct.current_token.token_kind)
+        .copied()
+    {
+        Some(precedence) => {
             let op = consume_operator(parser_struct);
-            let lhs_expr = parse_unary_expression(parser_struct);
+            let operand = parse_expr_with_precedence(parser_struct, precedence)?;
             let unary = UnaryExpr::new(
-                finish_position(&start_pos, &lhs_expr.get_source_position()),
+                start_pos.to(*operand.get_span()),
                 op,
-                Box::new(lhs_expr),
+                Box::new(operand),
             );
-            ExprType::UnaryExpr(unary)
+            Ok(ExprType::UnaryExpr(unary))
         }
+        None => parse_postfix_expr(parser_struct),
+    }
+}
 
-        _ => parse_primary_expr(parser_struct),
+// postfix-expr -> primary-expr ( "++" | "--" | "[" expr "]" | "." identifier )*
+fn parse_postfix_expr(parser_struct: &mut ParserData) -> ParseResult<ExprType> {
+    let start_pos = parser_struct.current_token.span;
+    let mut expr = parse_primary_expr(parser_struct)?;
+
+    loop {
+        expr = match parser_struct.current_token.token_kind {
+            TokenKind::PLUSPLUS | TokenKind::MINUSMINUS => {
+                let op = consume_operator(parser_struct);
+                let final_pos = start_pos.to(parser_struct.previous_position);
+                ExprType::PostfixExpr(PostfixExpr::new(final_pos, Box::new(expr), op))
+            }
+            TokenKind::LBRACKET => {
+                match_and_consume_next_token(parser_struct); // LBRACKET
+                let index = parse_expr(parser_struct)?;
+                match_token(parser_struct, TokenKind::RBRACKET)?;
+                let final_pos = start_pos.to(parser_struct.previous_position);
+                ExprType::ArrayExpr(ArrayExpr::new(final_pos, Box::new(expr), Box::new(index)))
+            }
+            TokenKind::DOT => {
+                match_and_consume_next_token(parser_struct); // DOT
+                let field = parse_identifier(parser_struct)?;
+                let final_pos = start_pos.to(parser_struct.previous_position);
+                ExprType::FieldExpr(FieldExpr::new(final_pos, Box::new(expr), field))
+            }
+            _ => return Ok(expr),
+        };
     }
 }
 
 // primary-expr        -> identifier arg-list?
-// | identifier "[" expr "]"
 // | "(" expr ")"
+// | "(" type ")" unary-expr
 // | INTLITERAL
 // | FLOATLITERAL
 // | BOOLLITERAL
+// | CHARLITERAL
 // | STRINGLITERAL
-fn parse_primary_expr(parser_struct: &mut ParserData) -> ExprType {
-    let start_pos = parser_struct.current_token.token_position;
+fn parse_primary_expr(parser_struct: &mut ParserData) -> ParseResult<ExprType> {
+    let start_pos = parser_struct.current_token.span;
 
     match parser_struct.current_token.token_kind {
         TokenKind::ID => {
-            let lhs_ident = parse_identifier(parser_struct);
-            let sim_vast = VarUntyped::new(
-                parser_struct.current_position.clone(),
-                lhs_ident.copy_with_null_decl(),
-            );
+            let lhs_ident = parse_identifier(parser_struct)?;
+            let sim_vast =
+                VarUntyped::new(lhs_ident.span, lhs_ident.copy_with_null_decl());
 
             match parser_struct.current_token.token_kind {
-                TokenKind::LBRACKET => {
-                    match_and_consume_next_token(parser_struct); // consume '['
-                    let rhs_expr = parse_expr(parser_struct);
-                    match_and_consume_next_token(parser_struct); // consume ']'
-
-                    let finish_pos = finish_position(&start_pos, &parser_struct.current_position);
-                    ExprType::ArrayExpr(ArrayExpr::new(finish_pos, sim_vast, Box::new(rhs_expr)))
-                }
                 TokenKind::LPAREN => {
-                    let args = parse_argument_list(parser_struct);
-                    let finish_pos = finish_position(&start_pos, &parser_struct.current_position);
-
-                    ExprType::CallExpr(CallExpr::new(finish_pos, lhs_ident, Box::new(args)))
-                }
-                _ => {
-                    let finish_pos = finish_position(&start_pos, &parser_struct.current_position);
-                    ExprType::VarExpr(VarExpr::new(finish_pos, sim_vast))
+                    let args = parse_argument_list(parser_struct)?;
+                    let finish_pos = start_pos.to(parser_struct.previous_position);
+
+                    Ok(ExprType::CallExpr(CallExpr::new(
+                        finish_pos,
+                        lhs_ident,
+                        Box::new(args),
+                    )))
                 }
+                _ => Ok(ExprType::VarExpr(VarExpr::new(start_pos, sim_vast))),
             }
         }
         TokenKind::LPAREN => {
             match_and_consume_next_token(parser_struct); // consume '('
-            let expr_ast = parse_expr(parser_struct);
-            match_and_consume_next_token(parser_struct); // consume ')'
-            expr_ast
+
+            // No expression starts with a type, so one token tells a cast from a grouping.
+            if token_starts_type(&parser_struct.current_token.token_kind) {
+                let cast_type = parse_type(parser_struct)?;
+                match_token(parser_struct, TokenKind::RPAREN)?;
+                let operand = parse_unary_expression(parser_struct)?;
+
+                let final_pos = start_pos.to(parser_struct.previous_position);
+                return Ok(ExprType::CastExpr(CastExpr::new(
+                    final_pos,
+                    Box::new(cast_type),
+                    Box::new(operand),
+                )));
+            }
+
+            let expr_ast = parse_expr(parser_struct)?;
+            match_token(parser_struct, TokenKind::RPAREN)?;
+
+            let final_pos = start_pos.to(parser_struct.previous_position);
+            Ok(ExprType::ParenExpr(ParenExpr::new(
+                final_pos,
+                Box::new(expr_ast),
+            )))
         }
         TokenKind::INTLITERAL => {
-            let int_literal = parse_int_literal(parser_struct);
-            let final_pos = finish_position(&start_pos, &parser_struct.current_position);
-            ExprType::IntExpr(IntExpr::new(final_pos, int_literal))
+            let int_literal = parse_int_literal(parser_struct)?;
+            Ok(ExprType::IntExpr(IntExpr::new(start_pos, int_literal)))
         }
         TokenKind::FLOATLITERAL => {
-            let float_literal = parse_float_literal(parser_struct);
-            let final_pos = finish_position(&start_pos, &parser_struct.current_position);
-            ExprType::FloatExpr(FloatExpr::new(final_pos, float_literal))
+            let float_literal = parse_float_literal(parser_struct)?;
+            Ok(ExprType::FloatExpr(FloatExpr::new(
+                start_pos,
+                float_literal,
+            )))
         }
         TokenKind::BOOLEANLITERAL => {
-            let bool_literal = parse_boolean_literal(parser_struct);
-            let final_pos = finish_position(&start_pos, &parser_struct.current_position);
-            ExprType::BooleanExpr(BooleanExpr::new(final_pos, bool_literal))
+            let bool_literal = parse_boolean_literal(parser_struct)?;
+            Ok(ExprType::BooleanExpr(BooleanExpr::new(
+                start_pos,
+                bool_literal,
+            )))
+        }
+        TokenKind::CHARLITERAL => {
+            let char_literal = parse_char_literal(parser_struct)?;
+            Ok(ExprType::CharExpr(CharExpr::new(start_pos, char_literal)))
         }
         TokenKind::STRINGLITERAL => {
-            let string_literal = parse_string_literal(parser_struct);
-            match string_literal {
-                ParserResult::Ok(ast_node) => match ast_node {
-                    AstNode::StringLiteral(string_lit) => {
-                        let string_expr =
-                            StringExpr::new(string_lit.source_position, Box::new(string_lit));
-                        ExprType::StringExpr(string_expr)
-                    }
-                    _ => panic!("Expected a StringLiteral"),
-                },
-                ParserResult::Error { error_string } => panic!("Should be no errors."),
-                ParserResult::NoMatch { expect } => panic!("Should always match."),
-            }
+            let string_lit = parse_string_literal(parser_struct)?;
+            let string_expr = StringExpr::new(string_lit.span, Box::new(string_lit));
+            Ok(ExprType::StringExpr(string_expr))
         }
-        _ => panic!(
-            "Illegal primary expression {:?}",
-            parser_struct.current_token
-        ),
+        _ => Err(syntax_error(parser_struct, "illegal primary expression")),
     }
 }
 
 // =================================== Parameters and Arguments ====================================
 
-fn parse_parameter_list(parser_data: &mut ParserData) -> ListType {
-    let start_pos = parser_data.current_token.token_position.clone();
-    match_and_consume_next_token(parser_data); // Consume LPAREN
+fn parse_parameter_list(parser_data: &mut ParserData) -> ParseResult<ListType> {
+    let start_pos = parser_data.current_token.span;
+    match_token(parser_data, TokenKind::LPAREN)?;
 
     if parser_data.current_token.token_kind != TokenKind::RPAREN {
-        let proper_para_list = parse_proper_parameter_list(parser_data);
-        match_and_consume_next_token(parser_data); // Consume RPAREN
-        proper_para_list
+        let proper_para_list = parse_proper_parameter_list(parser_data)?;
+        match_token(parser_data, TokenKind::RPAREN)?;
+        Ok(proper_para_list)
     } else {
         match_and_consume_next_token(parser_data); // Consume RPAREN
-        let final_pos = finish_position(&start_pos, &parser_data.current_token.token_position);
-        ListType::EmptyParamList(EmptyParamList::new(final_pos))
+        let final_pos = start_pos.to(parser_data.previous_position);
+        Ok(ListType::EmptyParamList(EmptyParamList::new(final_pos)))
     }
 }
 
-fn parse_proper_parameter_list(parser_data: &mut ParserData) -> ListType {
-    let start_pos = parser_data.current_token.token_position.clone();
-    let param_decl = parse_parameter_declaration(parser_data);
+fn parse_proper_parameter_list(parser_data: &mut ParserData) -> ParseResult<ListType> {
+    let start_pos = parser_data.current_token.span;
+    let param_decl = parse_parameter_declaration(parser_data)?;
 
     match parser_data.current_token.token_kind {
         TokenKind::COMMA => {
             match_and_consume_next_token(parser_data);
-            let para_list = parse_proper_parameter_list(parser_data);
-            let final_pos = finish_position(&start_pos, &parser_data.current_token.token_position);
+            let para_list = parse_proper_parameter_list(parser_data)?;
+            let final_pos = start_pos.to(parser_data.previous_position);
 
-            ListType::ParamList(ParamList::new(final_pos, param_decl, Box::new(para_list)))
+            Ok(ListType::ParamList(ParamList::new(
+                final_pos,
+                param_decl,
+                Box::new(para_list),
+            )))
         }
         _ => {
-            let empty_para_list = ListType::EmptyParamList(EmptyParamList::new(start_pos));
-            ListType::ParamList(ParamList::new(
-                start_pos,
+            let final_pos = start_pos.to(parser_data.previous_position);
+            let empty_para_list = ListType::EmptyParamList(EmptyParamList::new(final_pos));
+            Ok(ListType::ParamList(ParamList::new(
+                final_pos,
                 param_decl,
                 Box::new(empty_para_list),
-            ))
+            )))
         }
     }
 }
 
-fn parse_parameter_declaration(parser_data: &mut ParserData) -> ParaDecl {
-    let start_pos = parser_data.current_token.token_position;
-    let param_type = parse_type(parser_data);
-    let ident = parse_identifier(parser_data);
+fn parse_parameter_declaration(parser_data: &mut ParserData) -> ParseResult<ParaDecl> {
+    let start_pos = parser_data.current_token.span;
+    let is_const = parser_data.current_token.token_kind == TokenKind::CONST;
+    if is_const {
+        match_and_consume_next_token(parser_data); // CONST
+    }
+    let param_type = parse_type(parser_data)?;
+    let ident = parse_identifier(parser_data)?;
 
     match parser_data.current_token.token_kind {
         TokenKind::LBRACKET => {
-            let type_variant = parse_declarator(parser_data, Box::new(param_type));
-            let final_pos = finish_position(&start_pos, &parser_data.current_token.token_position);
-            ParaDecl::new(final_pos, Box::new(type_variant), Box::new(ident))
+            let type_variant = parse_declarator(parser_data, param_type)?;
+            let final_pos = start_pos.to(parser_data.previous_position);
+            Ok(ParaDecl::new(
+                final_pos,
+                Box::new(type_variant),
+                Box::new(ident),
+                is_const,
+            ))
         }
         _ => {
-            let final_pos = finish_position(&start_pos, &parser_data.current_token.token_position);
+            let final_pos = start_pos.to(parser_data.previous_position);
 
-            ParaDecl::new(
+            Ok(ParaDecl::new(
                 final_pos,
                 Box::new(AstTypeVariant::Primitive(param_type)),
                 Box::new(ident),
-            )
+                is_const,
+            ))
         }
     }
 }
 
 // arg-list -> "(" proper-arg-list? ")"
-fn parse_argument_list(parser_struct: &mut ParserData) -> ListType {
-    let start = parser_struct.current_position;
-    match_and_consume_next_token(parser_struct); // Consume LPAREN
+fn parse_argument_list(parser_struct: &mut ParserData) -> ParseResult<ListType> {
+    let start = parser_struct.current_token.span;
+    match_token(parser_struct, TokenKind::LPAREN)?;
 
     if parser_struct.current_token.token_kind == TokenKind::RPAREN {
-        let final_pos = finish_position(&start, &parser_struct.current_position);
         match_and_consume_next_token(parser_struct); // Consume RPAREN
-        return ListType::EmptyArgList(EmptyArgList::new(final_pos));
+        let final_pos = start.to(parser_struct.previous_position);
+        return Ok(ListType::EmptyArgList(EmptyArgList::new(final_pos)));
     }
 
-    let arg_list = parse_proper_argument_list(parser_struct);
-    match_and_consume_next_token(parser_struct); // Consume RPAREN
-    arg_list
+    let arg_list = parse_proper_argument_list(parser_struct)?;
+    match_token(parser_struct, TokenKind::RPAREN)?;
+    Ok(arg_list)
 }
 
 // proper-arg-list -> arg ( "," arg )*
-fn parse_proper_argument_list(parser_struct: &mut ParserData) -> ListType {
-    let start_pos = parser_struct.current_position;
-    let arg = parse_arg(parser_struct);
+fn parse_proper_argument_list(parser_struct: &mut ParserData) -> ParseResult<ListType> {
+    let start_pos = parser_struct.current_token.span;
+    let arg = parse_arg(parser_struct)?;
 
     let arg_expr = ExprType::Arg(arg);
 
     if parser_struct.current_token.token_kind != TokenKind::COMMA {
-        let final_pos = finish_position(&start_pos, &parser_struct.current_position);
-        // Shouldn't return empty Arg List.
-        return ListType::ArgList(ArgList::new(
+        let final_pos = start_pos.to(parser_struct.previous_position);
+        return Ok(ListType::ArgList(ArgList::new(
             final_pos,
             arg_expr,
             Box::new(ListType::EmptyArgList(EmptyArgList::new(final_pos))),
-        ));
+        )));
     }
 
     match_and_consume_next_token(parser_struct); // Consume COMMA
-    parse_proper_argument_list(parser_struct)
+    let rest_of_args = parse_proper_argument_list(parser_struct)?;
+    let final_pos = start_pos.to(parser_struct.previous_position);
+
+    Ok(ListType::ArgList(ArgList::new(
+        final_pos,
+        arg_expr,
+        Box::new(rest_of_args),
+    )))
 }
 
-fn parse_arg(parser_data: &mut ParserData) -> Arg {
-    let arg_position = parser_data.current_token.token_position.clone();
-    let expr = parse_expr(parser_data);
-    Arg::new(arg_position, Box::new(expr))
+fn parse_arg(parser_data: &mut ParserData) -> ParseResult<Arg> {
+    let arg_position = parser_data.current_token.span;
+    let expr = parse_expr(parser_data)?;
+    let final_pos = arg_position.to(*expr.get_span());
+    Ok(Arg::new(final_pos, Box::new(expr)))
 }
 
 // ========================== Literal and Type Parsing ==========================
 
-fn parse_int_literal(parser_data: &mut ParserData) -> IntLiteral {
+fn parse_int_literal(parser_data: &mut ParserData) -> ParseResult<IntLiteral> {
     match parser_data.current_token.token_kind {
         TokenKind::INTLITERAL => {
-            let int_literal_node = IntLiteral {
-                source_position: parser_data.current_token.token_position.clone(),
-                spelling: parser_data.current_token.spelling.clone(),
-            };
+            let int_literal_node = IntLiteral::new(
+                parser_data.current_token.span,
+                parser_data.current_token.spelling.clone(),
+            );
 
             match_and_consume_next_token(parser_data);
-            int_literal_node
+            Ok(int_literal_node)
         }
-        _ => panic!("integer literal expected here"),
+        _ => Err(syntax_error(parser_data, "integer literal expected here")),
     }
 }
 
-fn parse_float_literal(parser_data: &mut ParserData) -> FloatLiteral {
+fn parse_float_literal(parser_data: &mut ParserData) -> ParseResult<FloatLiteral> {
     match parser_data.current_token.token_kind {
         TokenKind::FLOATLITERAL => {
-            let float_literal_node = FloatLiteral {
-                source_position: parser_data.current_token.token_position.clone(),
-                spelling: parser_data.current_token.spelling.clone(),
-            };
+            let float_literal_node = FloatLiteral::new(
+                parser_data.current_token.span,
+                parser_data.current_token.spelling.clone(),
+            );
 
             match_and_consume_next_token(parser_data);
 
-            float_literal_node
+            Ok(float_literal_node)
         }
-        _ => panic!("float literal expected here"),
+        _ => Err(syntax_error(parser_data, "float literal expected here")),
     }
 }
 
-fn parse_boolean_literal(parser_data: &mut ParserData) -> BooleanLiteral {
+fn parse_boolean_literal(parser_data: &mut ParserData) -> ParseResult<BooleanLiteral> {
     match parser_data.current_token.token_kind {
         TokenKind::BOOLEANLITERAL => {
             let boolean_literal_node = BooleanLiteral {
-                source_position: parser_data.current_token.token_position.clone(),
+                span: parser_data.current_token.span,
                 spelling: parser_data.current_token.spelling.clone(),
             };
 
             match_and_consume_next_token(parser_data);
 
-            boolean_literal_node
+            Ok(boolean_literal_node)
         }
-        _ => panic!("boolean literal expected here"),
+        _ => Err(syntax_error(parser_data, "boolean literal expected here")),
     }
 }
 
-fn parse_string_literal(parser_struct: &mut ParserData) -> ParserResult {
+fn parse_char_literal(parser_data: &mut ParserData) -> ParseResult<CharLiteral> {
+    match parser_data.current_token.token_kind {
+        TokenKind::CHARLITERAL => {
+            let char_literal_node = CharLiteral {
+                span: parser_data.current_token.span,
+                spelling: parser_data.current_token.spelling.clone(),
+            };
+
+            match_and_consume_next_token(parser_data);
+
+            Ok(char_literal_node)
+        }
+        _ => Err(syntax_error(parser_data, "char literal expected here")),
+    }
+}
+
+fn parse_string_literal(parser_struct: &mut ParserData) -> ParseResult<StringLiteral> {
     match parser_struct.current_token.token_kind {
         TokenKind::STRINGLITERAL => {
-            let string_literal_node = StringLiteral {
-                source_position: parser_struct.current_token.token_position.clone(),
-                spelling: parser_struct.current_token.spelling.clone(),
-            };
-            // Here, we assume that scanner.get_next_token() updates
-            // parser_struct.current_token with the next token and returns it.
+            let string_literal_node = StringLiteral::new(
+                parser_struct.current_token.span,
+                parser_struct.current_token.spelling.clone(),
+            );
 
             match_and_consume_next_token(parser_struct);
 
-            ParserResult::Ok(AstNode::StringLiteral(string_literal_node))
+            Ok(string_literal_node)
         }
-        _ => ParserResult::NoMatch {
-            expect: "string literal",
-        },
+        _ => Err(syntax_error(parser_struct, "string literal expected here")),
     }
 }
 
-fn parse_identifier(parser_struct: &mut ParserData) -> Ident {
+fn parse_identifier(parser_struct: &mut ParserData) -> ParseResult<Ident> {
     match parser_struct.current_token.token_kind {
         TokenKind::ID => {
             let identifier = Ident::new(
                 parser_struct.current_token.spelling.clone(),
-                parser_struct.current_token.token_position,
+                parser_struct.current_token.span,
                 None,
             );
             match_and_consume_next_token(parser_struct);
-            identifier
+            Ok(identifier)
         }
-        _ => panic!("identifier expected here"),
+        _ => Err(syntax_error(parser_struct, "identifier expected here")),
     }
 }
 
-fn parse_type(parser_data: &mut ParserData) -> AstTypes {
-    let type_position = parser_data.current_token.token_position.clone();
+// type -> "void" | "boolean" | "char" | "int" | "float" | "string" | "struct" identifier
+fn parse_type(parser_data: &mut ParserData) -> ParseResult<AstTypes> {
+    let type_position = parser_data.current_token.span;
 
-    match parser_data.current_token.token_kind {
-        TokenKind::VOID => {
-            match_and_consume_next_token(parser_data);
-            AstTypes::VoidType(VoidType::new(type_position))
-        }
-        TokenKind::INT => {
-            match_and_consume_next_token(parser_data);
-            AstTypes::IntType(IntType::new(type_position))
-        }
-        TokenKind::FLOAT => {
-            match_and_consume_next_token(parser_data);
-            AstTypes::FloatType(FloatType::new(type_position))
-        }
-        TokenKind::BOOLEAN => {
-            match_and_consume_next_token(parser_data);
-            AstTypes::BooleanType(BooleanType::new(type_position))
-        }
-        TokenKind::STRINGLITERAL => {
-            match_and_consume_next_token(parser_data);
-            AstTypes::StringType(StringType::new(type_position))
-        }
-        _ => panic!(
-            "\"{:?}\" wrong type. Type expected.",
-            parser_data.current_token
-        ),
+    if parser_data.current_token.token_kind == TokenKind::STRUCT {
+        match_and_consume_next_token(parser_data);
+        let ident = parse_identifier(parser_data)?;
+        let final_pos = type_position.to(parser_data.previous_position);
+        return Ok(AstTypes::StructType(StructType::new(final_pos, ident)));
     }
+
+    let parsed_type = match parser_data.current_token.token_kind {
+        TokenKind::VOID => AstTypes::VoidType(VoidType::new(type_position)),
+        TokenKind::INT => AstTypes::IntType(IntType::new(type_position)),
+        TokenKind::FLOAT => AstTypes::FloatType(FloatType::new(type_position)),
+        TokenKind::BOOLEAN => AstTypes::BooleanType(BooleanType::new(type_position)),
+        TokenKind::CHAR => AstTypes::CharType(CharType::new(type_position)),
+        TokenKind::STRING => AstTypes::StringType(StringType::new(type_position)),
+        _ => return Err(syntax_error(parser_data, "type expected here")),
+    };
+
+    match_and_consume_next_token(parser_data);
+    Ok(parsed_type)
 }
 
 // =================================== Utility Functions =====================================
 
 fn match_and_consume_next_token(parser_struct: &mut ParserData) {
+    parser_struct.previous_position = parser_struct.current_token.span;
     parser_struct.current_token = parser_struct.scanner.get_next_token();
-    parser_struct.current_position = parser_struct.current_token.token_position;
+    parser_struct.current_position = parser_struct.current_token.span;
+}
+
+// Consumes the current token if it is the expected kind, otherwise reports what was expected.
+fn match_token(parser_struct: &mut ParserData, expected: TokenKind) -> ParseResult<()> {
+    if parser_struct.current_token.token_kind != expected {
+        let message = format!("\"{}\" expected here", expected.to_string());
+        return Err(syntax_error(parser_struct, &message));
+    }
+
+    match_and_consume_next_token(parser_struct);
+    Ok(())
 }
 
 fn consume_operator(parser_struct: &mut ParserData) -> Operator {
     let operator = Operator::new(
-        parser_struct.current_token.token_position.clone(),
+        parser_struct.current_token.span,
         parser_struct.current_token.spelling.clone(),
     );
-    parser_struct.current_token = parser_struct.scanner.get_next_token();
-    parser_struct.current_position = parser_struct.current_token.token_position;
+    match_and_consume_next_token(parser_struct);
     operator
 }
 
-fn accept_token(parser_struct: &mut ParserData, token: Token) -> bool {
-    todo!("Complete accept token");
-}
+// Builds a syntax error pointing at the current token.
+fn syntax_error(parser_struct: &ParserData, message: &str) -> Box<Diagnostic> {
+    let found = match parser_struct.current_token.token_kind {
+        TokenKind::EOF => String::from("found end of file"),
+        _ => format!("found \"{}\"", parser_struct.current_token.spelling),
+    };
 
-fn finish_position(
-    init_source_position: &SourcePosition,
-    end_source_position: &SourcePosition,
-) -> SourcePosition {
-    // Take the start of the initial and the end of the end and create a single source position.
-    SourcePosition::new(
-        init_source_position.line_start,
-        end_source_position.line_finish,
-        init_source_position.char_start,
-        end_source_position.char_end,
+    Box::new(
+        Diagnostic::error(parser_struct.current_token.span, message)
+            .with_code("P1")
+            .with_primary_label(found),
     )
 }
 
@@ -1158,10 +1362,14 @@ static EXPR_FIRST_SET: Lazy<HashSet<TokenKind>> = Lazy::new(|| {
         TokenKind::INTLITERAL,
         TokenKind::FLOATLITERAL,
         TokenKind::BOOLEANLITERAL,
+        TokenKind::CHARLITERAL,
         TokenKind::STRINGLITERAL,
         TokenKind::PLUS,
         TokenKind::MINUS,
         TokenKind::NOT,
+        TokenKind::TILDE,
+        TokenKind::PLUSPLUS,
+        TokenKind::MINUSMINUS,
         TokenKind::MULT,
         TokenKind::DIV,
         TokenKind::LPAREN,
@@ -1172,21 +1380,138 @@ static EXPR_FIRST_SET: Lazy<HashSet<TokenKind>> = Lazy::new(|| {
     .collect::<HashSet<_>>()
 });
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Associativity {
+    Left,
+    Right,
+}
+
+// The AST node an infix operator builds once both operands are parsed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum InfixNode {
+    Assign,
+    CompoundAssign,
+    Conditional,
+    Binary,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct InfixOperator {
+    precedence: u8,
+    associativity: Associativity,
+    node: InfixNode,
+}
+
+impl InfixOperator {
+    const fn new(precedence: u8, associativity: Associativity, node: InfixNode) -> Self {
+        Self {
+            precedence,
+            associativity,
+            node,
+        }
+    }
+}
+
+const ASSIGNMENT_PRECEDENCE: u8 = 1;
+const CONDITIONAL_PRECEDENCE: u8 = 2;
+const UNARY_PRECEDENCE: u8 = 13;
+
+// Higher precedence binds tighter.  Adding an operator to the language is a new row here.
+static INFIX_OPERATORS: Lazy<HashMap<TokenKind, InfixOperator>> = Lazy::new(|| {
+    use Associativity::{Left, Right};
+    use InfixNode::{Assign, Binary, CompoundAssign, Conditional};
+
+    [
+        (
+            TokenKind::EQ,
+            InfixOperator::new(ASSIGNMENT_PRECEDENCE, Right, Assign),
+        ),
+        (
+            TokenKind::PLUSEQ,
+            InfixOperator::new(ASSIGNMENT_PRECEDENCE, Right, CompoundAssign),
+        ),
+        (
+            TokenKind::MINUSEQ,
+            InfixOperator::new(ASSIGNMENT_PRECEDENCE, Right, CompoundAssign),
+        ),
+        (
+            TokenKind::MULTEQ,
+            InfixOperator::new(ASSIGNMENT_PRECEDENCE, Right, CompoundAssign),
+        ),
+        (
+            TokenKind::DIVEQ,
+            InfixOperator::new(ASSIGNMENT_PRECEDENCE, Right, CompoundAssign),
+        ),
+        (
+            TokenKind::QUESTION,
+            InfixOperator::new(CONDITIONAL_PRECEDENCE, Right, Conditional),
+        ),
+        (TokenKind::OROR, InfixOperator::new(3, Left, Binary)),
+        (TokenKind::ANDAND, InfixOperator::new(4, Left, Binary)),
+        (TokenKind::OR, InfixOperator::new(5, Left, Binary)),
+        (TokenKind::XOR, InfixOperator::new(6, Left, Binary)),
+        (TokenKind::AND, InfixOperator::new(7, Left, Binary)),
+        (TokenKind::EQEQ, InfixOperator::new(8, Left, Binary)),
+        (TokenKind::NOTEQ, InfixOperator::new(8, Left, Binary)),
+        (TokenKind::LT, InfixOperator::new(9, Left, Binary)),
+        (TokenKind::LTEQ, InfixOperator::new(9, Left, Binary)),
+        (TokenKind::GT, InfixOperator::new(9, Left, Binary)),
+        (TokenKind::GTEQ, InfixOperator::new(9, Left, Binary)),
+        (TokenKind::LTLT, InfixOperator::new(10, Left, Binary)),
+        (TokenKind::GTGT, InfixOperator::new(10, Left, Binary)),
+        (TokenKind::PLUS, InfixOperator::new(11, Left, Binary)),
+        (TokenKind::MINUS, InfixOperator::new(11, Left, Binary)),
+        (TokenKind::MULT, InfixOperator::new(12, Left, Binary)),
+        (TokenKind::DIV, InfixOperator::new(12, Left, Binary)),
+        (TokenKind::MOD, InfixOperator::new(12, Left, Binary)),
+    ]
+    .into_iter()
+    .collect::<HashMap<_, _>>()
+});
+
+// Prefix operators and the precedence their operand is parsed at.
+static PREFIX_OPERATORS: Lazy<HashMap<TokenKind, u8>> = Lazy::new(|| {
+    [
+        (TokenKind::PLUS, UNARY_PRECEDENCE),
+        (TokenKind::MINUS, UNARY_PRECEDENCE),
+        (TokenKind::NOT, UNARY_PRECEDENCE),
+        (TokenKind::TILDE, UNARY_PRECEDENCE),
+        (TokenKind::PLUSPLUS, UNARY_PRECEDENCE),
+        (TokenKind::MINUSMINUS, UNARY_PRECEDENCE),
+    ]
+    .into_iter()
+    .collect::<HashMap<_, _>>()
+});
+
 fn is_primitive_type(ast_type: &AstTypes) -> bool {
     match ast_type {
         AstTypes::IntType(_) => true,
         AstTypes::FloatType(_) => true,
         AstTypes::BooleanType(_) => true,
+        AstTypes::CharType(_) => true,
+        AstTypes::StringType(_) => true,
         AstTypes::VoidType(_) => true,
         _ => false,
     }
 }
 
+// Declarations start with a primitive type or a struct type.
+fn token_starts_type(token_kind: &TokenKind) -> bool {
+    token_is_primitive_type(token_kind) || *token_kind == TokenKind::STRUCT
+}
+
+// A variable declaration may put the const qualifier before its type.
+fn token_starts_declaration(token_kind: &TokenKind) -> bool {
+    token_starts_type(token_kind) || *token_kind == TokenKind::CONST
+}
+
 fn token_is_primitive_type(token_kind: &TokenKind) -> bool {
     match token_kind {
         TokenKind::INT => true,
         TokenKind::FLOAT => true,
         TokenKind::BOOLEAN => true,
+        TokenKind::CHAR => true,
+        TokenKind::STRING => true,
         TokenKind::VOID => true,
         _ => false,
     }
@@ -0,0 +1,633 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use crate::diagnostics::Diagnostic;
+use crate::source_map::{lines_with_endings, without_line_ending, SourceFile, Span};
+
+/*
+   Preprocessor
+   Runs over a file's text before the Scanner sees it, handling
+
+   #define SIZE 10
+   #ifdef DEBUG
+   ...
+   #else
+   ...
+   #endif
+
+   along with #ifndef.  Macros are object-like only: every later use of SIZE as an identifier,
+   outside strings and comments, is replaced by its value.  Macros may also be given on the command
+   line with -DSIZE=10, and are then defined in every file; otherwise a macro is known only in the
+   file that defines it.
+
+   Directive lines and lines in branches not taken are left out, but their newlines are kept, so
+   line structure is unchanged.  Any other directive, such as #include, is passed on to the
+   Scanner.  The preprocessed text remembers where each piece of it came from, and map_span turns a
+   span of it back into a span of the original file; text from a macro maps to the use of the
+   macro's name.
+*/
+
+// A macro defined on the command line: -DNAME=value, or -DNAME, which defines it as 1.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Define {
+    pub name: String,
+    pub value: String,
+}
+
+impl FromStr for Define {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, value) = s.split_once('=').unwrap_or((s, "1"));
+        if !is_identifier(name) {
+            return Err(format!("invalid macro name: {}", name));
+        }
+        Ok(Self {
+            name: name.to_string(),
+            value: value.to_string(),
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Preprocessed {
+    pub text: String,
+    pub diagnostics: Vec<Diagnostic>,
+    // The name of every macro the file defines, in order.
+    pub definitions: Vec<Span>,
+    // In order of text_lo, covering the text; the last is an empty segment at the end of both.
+    segments: Vec<Segment>,
+}
+
+// Text from text_lo up to the next segment came from source: copied, or expanded from a macro.
+#[derive(Clone, Copy, Debug)]
+struct Segment {
+    text_lo: usize,
+    source: Span,
+    expanded: bool,
+}
+
+impl Preprocessed {
+    // A span of the preprocessed text as a span of the original file.
+    pub fn map_span(&self, span: Span) -> Span {
+        let lo = self.map_offset(span.lo, false);
+        let hi = self.map_offset(span.hi, true).max(lo);
+        Span::new(span.file_id, lo, hi)
+    }
+
+    // Whether a span of the preprocessed text starts in text expanded from a macro.
+    pub fn is_expanded(&self, span: Span) -> bool {
+        let after = self
+            .segments
+            .partition_point(|segment| segment.text_lo <= span.lo);
+        self.segments[after.max(1) - 1].expanded
+    }
+
+    // An offset that ends a span belongs to the segment before it, when there is one.
+    fn map_offset(&self, offset: usize, is_end: bool) -> usize {
+        let after = self.segments.partition_point(|segment| {
+            segment.text_lo < offset || (!is_end && segment.text_lo == offset)
+        });
+        let segment = self.segments[after.max(1) - 1];
+
+        match (segment.expanded, is_end) {
+            (true, false) => segment.source.lo,
+            (true, true) => segment.source.hi,
+            (false, _) => segment.source.lo + offset.saturating_sub(segment.text_lo),
+        }
+    }
+}
+
+pub fn preprocess(source_file: &SourceFile, defines: &[Define]) -> Preprocessed {
+    let mut preprocessor = Preprocessor {
+        source_file,
+        output: Preprocessed {
+            text: String::new(),
+            diagnostics: Vec::new(),
+            definitions: Vec::new(),
+            segments: Vec::new(),
+        },
+        macros: defines
+            .iter()
+            .map(|define| {
+                let definition = Macro {
+                    value: define.value.clone(),
+                    span: None,
+                };
+                (define.name.clone(), definition)
+            })
+            .collect(),
+        conditionals: Vec::new(),
+        in_comment: false,
+    };
+
+    // A leading byte order mark is not part of the program.
+    let source = source_file.source.as_str();
+    let text = source.strip_prefix('\u{FEFF}').unwrap_or(source);
+    let mut line_start = source.len() - text.len();
+    for line in lines_with_endings(text) {
+        preprocessor.line(line_start, line);
+        line_start += line.len();
+    }
+    preprocessor.finish()
+}
+
+struct Macro {
+    value: String,
+    // Where it was defined, or None for a macro from the command line.
+    span: Option<Span>,
+}
+
+// An #ifdef or #ifndef whose #endif has not been reached.
+struct Conditional {
+    span: Span,
+    // Whether the lines around it are being kept.
+    enclosing_active: bool,
+    condition: bool,
+    else_span: Option<Span>,
+}
+
+impl Conditional {
+    fn active(&self) -> bool {
+        self.enclosing_active && (self.condition != self.else_span.is_some())
+    }
+}
+
+struct Preprocessor<'a> {
+    source_file: &'a SourceFile,
+    output: Preprocessed,
+    macros: HashMap<String, Macro>,
+    conditionals: Vec<Conditional>,
+    // Whether the last line kept ended inside a /* comment.
+    in_comment: bool,
+}
+
+impl Preprocessor<'_> {
+    fn active(&self) -> bool {
+        self.conditionals
+            .last()
+            .is_none_or(|conditional| conditional.active())
+    }
+
+    fn span(&self, lo: usize, hi: usize) -> Span {
+        Span::new(self.source_file.file_id, lo, hi)
+    }
+
+    fn line(&mut self, line_start: usize, line: &str) {
+        let content = without_line_ending(line);
+        let newline = line_start + content.len();
+
+        match directive(content) {
+            Some((name, rest_start)) if !self.in_comment => {
+                let span = self.span(line_start, line_start + content.trim_end().len());
+                let rest = strip_comment(&content[rest_start..]).trim();
+                match name {
+                    "define" => self.define(span, rest, line_start + rest_start),
+                    "ifdef" => self.conditional(span, "#ifdef", rest, true),
+                    "ifndef" => self.conditional(span, "#ifndef", rest, false),
+                    "else" => self.else_directive(span, rest),
+                    "endif" => self.endif(span, rest),
+                    // Left for the Scanner, which handles #include.
+                    _ if self.active() => self.copy(line_start, content),
+                    _ => (),
+                }
+            }
+            _ if self.active() => self.expand_line(line_start, content),
+            _ => (),
+        }
+
+        if content.len() < line.len() {
+            self.copy(newline, &line[content.len()..]);
+        }
+    }
+
+    fn finish(mut self) -> Preprocessed {
+        for conditional in std::mem::take(&mut self.conditionals) {
+            self.error(
+                Diagnostic::error(conditional.span, "unterminated conditional directive")
+                    .with_code("D3")
+                    .with_primary_label("no matching #endif"),
+            );
+        }
+
+        let end = self.source_file.source.len();
+        self.output.segments.push(Segment {
+            text_lo: self.output.text.len(),
+            source: self.span(end, end),
+            expanded: false,
+        });
+        self.output
+    }
+
+    fn error(&mut self, diagnostic: Diagnostic) {
+        self.output.diagnostics.push(diagnostic);
+    }
+
+    fn define(&mut self, span: Span, rest: &str, rest_lo: usize) {
+        if !self.active() {
+            return;
+        }
+
+        let name_len = identifier_len(rest);
+        let (name, value) = rest.split_at(name_len);
+        if name.is_empty() {
+            self.error(
+                Diagnostic::error(span, "malformed #define: expected a macro name").with_code("D1"),
+            );
+            return;
+        }
+        if value.starts_with('(') {
+            self.error(
+                Diagnostic::error(span, "function-like macros are not supported").with_code("D1"),
+            );
+            return;
+        }
+
+        // rest is trimmed, so the name starts at the first non-blank character after #define.
+        let name_lo = rest_lo + self.source_file.source[rest_lo..].find(name).unwrap_or(0);
+        let name_span = self.span(name_lo, name_lo + name.len());
+        let value = value.trim().to_string();
+
+        if let Some(previous) = self.macros.get(name) {
+            if previous.value != value {
+                let diagnostic = Diagnostic::error(name_span, format!("macro redefined: {}", name))
+                    .with_code("D2");
+                let diagnostic = match previous.span {
+                    Some(previous_span) => {
+                        diagnostic.with_label(previous_span, "previously defined here")
+                    }
+                    None => diagnostic.with_note("previously defined on the command line"),
+                };
+                self.error(diagnostic);
+            }
+            return;
+        }
+
+        let definition = Macro {
+            value,
+            span: Some(name_span),
+        };
+        self.macros.insert(name.to_string(), definition);
+        self.output.definitions.push(name_span);
+    }
+
+    fn conditional(&mut self, span: Span, directive: &str, rest: &str, if_defined: bool) {
+        let condition = if is_identifier(rest) {
+            self.macros.contains_key(rest) == if_defined
+        } else {
+            if self.active() {
+                self.error(
+                    Diagnostic::error(
+                        span,
+                        format!("malformed {}: expected a macro name", directive),
+                    )
+                    .with_code("D1"),
+                );
+            }
+            false
+        };
+
+        self.conditionals.push(Conditional {
+            span,
+            enclosing_active: self.active(),
+            condition,
+            else_span: None,
+        });
+    }
+
+    fn else_directive(&mut self, span: Span, rest: &str) {
+        self.expect_nothing_after(span, "#else", rest);
+
+        match self.conditionals.last_mut() {
+            None => self
+                .error(Diagnostic::error(span, "#else without #ifdef or #ifndef").with_code("D3")),
+            Some(Conditional {
+                else_span: Some(else_span),
+                ..
+            }) => {
+                let previous = *else_span;
+                self.error(
+                    Diagnostic::error(span, "#else after #else")
+                        .with_code("D3")
+                        .with_label(previous, "first #else here"),
+                );
+            }
+            Some(conditional) => conditional.else_span = Some(span),
+        }
+    }
+
+    fn endif(&mut self, span: Span, rest: &str) {
+        self.expect_nothing_after(span, "#endif", rest);
+
+        if self.conditionals.pop().is_none() {
+            self.error(Diagnostic::error(span, "#endif without #ifdef or #ifndef").with_code("D3"));
+        }
+    }
+
+    fn expect_nothing_after(&mut self, span: Span, directive: &str, rest: &str) {
+        if !rest.is_empty() && self.active() {
+            self.error(
+                Diagnostic::error(span, format!("unexpected text after {}", directive))
+                    .with_code("D1"),
+            );
+        }
+    }
+
+    // Copies the text at lo of the original file, joining it to the previous copy where possible.
+    fn copy(&mut self, lo: usize, text: &str) {
+        let hi = lo + text.len();
+        self.output.text.push_str(text);
+
+        match self.output.segments.last_mut() {
+            Some(segment) if !segment.expanded && segment.source.hi == lo => {
+                segment.source.hi = hi;
+            }
+            _ => {
+                let segment = Segment {
+                    text_lo: self.output.text.len() - text.len(),
+                    source: self.span(lo, hi),
+                    expanded: false,
+                };
+                self.output.segments.push(segment);
+            }
+        }
+    }
+
+    fn expand_line(&mut self, line_start: usize, content: &str) {
+        let mut copied = 0;
+        let mut index = 0;
+
+        while index < content.len() {
+            let rest = &content[index..];
+
+            if self.in_comment {
+                match rest.find("*/") {
+                    Some(end) => {
+                        index += end + 2;
+                        self.in_comment = false;
+                    }
+                    None => index = content.len(),
+                }
+                continue;
+            }
+
+            let skipped = match rest.chars().next().unwrap_or_default() {
+                '/' if rest.starts_with("//") => content.len() - index,
+                '/' if rest.starts_with("/*") => {
+                    self.in_comment = true;
+                    2
+                }
+                '"' | '\'' => quoted_literal_len(rest),
+                c if c.is_ascii_digit() => rest
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '.'))
+                    .unwrap_or(rest.len()),
+                c if c == '_' || c.is_ascii_alphabetic() => {
+                    let name = &rest[..identifier_len(rest)];
+                    if let Some(value) = self.expansion(name) {
+                        self.copy(line_start + copied, &content[copied..index]);
+                        let use_lo = line_start + index;
+                        self.expanded(self.span(use_lo, use_lo + name.len()), &value);
+                        copied = index + name.len();
+                    }
+                    name.len()
+                }
+                c => c.len_utf8(),
+            };
+            index += skipped;
+        }
+
+        self.copy(line_start + copied, &content[copied..]);
+    }
+
+    // Spaces around the value keep it from running into the tokens beside it.
+    fn expanded(&mut self, use_span: Span, value: &str) {
+        self.output.segments.push(Segment {
+            text_lo: self.output.text.len(),
+            source: use_span,
+            expanded: true,
+        });
+        self.output.text.push_str(&format!(" {} ", value));
+    }
+
+    // The text a macro expands to, with the macros in its value expanded in turn.
+    fn expansion(&self, name: &str) -> Option<String> {
+        self.expand_macro(name, &mut Vec::new())
+    }
+
+    // A macro is not expanded again within its own value, so it cannot recurse forever.
+    fn expand_macro<'m>(&'m self, name: &'m str, expanding: &mut Vec<&'m str>) -> Option<String> {
+        let definition = self.macros.get(name)?;
+        if expanding.contains(&name) {
+            return None;
+        }
+        expanding.push(name);
+
+        let value = &definition.value;
+        let mut expanded = String::new();
+        let mut index = 0;
+        while index < value.len() {
+            let rest = &value[index..];
+            let len = match rest.chars().next().unwrap_or_default() {
+                '"' | '\'' => quoted_literal_len(rest),
+                c if c.is_ascii_digit() => rest
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '.'))
+                    .unwrap_or(rest.len()),
+                c if c == '_' || c.is_ascii_alphabetic() => identifier_len(rest),
+                c => c.len_utf8(),
+            };
+
+            let piece = &rest[..len];
+            match self.expand_macro(piece, expanding) {
+                Some(inner) if is_identifier(piece) => expanded.push_str(&inner),
+                _ => expanded.push_str(piece),
+            }
+            index += len;
+        }
+
+        expanding.pop();
+        Some(expanded)
+    }
+}
+
+// The directive named on a line starting with #, and where the rest of the line begins.
+fn directive(line: &str) -> Option<(&str, usize)> {
+    let after_hash = line.trim_start().strip_prefix('#')?;
+    let name_len = after_hash
+        .find(|c: char| !c.is_alphabetic())
+        .unwrap_or(after_hash.len());
+    let rest_start = line.len() - after_hash.len() + name_len;
+    Some((&after_hash[..name_len], rest_start))
+}
+
+// Removes a trailing // or /* comment from the rest of a directive.
+fn strip_comment(text: &str) -> &str {
+    let mut index = 0;
+    while index < text.len() {
+        let rest = &text[index..];
+        if rest.starts_with("//") || rest.starts_with("/*") {
+            return &text[..index];
+        }
+        index += match rest.chars().next().unwrap_or_default() {
+            '"' | '\'' => quoted_literal_len(rest),
+            c => c.len_utf8(),
+        };
+    }
+    text
+}
+
+// Length of the string or character literal at the start of text, up to its closing quote or the
+// end.
+fn quoted_literal_len(text: &str) -> usize {
+    let quote = text.chars().next().unwrap_or_default();
+    let mut escaped = false;
+    for (index, c) in text.char_indices().skip(1) {
+        match c {
+            c if c == quote && !escaped => return index + 1,
+            '\\' => escaped = !escaped,
+            _ => escaped = false,
+        }
+    }
+    text.len()
+}
+
+fn identifier_len(text: &str) -> usize {
+    if !text.starts_with(|c: char| c == '_' || c.is_ascii_alphabetic()) {
+        return 0;
+    }
+    text.find(|c: char| !(c == '_' || c.is_ascii_alphanumeric()))
+        .unwrap_or(text.len())
+}
+
+fn is_identifier(text: &str) -> bool {
+    !text.is_empty() && identifier_len(text) == text.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source_map::FileId;
+
+    fn run(source: &str, defines: &[Define]) -> (SourceFile, Preprocessed) {
+        let source_file = SourceFile::new(FileId(0), "<input>", source.to_string());
+        let preprocessed = preprocess(&source_file, defines);
+        (source_file, preprocessed)
+    }
+
+    fn messages(preprocessed: &Preprocessed) -> Vec<(&str, &str)> {
+        preprocessed
+            .diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.code.unwrap(), diagnostic.message.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_define_expands_identifiers_only() {
+        let (_, preprocessed) = run(
+            "#define N 10\nint a[N]; // N\nint NN = N; putString(\"N\");\n",
+            &[],
+        );
+        assert_eq!(
+            preprocessed.text,
+            "\nint a[ 10 ]; // N\nint NN =  10 ; putString(\"N\");\n"
+        );
+        assert!(preprocessed.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_define_skips_char_literals() {
+        let (_, preprocessed) = run(
+            "#define a 5\n#define n 6\nchar c = 'a'; c = '\\n'; c = '\\''; a;\n",
+            &[],
+        );
+        assert_eq!(
+            preprocessed.text,
+            "\n\nchar c = 'a'; c = '\\n'; c = '\\'';  5 ;\n"
+        );
+    }
+
+    #[test]
+    fn test_nested_and_recursive_macros() {
+        let (_, preprocessed) = run("#define A B + 1\n#define B 2\n#define C C\nA C\n", &[]);
+        assert_eq!(preprocessed.text, "\n\n\n 2 + 1   C \n");
+    }
+
+    #[test]
+    fn test_conditionals() {
+        let source =
+            "#ifdef DEBUG\na\n#else\nb\n#endif\n#ifndef DEBUG\nc\n#ifdef X\nd\n#endif\n#endif\n";
+        let (_, preprocessed) = run(source, &[]);
+        assert_eq!(preprocessed.text, "\n\n\nb\n\n\nc\n\n\n\n\n");
+
+        let (_, preprocessed) = run(source, &["DEBUG".parse().unwrap()]);
+        assert_eq!(preprocessed.text, "\na\n\n\n\n\n\n\n\n\n\n");
+    }
+
+    #[test]
+    fn test_command_line_defines() {
+        assert_eq!(
+            "SIZE=4".parse(),
+            Ok(Define {
+                name: String::from("SIZE"),
+                value: String::from("4")
+            })
+        );
+        assert_eq!("DEBUG".parse::<Define>().unwrap().value, "1");
+        assert!("4=SIZE".parse::<Define>().is_err());
+
+        let (_, preprocessed) = run("int a[SIZE];", &["SIZE=4".parse().unwrap()]);
+        assert_eq!(preprocessed.text, "int a[ 4 ];");
+    }
+
+    #[test]
+    fn test_spans_map_to_original_text() {
+        let (source_file, preprocessed) = run("#define N 10\nint a[N];\n", &[]);
+        let file_id = source_file.file_id;
+        let text_span = |text: &str| {
+            let lo = preprocessed.text.find(text).unwrap();
+            Span::new(file_id, lo, lo + text.len())
+        };
+
+        let int_span = preprocessed.map_span(text_span("int"));
+        assert_eq!(source_file.text(int_span), "int");
+        assert_eq!(source_file.line_number(int_span.lo), 2);
+
+        let value_span = preprocessed.map_span(text_span("10"));
+        assert_eq!(source_file.text(value_span), "N");
+        let bracket_span = preprocessed.map_span(text_span("]"));
+        assert_eq!(source_file.text(bracket_span), "]");
+
+        let end = Span::point(file_id, preprocessed.text.len());
+        assert_eq!(preprocessed.map_span(end).lo, source_file.source.len());
+    }
+
+    #[test]
+    fn test_directive_errors() {
+        let (_, preprocessed) = run(
+            "#define\n#define N 1\n#define N 2\n#define F(x) x\n#else\n#endif\n#ifdef\n#else\n#else\n",
+            &[],
+        );
+        assert_eq!(
+            messages(&preprocessed),
+            vec![
+                ("D1", "malformed #define: expected a macro name"),
+                ("D2", "macro redefined: N"),
+                ("D1", "function-like macros are not supported"),
+                ("D3", "#else without #ifdef or #ifndef"),
+                ("D3", "#endif without #ifdef or #ifndef"),
+                ("D1", "malformed #ifdef: expected a macro name"),
+                ("D3", "#else after #else"),
+                ("D3", "unterminated conditional directive"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_other_directives_pass_through() {
+        let (_, preprocessed) = run(
+            "#include \"a.vc\"\n#ifdef X\n#include \"b.vc\"\n#endif\n",
+            &[],
+        );
+        assert_eq!(preprocessed.text, "#include \"a.vc\"\n\n\n\n");
+    }
+}
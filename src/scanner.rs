@@ -1,11 +1,14 @@
 use std::collections::HashSet;
+use std::sync::Arc;
 
 use log::{debug, error, info, warn};
 
+use crate::diagnostics::Diagnostic;
 use crate::globals::TAB_SIZE;
+use crate::preprocessor::{preprocess, Define, Preprocessed};
 use crate::scanner::scanner_handlers::handle_tokens;
+use crate::source_map::{ColumnMode, FileId, SourceFile, Span};
 use crate::token::{Token, TokenKind};
-use crate::utils::SourcePosition;
 
 pub mod scanner_handlers;
 
@@ -18,78 +21,143 @@ pub mod scanner_handlers;
    3. Advance the character number per number of characters in the token.
 
    Parser will then take the Token and operate upon it.
+
+   Positions are tracked only as byte offsets into the file; each token's line and column are
+   looked up from its SourceFile.  The text scanned is the file after preprocessing, so every span
+   is mapped back to the original file before it leaves the Scanner.
 */
 #[derive(Clone, Debug)]
 pub struct ScannerProductType {
     file_contents: Vec<char>,
-    curr_source_pos: SourcePosition,
+    file_id: FileId,
+    // Byte offset of the first character of the token being scanned.
+    token_start: usize,
     curr_char_index: usize,
+    curr_byte_index: usize,
     curr_token_spelling: String,
     final_token_kind: TokenKind,
+    diagnostics: Vec<Diagnostic>,
 }
 
 impl std::fmt::Display for ScannerProductType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "ScannerProductType {{ curr_source_pos: {:?}, curr_char_index: {:?}, curr_token_spelling: {:?}, final_token_kind: {:?} }}",
-            self.curr_source_pos, self.curr_char_index, self.curr_token_spelling, self.final_token_kind
+            "ScannerProductType {{ token_start: {:?}, curr_char_index: {:?}, curr_token_spelling: {:?}, final_token_kind: {:?} }}",
+            self.token_start, self.curr_char_index, self.curr_token_spelling, self.final_token_kind
         )
     }
 }
 
 #[derive(Clone, Debug)]
 pub struct Scanner {
-    global_source_position: SourcePosition,
+    source_file: Arc<SourceFile>,
     global_character_index: usize,
+    global_byte_index: usize,
     file_contents: Vec<char>,
+    preprocessed: Preprocessed,
+    diagnostics: Vec<Diagnostic>,
 }
 
 impl Scanner {
+    // Scans a string that is not part of any SourceMap; its spans use FileId(0).
     pub fn new(s: String) -> Self {
+        Self::from_source_file(Arc::new(SourceFile::new(FileId(0), "<input>", s)))
+    }
+
+    pub fn from_source_file(source_file: Arc<SourceFile>) -> Self {
+        Self::with_defines(source_file, &[])
+    }
+
+    // Scans the file with the given macros already defined, as by -DNAME=value.
+    pub fn with_defines(source_file: Arc<SourceFile>, defines: &[Define]) -> Self {
+        let mut preprocessed = preprocess(&source_file, defines);
         Self {
-            global_source_position: SourcePosition::new(1, 1, 1, 1),
+            file_contents: preprocessed.text.chars().collect(),
+            diagnostics: std::mem::take(&mut preprocessed.diagnostics),
+            preprocessed,
+            source_file,
             global_character_index: 0,
-            file_contents: s.chars().collect(),
+            global_byte_index: 0,
         }
     }
+
+    pub fn source_file(&self) -> &Arc<SourceFile> {
+        &self.source_file
+    }
+
+    // Lexical errors do not stop scanning; they are collected here for the caller to report.
+    pub fn take_diagnostics(&mut self) -> Vec<Diagnostic> {
+        std::mem::take(&mut self.diagnostics)
+    }
+
     pub fn get_next_token(&mut self) -> Token {
         let mut product_type = ScannerProductType {
             file_contents: self.file_contents.clone(),
-            curr_source_pos: self.global_source_position.clone(),
+            file_id: self.source_file.file_id,
+            token_start: self.global_byte_index,
             curr_char_index: self.global_character_index,
+            curr_byte_index: self.global_byte_index,
             curr_token_spelling: "".to_string(),
             final_token_kind: TokenKind::ERROR,
+            diagnostics: Vec::new(),
         };
-        let final_product_type = get_token(&mut product_type);
+        let mut final_product_type = get_token(&mut product_type);
+        for mut diagnostic in final_product_type.diagnostics.drain(..) {
+            diagnostic.span = self.preprocessed.map_span(diagnostic.span);
+            for label in &mut diagnostic.secondary_labels {
+                label.span = self.preprocessed.map_span(label.span);
+            }
+            self.diagnostics.push(diagnostic);
+        }
 
-        self.global_source_position = final_product_type.curr_source_pos.clone();
-        self.global_source_position.char_start = self.global_source_position.char_end + 1;
-        self.global_source_position.char_end = self.global_source_position.char_end + 1;
-        self.global_character_index = final_product_type.curr_char_index.clone();
+        self.global_character_index = final_product_type.curr_char_index;
+        self.global_byte_index = final_product_type.curr_byte_index;
 
+        let text_span = Span::new(
+            final_product_type.file_id,
+            final_product_type.token_start,
+            final_product_type.curr_byte_index,
+        );
+        let span = self.preprocessed.map_span(text_span);
         Token {
             token_kind: final_product_type.final_token_kind,
             spelling: final_product_type.curr_token_spelling,
-            token_position: final_product_type.curr_source_pos,
+            token_position: self
+                .source_file
+                .position(span, ColumnMode::Tabs(TAB_SIZE as usize)),
+            span,
+            expanded: self.preprocessed.is_expanded(text_span),
         }
     }
 }
 
 fn get_token(adt: &mut ScannerProductType) -> ScannerProductType {
     skip_spaces_comments_newlines(adt);
+    adt.token_start = adt.curr_byte_index;
+
+    handle_tokens(adt).unwrap_or_else(|_| handle_illegal_character(adt))
+}
+
+// The span of the text consumed so far for the current token.
+pub fn current_span(adt: &ScannerProductType) -> Span {
+    Span::new(adt.file_id, adt.token_start, adt.curr_byte_index)
+}
 
-    let result = handle_tokens(adt).unwrap();
-    let end_diff: i32 = result.curr_token_spelling.len() as i32 - 1;
+// Nothing matched: the character becomes an ERROR token on its own so scanning can continue.
+fn handle_illegal_character(adt: &mut ScannerProductType) -> ScannerProductType {
+    accept_next_character(adt);
+    adt.diagnostics.push(
+        Diagnostic::error(
+            current_span(adt),
+            format!("illegal character: {}", adt.curr_token_spelling),
+        )
+        .with_code("S1"),
+    );
 
     ScannerProductType {
-        curr_source_pos: SourcePosition::new(
-            adt.curr_source_pos.line_start,
-            adt.curr_source_pos.line_finish,
-            adt.curr_source_pos.char_start,
-            adt.curr_source_pos.char_end + end_diff,
-        ),
-        ..result.clone()
+        final_token_kind: TokenKind::ERROR,
+        ..adt.clone()
     }
 }
 
@@ -101,31 +169,13 @@ pub fn accept_next_character(adt: &mut ScannerProductType) {
 }
 
 pub fn skip_next_character(adt: &mut ScannerProductType) {
-    let current_char = adt.file_contents[adt.curr_char_index];
-    adt.curr_char_index += 1;
-    if current_char == '\n' {
-        adt.curr_source_pos.line_start += 1;
-        adt.curr_source_pos.line_finish += 1;
-        adt.curr_source_pos.char_start = 1;
-        adt.curr_source_pos.char_end = 1;
-    } else {
-        adt.curr_source_pos.char_start += 1;
-        adt.curr_source_pos.char_end += 1;
-    }
+    increment_character_position(adt);
 }
 
 fn increment_character_position(adt: &mut ScannerProductType) {
     let current_char = adt.file_contents[adt.curr_char_index];
     adt.curr_char_index += 1;
-    if current_char == '\n' {
-        adt.curr_source_pos.line_start += 1;
-        adt.curr_source_pos.line_finish += 1;
-        adt.curr_source_pos.char_start = 1;
-        adt.curr_source_pos.char_end = 1;
-    } else {
-        // adt.curr_source_pos.char_start += 1;
-        // adt.curr_source_pos.char_end += 1;
-    }
+    adt.curr_byte_index += current_char.len_utf8();
 }
 
 fn get_current_char(adt: &mut ScannerProductType) -> char {
@@ -147,38 +197,54 @@ fn skip_spaces_comments_newlines(adt: &mut ScannerProductType) {
     let current_char = get_current_char(adt);
     let next_char = get_next_char(adt);
 
-    // While current char is a space, tab, newline.
+    // While current char is whitespace: a space, tab, form feed, vertical tab or line ending.
     match (current_char, next_char) {
         ('/', '/') => {
             skip_next_character(adt);
             handle_single_line_comment(adt)
         }
-        ('/', '*') => handle_multiline_comment(adt),
-        (' ', _) => handle_remove_spaces(adt),
+        ('/', '*') => {
+            let comment_start = adt.curr_byte_index;
+            skip_next_character(adt);
+            skip_next_character(adt);
+            let comment_start = Span::new(adt.file_id, comment_start, adt.curr_byte_index);
+            handle_multiline_comment(adt, comment_start)
+        }
+        (' ', _) | ('\x0C', _) | ('\x0B', _) => handle_remove_spaces(adt),
         ('\t', _) => handle_tab(adt),
-        ('\n', _) => handle_newline(adt),
+        ('\n', _) | ('\r', _) => handle_newline(adt),
         _ => (),
     }
 }
 
+// Runs to the end of the line, at \n, \r\n or a lone \r, or to the end of the file.
 fn handle_single_line_comment(adt: &mut ScannerProductType) {
-    skip_next_character(adt);
-    let curr_char = get_current_char(adt);
-
-    if curr_char != '\n' && curr_char != '$' {
-        handle_single_line_comment(adt);
-    } else {
+    while adt.curr_char_index < adt.file_contents.len()
+        && !matches!(get_current_char(adt), '\n' | '\r')
+    {
         skip_next_character(adt);
-        skip_spaces_comments_newlines(adt);
     }
+    skip_spaces_comments_newlines(adt);
 }
 
-fn handle_multiline_comment(adt: &mut ScannerProductType) {
+fn handle_multiline_comment(adt: &mut ScannerProductType, comment_start: Span) {
+    if adt.curr_char_index + 1 >= adt.file_contents.len() {
+        // Runs to the end of the file; the next token is EOF.
+        adt.diagnostics.push(
+            Diagnostic::error(comment_start, "unterminated comment")
+                .with_code("S2")
+                .with_primary_label("comment starts here"),
+        );
+        while adt.curr_char_index < adt.file_contents.len() {
+            skip_next_character(adt);
+        }
+        return;
+    }
+
     let curr_char = get_current_char(adt);
     let next_char = get_next_char(adt);
 
     match (curr_char, next_char) {
-        (_, '$') => panic!("Multiline comment not closed"),
         ('*', '/') => {
             skip_next_character(adt);
             skip_next_character(adt);
@@ -186,7 +252,7 @@ fn handle_multiline_comment(adt: &mut ScannerProductType) {
         }
         (_, _) => {
             skip_next_character(adt);
-            handle_multiline_comment(adt);
+            handle_multiline_comment(adt, comment_start);
         }
     }
 }
@@ -197,14 +263,12 @@ fn handle_remove_spaces(adt: &mut ScannerProductType) {
 }
 
 fn handle_tab(adt: &mut ScannerProductType) {
-    adt.curr_source_pos.char_start += TAB_SIZE - (adt.curr_source_pos.char_start % TAB_SIZE);
-    adt.curr_source_pos.char_end += TAB_SIZE - (adt.curr_source_pos.char_end % TAB_SIZE);
     skip_next_character(adt);
     skip_spaces_comments_newlines(adt);
 }
 
 fn handle_newline(adt: &mut ScannerProductType) {
-    if adt.file_contents[adt.curr_char_index] == '\n' {
+    if matches!(adt.file_contents[adt.curr_char_index], '\n' | '\r') {
         skip_next_character(adt);
     }
     skip_spaces_comments_newlines(adt);
@@ -216,7 +280,6 @@ mod tests {
 
     use crate::scanner::{accept_next_character, ScannerProductType, skip_next_character};
     use crate::token::TokenKind;
-    use crate::utils::SourcePosition;
 
     #[test]
     fn it_works() {
@@ -1,18 +1,24 @@
-use std::collections::HashSet;
 use std::str::FromStr;
 
 use log::error;
 
+use crate::diagnostics::Diagnostic;
 use crate::scanner::{
-    accept_next_character, get_current_char, get_next_char, skip_next_character, ScannerProductType,
+    accept_next_character, current_span, get_current_char, get_next_char, skip_next_character,
+    ScannerProductType,
 };
+use crate::source_map::Span;
 use crate::token::TokenKind;
+use crate::utils::escapes::decode_escape;
+use crate::utils::int_literal_radix;
 
 pub fn handle_tokens(adt: &mut ScannerProductType) -> Result<ScannerProductType, String> {
     let a = 2;
 
     if let Ok(new_adt) = handle_special(adt) {
         Ok(new_adt)
+    } else if let Ok(new_adt) = handle_directives(adt) {
+        Ok(new_adt)
     } else if let Ok(new_adt) = handle_separators(adt) {
         Ok(new_adt)
     } else if let Ok(new_adt) = handle_operators(adt) {
@@ -39,6 +45,66 @@ pub fn handle_special(adt: &mut ScannerProductType) -> Result<ScannerProductType
     Err(String::from("Unhandled special token"))
 }
 
+// #include "file", which must start its line.  The spelling is the file name as written, and the
+// span covers the whole directive.  Anything else starting with # is skipped to the end of the
+// line as a single ERROR token.
+fn handle_directives(adt: &mut ScannerProductType) -> Result<ScannerProductType, String> {
+    if get_current_char(adt) != '#' || !at_line_start(adt) {
+        return Err(String::from("No match found in directives."));
+    }
+    skip_next_character(adt); // Absorbs the #.
+
+    let mut directive_name = String::new();
+    while get_current_char(adt).is_alphabetic() {
+        directive_name.push(get_current_char(adt));
+        skip_next_character(adt);
+    }
+    while matches!(get_current_char(adt), ' ' | '\t') {
+        skip_next_character(adt);
+    }
+
+    if directive_name == "include" && get_current_char(adt) == '"' {
+        skip_next_character(adt);
+        while !at_end_of_line(adt) && get_current_char(adt) != '"' {
+            accept_next_character(adt);
+        }
+        if !at_end_of_line(adt) {
+            skip_next_character(adt); // Absorbs the closing quote.
+            return Ok(ScannerProductType {
+                final_token_kind: TokenKind::INCLUDE,
+                ..adt.clone()
+            });
+        }
+    }
+
+    while !at_end_of_line(adt) {
+        skip_next_character(adt);
+    }
+    let message = match directive_name.as_str() {
+        "include" => String::from("malformed #include: expected a \"file\" name"),
+        _ => format!("unknown directive: #{}", directive_name),
+    };
+    adt.diagnostics
+        .push(Diagnostic::error(current_span(adt), message).with_code("S5"));
+
+    Ok(ScannerProductType {
+        final_token_kind: TokenKind::ERROR,
+        ..adt.clone()
+    })
+}
+
+fn at_line_start(adt: &ScannerProductType) -> bool {
+    adt.file_contents[..adt.curr_char_index]
+        .iter()
+        .rev()
+        .take_while(|c| !matches!(c, '\n' | '\r'))
+        .all(|c| c.is_whitespace())
+}
+
+fn at_end_of_line(adt: &mut ScannerProductType) -> bool {
+    adt.curr_char_index >= adt.file_contents.len() || matches!(get_current_char(adt), '\n' | '\r')
+}
+
 pub fn handle_separators(adt: &mut ScannerProductType) -> Result<ScannerProductType, String> {
     let current_char = adt.file_contents[adt.curr_char_index];
 
@@ -103,6 +169,28 @@ pub fn handle_separators(adt: &mut ScannerProductType) -> Result<ScannerProductT
                 ..adt.clone()
             })
         }
+        ':' => {
+            accept_next_character(adt);
+            Ok(ScannerProductType {
+                final_token_kind: TokenKind::COLON,
+                ..adt.clone()
+            })
+        }
+        '?' => {
+            accept_next_character(adt);
+            Ok(ScannerProductType {
+                final_token_kind: TokenKind::QUESTION,
+                ..adt.clone()
+            })
+        }
+        // A . followed by a digit starts a float such as .5 instead.
+        '.' if !get_next_char(adt).is_numeric() => {
+            accept_next_character(adt);
+            Ok(ScannerProductType {
+                final_token_kind: TokenKind::DOT,
+                ..adt.clone()
+            })
+        }
         _ => Err(String::from("No match found in separators.")),
     }
 }
@@ -112,13 +200,45 @@ fn handle_operators(adt: &mut ScannerProductType) -> Result<ScannerProductType,
     let next_char = get_next_char(adt);
 
     match (current_char, next_char) {
-        ('+', next_char) => {
+        ('+', '+') => {
+            accept_next_character(adt);
+            accept_next_character(adt);
+            Ok(ScannerProductType {
+                final_token_kind: TokenKind::PLUSPLUS,
+                ..adt.clone()
+            })
+        }
+        ('+', '=') => {
+            accept_next_character(adt);
+            accept_next_character(adt);
+            Ok(ScannerProductType {
+                final_token_kind: TokenKind::PLUSEQ,
+                ..adt.clone()
+            })
+        }
+        ('+', _) => {
             accept_next_character(adt);
             Ok(ScannerProductType {
                 final_token_kind: TokenKind::PLUS,
                 ..adt.clone()
             })
         }
+        ('-', '-') => {
+            accept_next_character(adt);
+            accept_next_character(adt);
+            Ok(ScannerProductType {
+                final_token_kind: TokenKind::MINUSMINUS,
+                ..adt.clone()
+            })
+        }
+        ('-', '=') => {
+            accept_next_character(adt);
+            accept_next_character(adt);
+            Ok(ScannerProductType {
+                final_token_kind: TokenKind::MINUSEQ,
+                ..adt.clone()
+            })
+        }
         ('-', _) => {
             accept_next_character(adt);
             Ok(ScannerProductType {
@@ -126,6 +246,14 @@ fn handle_operators(adt: &mut ScannerProductType) -> Result<ScannerProductType,
                 ..adt.clone()
             })
         }
+        ('*', '=') => {
+            accept_next_character(adt);
+            accept_next_character(adt);
+            Ok(ScannerProductType {
+                final_token_kind: TokenKind::MULTEQ,
+                ..adt.clone()
+            })
+        }
         ('*', _) => {
             accept_next_character(adt);
             Ok(ScannerProductType {
@@ -133,6 +261,14 @@ fn handle_operators(adt: &mut ScannerProductType) -> Result<ScannerProductType,
                 ..adt.clone()
             })
         }
+        ('/', '=') => {
+            accept_next_character(adt);
+            accept_next_character(adt);
+            Ok(ScannerProductType {
+                final_token_kind: TokenKind::DIVEQ,
+                ..adt.clone()
+            })
+        }
         ('/', _) => {
             accept_next_character(adt);
             Ok(ScannerProductType {
@@ -140,6 +276,13 @@ fn handle_operators(adt: &mut ScannerProductType) -> Result<ScannerProductType,
                 ..adt.clone()
             })
         }
+        ('%', _) => {
+            accept_next_character(adt);
+            Ok(ScannerProductType {
+                final_token_kind: TokenKind::MOD,
+                ..adt.clone()
+            })
+        }
         ('!', '=') => {
             accept_next_character(adt);
             accept_next_character(adt);
@@ -155,6 +298,13 @@ fn handle_operators(adt: &mut ScannerProductType) -> Result<ScannerProductType,
                 ..adt.clone()
             })
         }
+        ('~', _) => {
+            accept_next_character(adt);
+            Ok(ScannerProductType {
+                final_token_kind: TokenKind::TILDE,
+                ..adt.clone()
+            })
+        }
         ('=', '=') => {
             accept_next_character(adt);
             accept_next_character(adt);
@@ -170,6 +320,14 @@ fn handle_operators(adt: &mut ScannerProductType) -> Result<ScannerProductType,
                 ..adt.clone()
             })
         }
+        ('<', '<') => {
+            accept_next_character(adt);
+            accept_next_character(adt);
+            Ok(ScannerProductType {
+                final_token_kind: TokenKind::LTLT,
+                ..adt.clone()
+            })
+        }
         ('<', '=') => {
             accept_next_character(adt);
             accept_next_character(adt);
@@ -185,6 +343,14 @@ fn handle_operators(adt: &mut ScannerProductType) -> Result<ScannerProductType,
                 ..adt.clone()
             })
         }
+        ('>', '>') => {
+            accept_next_character(adt);
+            accept_next_character(adt);
+            Ok(ScannerProductType {
+                final_token_kind: TokenKind::GTGT,
+                ..adt.clone()
+            })
+        }
         ('>', '=') => {
             accept_next_character(adt);
             accept_next_character(adt);
@@ -208,6 +374,13 @@ fn handle_operators(adt: &mut ScannerProductType) -> Result<ScannerProductType,
                 ..adt.clone()
             })
         }
+        ('&', _) => {
+            accept_next_character(adt);
+            Ok(ScannerProductType {
+                final_token_kind: TokenKind::AND,
+                ..adt.clone()
+            })
+        }
         ('|', '|') => {
             accept_next_character(adt);
             accept_next_character(adt);
@@ -216,6 +389,20 @@ fn handle_operators(adt: &mut ScannerProductType) -> Result<ScannerProductType,
                 ..adt.clone()
             })
         }
+        ('|', _) => {
+            accept_next_character(adt);
+            Ok(ScannerProductType {
+                final_token_kind: TokenKind::OR,
+                ..adt.clone()
+            })
+        }
+        ('^', _) => {
+            accept_next_character(adt);
+            Ok(ScannerProductType {
+                final_token_kind: TokenKind::XOR,
+                ..adt.clone()
+            })
+        }
         _ => Err(String::from("No match found in operators.")),
     }
 }
@@ -227,8 +414,14 @@ fn handle_literals(adt: &mut ScannerProductType) -> Result<ScannerProductType, S
     match (curr_char, next_char) {
         (c, _) if c.is_numeric() || (c == '.' && next_char.is_numeric()) => handle_numbers(adt),
         ('"', _) => {
+            let string_start = Span::new(adt.file_id, adt.curr_byte_index, adt.curr_byte_index + 1);
             skip_next_character(adt); // Absorbs the first quote.
-            handle_strings(adt)
+            handle_strings(adt, string_start)
+        }
+        ('\'', _) => {
+            let char_start = Span::new(adt.file_id, adt.curr_byte_index, adt.curr_byte_index + 1);
+            skip_next_character(adt); // Absorbs the opening quote.
+            handle_chars(adt, char_start)
         }
         _ => Err(String::from("No match found in literals.")),
     }
@@ -274,35 +467,50 @@ fn handle_parse_ids(adt: &mut ScannerProductType) -> Result<ScannerProductType,
     }
 }
 
+// Integer literals are decimal, hex (0x), octal (0o, or a leading 0) or binary (0b), and any
+// literal may separate its digits with _.  A malformed literal keeps its spelling and is reported.
 fn handle_numbers(adt: &mut ScannerProductType) -> Result<ScannerProductType, String> {
-    let curr_char = get_current_char(adt);
-    let next_char = get_next_char(adt);
+    let number_start = adt.curr_byte_index;
 
-    match curr_char {
-        curr_char if curr_char.is_numeric() => {
-            accept_next_character(adt);
-            handle_numbers(adt)
-        }
-        '.' | 'e' | 'E' => {
+    let has_prefix = matches!(get_next_char(adt), 'x' | 'X' | 'o' | 'O' | 'b' | 'B');
+    if get_current_char(adt) == '0' && has_prefix {
+        accept_next_character(adt);
+        accept_next_character(adt);
+        // Letters are taken too, so that a bad digit is reported rather than starting an identifier.
+        while get_current_char(adt).is_ascii_alphanumeric() || get_current_char(adt) == '_' {
             accept_next_character(adt);
-            handle_floats(adt)
         }
-        _ => Ok(ScannerProductType {
+        check_int_literal(adt, number_start);
+        return Ok(ScannerProductType {
             final_token_kind: TokenKind::INTLITERAL,
             ..adt.clone()
-        }),
+        });
+    }
+
+    accept_digits(adt);
+    match get_current_char(adt) {
+        '.' | 'e' | 'E' => handle_floats(adt, number_start),
+        _ => {
+            check_int_literal(adt, number_start);
+            Ok(ScannerProductType {
+                final_token_kind: TokenKind::INTLITERAL,
+                ..adt.clone()
+            })
+        }
     }
 }
 
-fn handle_floats(adt: &mut ScannerProductType) -> Result<ScannerProductType, String> {
+fn handle_floats(
+    adt: &mut ScannerProductType,
+    number_start: usize,
+) -> Result<ScannerProductType, String> {
     if get_current_char(adt) == '.' {
         accept_next_character(adt);
-        while get_current_char(adt).is_numeric() {
-            accept_next_character(adt);
-        }
+        accept_digits(adt);
     }
 
     let curr_char = get_current_char(adt);
+    let mut exponent_digits = true;
 
     if curr_char == 'e' || curr_char == 'E' {
         accept_next_character(adt);
@@ -310,9 +518,18 @@ fn handle_floats(adt: &mut ScannerProductType) -> Result<ScannerProductType, Str
         if curr_char == '+' || curr_char == '-' {
             accept_next_character(adt);
         }
-        while get_current_char(adt).is_numeric() {
-            accept_next_character(adt);
-        }
+        let exponent_start = adt.curr_token_spelling.len();
+        accept_digits(adt);
+        exponent_digits = adt.curr_token_spelling[exponent_start..]
+            .starts_with(|c: char| c.is_ascii_digit());
+    }
+
+    // Reading the e of an exponent as a . catches 1_e5 along with 1_.5.
+    let digits = adt.curr_token_spelling.replace(['e', 'E'], ".");
+    if !exponent_digits {
+        malformed_number(adt, number_start, "the exponent has no digits");
+    } else if separators_misplaced(&digits) {
+        malformed_number(adt, number_start, "_ may only separate digits");
     }
 
     Ok(ScannerProductType {
@@ -321,16 +538,70 @@ fn handle_floats(adt: &mut ScannerProductType) -> Result<ScannerProductType, Str
     })
 }
 
-fn handle_strings(adt: &mut ScannerProductType) -> Result<ScannerProductType, String> {
-    // Skip the initial quote
-    // TODO: Fix for \"a\" here.
+fn accept_digits(adt: &mut ScannerProductType) {
+    while get_current_char(adt).is_ascii_digit() || get_current_char(adt) == '_' {
+        accept_next_character(adt);
+    }
+}
+
+// Every digit must belong to the literal's radix, and there must be at least one.
+fn check_int_literal(adt: &mut ScannerProductType, number_start: usize) {
+    let spelling = adt.curr_token_spelling.clone();
+    let (radix, digits) = int_literal_radix(&spelling);
+    let radix_name = match radix {
+        16 => "a hex",
+        8 => "an octal",
+        2 => "a binary",
+        _ => "a decimal",
+    };
+
+    if let Some(bad_digit) = digits.chars().find(|c| *c != '_' && !c.is_digit(radix)) {
+        let label = format!("{} is not {} digit", bad_digit, radix_name);
+        malformed_number(adt, number_start, &label);
+    } else if !digits.chars().any(|c| c.is_digit(radix)) {
+        let label = format!("{} literal needs at least one digit", radix_name);
+        malformed_number(adt, number_start, &label);
+    } else if separators_misplaced(digits) {
+        malformed_number(adt, number_start, "_ may only separate digits");
+    }
+}
+
+// A _ separator must sit between two digits.
+fn separators_misplaced(digits: &str) -> bool {
+    let digits: Vec<char> = digits.chars().collect();
+    digits.iter().enumerate().any(|(index, c)| {
+        *c == '_'
+            && !(index > 0
+                && digits[index - 1].is_ascii_alphanumeric()
+                && digits.get(index + 1).is_some_and(|next| next.is_ascii_alphanumeric()))
+    })
+}
+
+fn malformed_number(adt: &mut ScannerProductType, number_start: usize, label: &str) {
+    let span = Span::new(adt.file_id, number_start, adt.curr_byte_index);
+    adt.diagnostics.push(
+        Diagnostic::error(
+            span,
+            format!("malformed numeric literal: {}", adt.curr_token_spelling),
+        )
+        .with_code("S8")
+        .with_primary_label(label),
+    );
+}
+
+fn handle_strings(
+    adt: &mut ScannerProductType,
+    string_start: Span,
+) -> Result<ScannerProductType, String> {
+    // The initial quote has already been skipped.
+    if adt.curr_char_index >= adt.file_contents.len() {
+        return Ok(unterminated_string(adt, string_start));
+    }
 
     let curr_char = get_current_char(adt);
     let next_char = get_next_char(adt);
 
     match (curr_char, next_char) {
-        // Add more cases here for escaping.
-        // TODO: add case for escaping \'
         ('"', _) => {
             skip_next_character(adt);
             Ok(ScannerProductType {
@@ -339,25 +610,90 @@ fn handle_strings(adt: &mut ScannerProductType) -> Result<ScannerProductType, St
             })
         }
         ('\\', _) => {
-            // Generate a hash set of valid escape characters.
-            let escapes: HashSet<char> = ['n', 't', 'r', 'f', 'b', '\\', '"', '\'']
-                .iter()
-                .cloned()
-                .collect();
-            if escapes.contains(&next_char) {
-                accept_next_character(adt);
-                accept_next_character(adt);
-                handle_strings(adt)
-            } else {
-                Err(String::from("Invalid escape character: {next_char}"))
-            }
+            accept_escape(adt);
+            handle_strings(adt, string_start)
         }
-        ('\n', _) | ('\r', _) => Err(String::from(
-            "Error: Unterminated string literal.  adt: {adt}",
-        )),
+        ('\n', _) | ('\r', _) => Ok(unterminated_string(adt, string_start)),
         (_, _) => {
             accept_next_character(adt);
-            handle_strings(adt)
+            handle_strings(adt, string_start)
         }
     }
 }
+
+// Accepts a backslash and the rest of its escape, reporting the escape if it is malformed.
+fn accept_escape(adt: &mut ScannerProductType) {
+    // Spelling is kept as written; only the diagnostic records the problem.
+    let escape_start = adt.curr_byte_index;
+    let (value, length) = decode_escape(&adt.file_contents[adt.curr_char_index + 1..]);
+    for _ in 0..=length {
+        accept_next_character(adt);
+    }
+
+    if let Err(error) = value {
+        let span = Span::new(adt.file_id, escape_start, adt.curr_byte_index);
+        let mut diagnostic = Diagnostic::error(span, error.message).with_code("S4");
+        if let Some(reason) = error.reason {
+            diagnostic = diagnostic.with_primary_label(reason);
+        }
+        adt.diagnostics.push(diagnostic);
+    }
+}
+
+// A character literal holds exactly one character or escape.  As with strings, the quotes are
+// left out of the spelling.
+fn handle_chars(
+    adt: &mut ScannerProductType,
+    char_start: Span,
+) -> Result<ScannerProductType, String> {
+    let mut length = 0;
+    while !at_end_of_line(adt) && get_current_char(adt) != '\'' {
+        if get_current_char(adt) == '\\' {
+            accept_escape(adt);
+        } else {
+            accept_next_character(adt);
+        }
+        length += 1;
+    }
+
+    if at_end_of_line(adt) {
+        adt.diagnostics.push(
+            Diagnostic::error(char_start, "unterminated character literal")
+                .with_code("S6")
+                .with_primary_label("character literal starts here"),
+        );
+    } else {
+        skip_next_character(adt); // Absorbs the closing quote.
+        if length != 1 {
+            let message = if length == 0 {
+                "empty character literal"
+            } else {
+                "character literal holds more than one character"
+            };
+            adt.diagnostics
+                .push(Diagnostic::error(current_span(adt), message).with_code("S7"));
+        }
+    }
+
+    Ok(ScannerProductType {
+        final_token_kind: TokenKind::CHARLITERAL,
+        ..adt.clone()
+    })
+}
+
+// An unterminated string is still returned as a STRINGLITERAL holding what was read.
+fn unterminated_string(
+    adt: &mut ScannerProductType,
+    string_start: Span,
+) -> ScannerProductType {
+    adt.diagnostics.push(
+        Diagnostic::error(string_start, "unterminated string")
+            .with_code("S3")
+            .with_primary_label("string starts here"),
+    );
+
+    ScannerProductType {
+        final_token_kind: TokenKind::STRINGLITERAL,
+        ..adt.clone()
+    }
+}
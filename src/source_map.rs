@@ -0,0 +1,447 @@
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::utils::SourcePosition;
+
+/*
+   Source map
+   Tokens, AST nodes and diagnostics record where they came from as a Span: a file and a range of
+   byte offsets into it.  Line and column numbers are only worked out when something is shown to
+   a person, by asking the SourceMap that owns the loaded files, e.g.
+
+   let file_id = source_map.add_file("gcd.vc", contents);
+   ...
+   let position = source_map.position(token.span, ColumnMode::Utf8);
+*/
+
+// Index of a file within its SourceMap.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FileId(pub usize);
+
+// The half-open byte range lo..hi of one file.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Span {
+    pub file_id: FileId,
+    pub lo: usize,
+    pub hi: usize,
+}
+
+impl Span {
+    pub fn new(file_id: FileId, lo: usize, hi: usize) -> Self {
+        Self { file_id, lo, hi }
+    }
+
+    // An empty span at a single offset, e.g. the end of the file.
+    pub fn point(file_id: FileId, offset: usize) -> Self {
+        Self::new(file_id, offset, offset)
+    }
+
+    pub fn len(&self) -> usize {
+        self.hi - self.lo
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lo == self.hi
+    }
+
+    // From the start of self to the end of end, e.g. a binary expression from its two operands.
+    pub fn to(self, end: Span) -> Span {
+        Span::new(self.file_id, self.lo, end.hi.max(self.lo))
+    }
+
+    // The smallest span covering both.
+    pub fn merge(self, other: Span) -> Span {
+        Span::new(self.file_id, self.lo.min(other.lo), self.hi.max(other.hi))
+    }
+
+    pub fn shrink_to_lo(self) -> Span {
+        Span::point(self.file_id, self.lo)
+    }
+
+    pub fn shrink_to_hi(self) -> Span {
+        Span::point(self.file_id, self.hi)
+    }
+
+    pub fn contains(&self, offset: usize) -> bool {
+        self.lo <= offset && offset < self.hi
+    }
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}..{}", self.file_id.0, self.lo, self.hi)
+    }
+}
+
+/*
+   How columns are counted.  Lines and columns are always 1-based.
+
+   Utf8    one column per character, however many bytes it takes, as most editors count.
+   Tabs(n) like Utf8, except a tab advances to the next tab stop every n columns, as the source
+           looks when printed, and as the VC reference compiler counts.
+   Utf16   one column per UTF-16 code unit, as the Language Server Protocol counts.
+*/
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColumnMode {
+    Utf8,
+    Tabs(usize),
+    Utf16,
+}
+
+// A line ends at \n, \r\n or a lone \r.  Each line keeps its ending; the last may have none.
+pub fn lines_with_endings(source: &str) -> impl Iterator<Item = &str> {
+    let mut rest = source;
+    std::iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+        let end = match rest.find(['\n', '\r']) {
+            Some(index) if rest[index..].starts_with("\r\n") => index + 2,
+            Some(index) => index + 1,
+            None => rest.len(),
+        };
+        let (line, after) = rest.split_at(end);
+        rest = after;
+        Some(line)
+    })
+}
+
+pub fn without_line_ending(line: &str) -> &str {
+    line.strip_suffix("\r\n")
+        .or_else(|| line.strip_suffix(['\n', '\r']))
+        .unwrap_or(line)
+}
+
+#[derive(Debug)]
+pub struct SourceFile {
+    pub file_id: FileId,
+    pub name: String,
+    pub source: String,
+    // Byte offset of the start of each line.
+    line_starts: Vec<usize>,
+    // Length of a leading byte order mark, which is not part of the first line's text.
+    bom_length: usize,
+}
+
+impl SourceFile {
+    pub fn new(file_id: FileId, name: impl Into<String>, source: String) -> Self {
+        // The first line always starts at 0, even in an empty file.  A line ending at the very end
+        // of the file starts one more, empty, line.
+        let line_starts = std::iter::once(0)
+            .chain(
+                lines_with_endings(&source)
+                    .scan(0, |line_start, line| {
+                        *line_start += line.len();
+                        Some(*line_start)
+                    })
+                    .filter(|line_start| {
+                        *line_start < source.len() || source.ends_with(['\n', '\r'])
+                    }),
+            )
+            .collect();
+        let bom_length = source.len() - source.strip_prefix('\u{FEFF}').unwrap_or(&source).len();
+
+        Self {
+            file_id,
+            name: name.into(),
+            source,
+            line_starts,
+            bom_length,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.source.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.source.is_empty()
+    }
+
+    pub fn span(&self) -> Span {
+        Span::new(self.file_id, 0, self.source.len())
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    // 1-based line containing offset.  The end of the file belongs to the last line.
+    pub fn line_number(&self, offset: usize) -> usize {
+        match self.line_starts.binary_search(&offset) {
+            Ok(index) => index + 1,
+            Err(index) => index,
+        }
+    }
+
+    // Text of a 1-based line, without its line ending.
+    pub fn line_text(&self, line_number: usize) -> Option<&str> {
+        let start = self.line_start(line_number)?;
+        let end = self
+            .line_starts
+            .get(line_number)
+            .copied()
+            .unwrap_or(self.source.len());
+        Some(without_line_ending(&self.source[start..end]))
+    }
+
+    // Byte offset of the first character of a 1-based line, after any byte order mark.
+    pub fn line_start(&self, line_number: usize) -> Option<usize> {
+        let start = *self.line_starts.get(line_number.checked_sub(1)?)?;
+        Some(if line_number == 1 { self.bom_length } else { start })
+    }
+
+    // 1-based column of offset on its line.
+    pub fn column(&self, offset: usize, mode: ColumnMode) -> usize {
+        let offset = self.clamp_to_char_boundary(offset);
+        let line_start = self.line_start(self.line_number(offset)).unwrap_or(0);
+        column_of(&self.source[line_start.min(offset)..offset], mode)
+    }
+
+    // 1-based line and column of offset.
+    pub fn line_col(&self, offset: usize, mode: ColumnMode) -> (usize, usize) {
+        (self.line_number(offset), self.column(offset, mode))
+    }
+
+    // Byte offset of a 1-based line and column, the inverse of line_col.  Columns past the end of
+    // the line are clamped to it.
+    pub fn offset(&self, line_number: usize, column: usize, mode: ColumnMode) -> Option<usize> {
+        let start = self.line_start(line_number)?;
+        let line = self.line_text(line_number)?;
+
+        let mut current = 1;
+        for (index, c) in line.char_indices() {
+            if current >= column {
+                return Some(start + index);
+            }
+            current = advance_column(current, c, mode);
+        }
+        Some(start + line.len())
+    }
+
+    // Line and column of the first and last character of span.  An empty span is shown as the one
+    // character at its start, matching how SourcePosition has always been printed.
+    pub fn position(&self, span: Span, mode: ColumnMode) -> SourcePosition {
+        let (line_start, char_start) = self.line_col(span.lo, mode);
+        let (line_finish, char_end) = match self.text(span).char_indices().last() {
+            Some((last_char_index, _)) => self.line_col(span.lo + last_char_index, mode),
+            None => (line_start, char_start),
+        };
+
+        SourcePosition::new(
+            line_start as i32,
+            line_finish as i32,
+            char_start as i32,
+            char_end as i32,
+        )
+    }
+
+    // Number of characters between two byte offsets.
+    pub fn char_count(&self, lo: usize, hi: usize) -> usize {
+        self.text(Span::new(self.file_id, lo, hi)).chars().count()
+    }
+
+    pub fn text(&self, span: Span) -> &str {
+        let lo = self.clamp_to_char_boundary(span.lo);
+        let hi = self.clamp_to_char_boundary(span.hi.max(span.lo));
+        &self.source[lo..hi]
+    }
+
+    fn clamp_to_char_boundary(&self, offset: usize) -> usize {
+        let mut offset = offset.min(self.source.len());
+        while !self.source.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        offset
+    }
+}
+
+fn advance_column(column: usize, c: char, mode: ColumnMode) -> usize {
+    match (c, mode) {
+        ('\t', ColumnMode::Tabs(tab_size)) => column + tab_size - ((column - 1) % tab_size),
+        (_, ColumnMode::Utf16) => column + c.len_utf16(),
+        _ => column + 1,
+    }
+}
+
+fn column_of(line_prefix: &str, mode: ColumnMode) -> usize {
+    line_prefix
+        .chars()
+        .fold(1, |column, c| advance_column(column, c, mode))
+}
+
+/*
+   SourceMap
+   Owns every file loaded during a run.  A FileId is an index into files, so a Span from any
+   file can be resolved back to its name, text, line and column.
+*/
+#[derive(Debug, Default)]
+pub struct SourceMap {
+    files: Vec<Arc<SourceFile>>,
+}
+
+impl SourceMap {
+    pub fn new() -> Self {
+        Self { files: Vec::new() }
+    }
+
+    pub fn add_file(&mut self, name: impl Into<String>, source: String) -> FileId {
+        let file_id = FileId(self.files.len());
+        self.files
+            .push(Arc::new(SourceFile::new(file_id, name, source)));
+        file_id
+    }
+
+    pub fn load_file(&mut self, path: impl AsRef<Path>) -> std::io::Result<FileId> {
+        let path = path.as_ref();
+        let source = std::fs::read_to_string(path)?;
+        Ok(self.add_file(path.to_string_lossy(), source))
+    }
+
+    pub fn file(&self, file_id: FileId) -> &Arc<SourceFile> {
+        &self.files[file_id.0]
+    }
+
+    pub fn files(&self) -> impl Iterator<Item = &Arc<SourceFile>> {
+        self.files.iter()
+    }
+
+    pub fn file_name(&self, span: Span) -> &str {
+        &self.file(span.file_id).name
+    }
+
+    pub fn text(&self, span: Span) -> &str {
+        self.file(span.file_id).text(span)
+    }
+
+    pub fn position(&self, span: Span, mode: ColumnMode) -> SourcePosition {
+        self.file(span.file_id).position(span, mode)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source_map(source: &str) -> (SourceMap, FileId) {
+        let mut source_map = SourceMap::new();
+        let file_id = source_map.add_file("test.vc", source.to_string());
+        (source_map, file_id)
+    }
+
+    #[test]
+    fn test_line_col_modes() {
+        let (source_map, file_id) = source_map("int a;\n\tint bé;\n");
+        let file = source_map.file(file_id);
+
+        let b = file.source.find('b').unwrap();
+        assert_eq!(file.line_col(b, ColumnMode::Utf8), (2, 6));
+        assert_eq!(file.line_col(b, ColumnMode::Tabs(4)), (2, 9));
+        assert_eq!(file.line_col(b, ColumnMode::Utf16), (2, 6));
+
+        let semicolon = file.source.rfind(';').unwrap();
+        assert_eq!(file.line_col(semicolon, ColumnMode::Utf8), (2, 8));
+        assert_eq!(file.line_col(file.len(), ColumnMode::Utf8), (3, 1));
+    }
+
+    #[test]
+    fn test_tab_stops() {
+        let (source_map, file_id) = source_map("abc\tx\n   \ty\n\t\tz");
+        let file = source_map.file(file_id);
+
+        for (c, line, column) in [('x', 1, 5), ('y', 2, 5), ('z', 3, 9)] {
+            let offset = file.source.find(c).unwrap();
+            assert_eq!(file.line_col(offset, ColumnMode::Tabs(4)), (line, column));
+        }
+    }
+
+    #[test]
+    fn test_utf16_columns() {
+        let (source_map, file_id) = source_map("s = \"\u{1F600}\"; x");
+        let file = source_map.file(file_id);
+
+        let x = file.source.find('x').unwrap();
+        assert_eq!(file.line_col(x, ColumnMode::Utf8), (1, 10));
+        assert_eq!(file.line_col(x, ColumnMode::Utf16), (1, 11));
+        assert_eq!(file.offset(1, 11, ColumnMode::Utf16), Some(x));
+    }
+
+    #[test]
+    fn test_offset_round_trip() {
+        let (source_map, file_id) = source_map("int é;\r\n\tfloat f;");
+        let file = source_map.file(file_id);
+
+        for (offset, _) in file.source.char_indices() {
+            for mode in [ColumnMode::Utf8, ColumnMode::Tabs(4), ColumnMode::Utf16] {
+                let (line, column) = file.line_col(offset, mode);
+                if file.line_text(line).unwrap().len() + file.line_start(line).unwrap() > offset {
+                    assert_eq!(file.offset(line, column, mode), Some(offset));
+                }
+            }
+        }
+        assert_eq!(file.line_text(1), Some("int é;"));
+    }
+
+    #[test]
+    fn test_line_endings() {
+        let (source_map, file_id) = source_map("a\r\nb\rc\nd\r");
+        let file = source_map.file(file_id);
+
+        assert_eq!(file.line_count(), 5);
+        assert_eq!(file.line_col(file.source.find('c').unwrap(), ColumnMode::Utf8), (3, 1));
+        assert_eq!(file.line_text(1), Some("a"));
+        assert_eq!(file.line_text(2), Some("b"));
+        assert_eq!(file.line_text(5), Some(""));
+    }
+
+    #[test]
+    fn test_empty_file() {
+        let (source_map, file_id) = source_map("");
+        let file = source_map.file(file_id);
+
+        assert_eq!(file.line_count(), 1);
+        assert_eq!(file.line_col(0, ColumnMode::Utf8), (1, 1));
+        assert_eq!(file.line_text(1), Some(""));
+    }
+
+    #[test]
+    fn test_byte_order_mark() {
+        let (source_map, file_id) = source_map("\u{FEFF}int x;\nint y;");
+        let file = source_map.file(file_id);
+
+        let x = file.source.find('x').unwrap();
+        assert_eq!(file.line_col(x, ColumnMode::Utf8), (1, 5));
+        assert_eq!(file.line_col(x, ColumnMode::Utf16), (1, 5));
+        assert_eq!(file.offset(1, 5, ColumnMode::Utf8), Some(x));
+        assert_eq!(file.line_text(1), Some("int x;"));
+    }
+
+    #[test]
+    fn test_span_merge() {
+        let file_id = FileId(0);
+        let lhs = Span::new(file_id, 4, 5);
+        let rhs = Span::new(file_id, 8, 10);
+
+        assert_eq!(lhs.to(rhs), Span::new(file_id, 4, 10));
+        assert_eq!(rhs.merge(lhs), Span::new(file_id, 4, 10));
+        assert_eq!(lhs.to(rhs).len(), 6);
+        assert!(lhs.shrink_to_hi().is_empty());
+    }
+
+    #[test]
+    fn test_position() {
+        let (source_map, file_id) = source_map("x = 1;\nfoo(a,\n    b);");
+
+        let call = Span::new(file_id, 7, 20);
+        assert_eq!(source_map.text(call), "foo(a,\n    b)");
+        assert_eq!(
+            source_map.position(call, ColumnMode::Utf8),
+            SourcePosition::new(2, 3, 1, 6)
+        );
+        assert_eq!(
+            source_map.position(Span::point(file_id, 4), ColumnMode::Utf8),
+            SourcePosition::new(1, 1, 5, 5)
+        );
+    }
+}
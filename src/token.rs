@@ -1,12 +1,18 @@
+use std::fmt;
 use std::str::FromStr;
 
+use crate::source_map::Span;
 use crate::utils::SourcePosition;
 
-#[derive(Debug, PartialEq)]
+#[derive(PartialEq)]
 pub struct Token {
     pub token_kind: TokenKind,
     pub spelling: String,
+    // Line and column as the VC reference compiler reports them, with tab-aware columns.
     pub token_position: SourcePosition,
+    pub span: Span,
+    // Whether it came from expanding a macro, in which case span covers the macro's use.
+    pub expanded: bool,
 }
 
 impl Token {
@@ -17,12 +23,16 @@ impl Token {
                 token_kind: kind,
                 spelling,
                 token_position: position,
+                span: Span::default(),
+                expanded: false,
             }
         } else {
             Self {
                 token_kind: kind,
                 spelling,
                 token_position: position,
+                span: Span::default(),
+                expanded: false,
             }
         }
     }
@@ -33,18 +43,38 @@ impl Token {
     // Use default implementation of ToString derived from Debug
 }
 
+// The token listing printed by `vc scan`, which the scanner solution files record.  The span is
+// left out: token_position already says where the token is.
+impl fmt::Debug for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Token")
+            .field("token_kind", &self.token_kind)
+            .field("spelling", &self.spelling)
+            .field("token_position", &self.token_position)
+            .finish()
+    }
+}
+
 #[derive(Debug, Eq, Hash, PartialEq, Clone, Copy)]
 pub enum TokenKind {
     // Token kinds referred to by name.  As per enum defintion.
     BOOLEAN,
     BREAK,
+    CHAR,
+    CASE,
+    CONST,
     CONTINUE,
+    DEFAULT,
+    DO,
     ELSE,
     FLOAT,
     FOR,
     IF,
     INT,
     RETURN,
+    STRING,
+    STRUCT,
+    SWITCH,
     VOID,
     WHILE,
 
@@ -63,6 +93,19 @@ pub enum TokenKind {
     GTEQ,
     ANDAND,
     OROR,
+    PLUSEQ,
+    MINUSEQ,
+    MULTEQ,
+    DIVEQ,
+    PLUSPLUS,
+    MINUSMINUS,
+    MOD,
+    AND,
+    OR,
+    XOR,
+    TILDE,
+    LTLT,
+    GTGT,
 
     // separators
     LBRACE,
@@ -73,6 +116,9 @@ pub enum TokenKind {
     RBRACKET,
     SEMICOLON,
     COMMA,
+    COLON,
+    QUESTION,
+    DOT,
 
     // identifiers
     ID,
@@ -81,8 +127,12 @@ pub enum TokenKind {
     INTLITERAL,
     FLOATLITERAL,
     BOOLEANLITERAL,
+    CHARLITERAL,
     STRINGLITERAL,
 
+    // directives
+    INCLUDE,
+
     // special tokens
     ERROR,
     EOF,
@@ -108,13 +158,21 @@ impl FromStr for TokenKind {
         match s {
             "boolean" => Ok(TokenKind::BOOLEAN),
             "break" => Ok(TokenKind::BREAK),
+            "case" => Ok(TokenKind::CASE),
+            "char" => Ok(TokenKind::CHAR),
+            "const" => Ok(TokenKind::CONST),
             "continue" => Ok(TokenKind::CONTINUE),
+            "default" => Ok(TokenKind::DEFAULT),
+            "do" => Ok(TokenKind::DO),
             "else" => Ok(TokenKind::ELSE),
             "float" => Ok(TokenKind::FLOAT),
             "for" => Ok(TokenKind::FOR),
             "if" => Ok(TokenKind::IF),
             "int" => Ok(TokenKind::INT),
             "return" => Ok(TokenKind::RETURN),
+            "string" => Ok(TokenKind::STRING),
+            "struct" => Ok(TokenKind::STRUCT),
+            "switch" => Ok(TokenKind::SWITCH),
             "void" => Ok(TokenKind::VOID),
             "while" => Ok(TokenKind::WHILE),
             "+" => Ok(TokenKind::PLUS),
@@ -131,6 +189,19 @@ impl FromStr for TokenKind {
             ">=" => Ok(TokenKind::GTEQ),
             "&&" => Ok(TokenKind::ANDAND),
             "||" => Ok(TokenKind::OROR),
+            "+=" => Ok(TokenKind::PLUSEQ),
+            "-=" => Ok(TokenKind::MINUSEQ),
+            "*=" => Ok(TokenKind::MULTEQ),
+            "/=" => Ok(TokenKind::DIVEQ),
+            "++" => Ok(TokenKind::PLUSPLUS),
+            "--" => Ok(TokenKind::MINUSMINUS),
+            "%" => Ok(TokenKind::MOD),
+            "&" => Ok(TokenKind::AND),
+            "|" => Ok(TokenKind::OR),
+            "^" => Ok(TokenKind::XOR),
+            "~" => Ok(TokenKind::TILDE),
+            "<<" => Ok(TokenKind::LTLT),
+            ">>" => Ok(TokenKind::GTGT),
             "{" => Ok(TokenKind::LBRACE),
             "}" => Ok(TokenKind::RBRACE),
             "(" => Ok(TokenKind::LPAREN),
@@ -139,10 +210,15 @@ impl FromStr for TokenKind {
             "]" => Ok(TokenKind::RBRACKET),
             ";" => Ok(TokenKind::SEMICOLON),
             "," => Ok(TokenKind::COMMA),
+            ":" => Ok(TokenKind::COLON),
+            "?" => Ok(TokenKind::QUESTION),
+            "." => Ok(TokenKind::DOT),
             "<int-literal>" => Ok(TokenKind::INTLITERAL),
             "<float-literal>" => Ok(TokenKind::FLOATLITERAL),
             "<boolean-literal>" => Ok(TokenKind::BOOLEANLITERAL),
+            "<char-literal>" => Ok(TokenKind::CHARLITERAL),
             "<string-literal>" => Ok(TokenKind::STRINGLITERAL),
+            "#include" => Ok(TokenKind::INCLUDE),
             "<error>" => Ok(TokenKind::ERROR),
             "$" => Ok(TokenKind::EOF),
             _ => Ok(TokenKind::ID),
@@ -155,13 +231,21 @@ impl std::string::ToString for TokenKind {
         match self {
             TokenKind::BOOLEAN => String::from("boolean"),
             TokenKind::BREAK => String::from("break"),
+            TokenKind::CASE => String::from("case"),
+            TokenKind::CHAR => String::from("char"),
+            TokenKind::CONST => String::from("const"),
             TokenKind::CONTINUE => String::from("continue"),
+            TokenKind::DEFAULT => String::from("default"),
+            TokenKind::DO => String::from("do"),
             TokenKind::ELSE => String::from("else"),
             TokenKind::FLOAT => String::from("float"),
             TokenKind::FOR => String::from("for"),
             TokenKind::IF => String::from("if"),
             TokenKind::INT => String::from("int"),
             TokenKind::RETURN => String::from("return"),
+            TokenKind::STRING => String::from("string"),
+            TokenKind::STRUCT => String::from("struct"),
+            TokenKind::SWITCH => String::from("switch"),
             TokenKind::VOID => String::from("void"),
             TokenKind::WHILE => String::from("while"),
             TokenKind::PLUS => String::from("+"),
@@ -178,6 +262,19 @@ impl std::string::ToString for TokenKind {
             TokenKind::GTEQ => String::from(">="),
             TokenKind::ANDAND => String::from("&&"),
             TokenKind::OROR => String::from("||"),
+            TokenKind::PLUSEQ => String::from("+="),
+            TokenKind::MINUSEQ => String::from("-="),
+            TokenKind::MULTEQ => String::from("*="),
+            TokenKind::DIVEQ => String::from("/="),
+            TokenKind::PLUSPLUS => String::from("++"),
+            TokenKind::MINUSMINUS => String::from("--"),
+            TokenKind::MOD => String::from("%"),
+            TokenKind::AND => String::from("&"),
+            TokenKind::OR => String::from("|"),
+            TokenKind::XOR => String::from("^"),
+            TokenKind::TILDE => String::from("~"),
+            TokenKind::LTLT => String::from("<<"),
+            TokenKind::GTGT => String::from(">>"),
             TokenKind::LBRACE => String::from("{"),
             TokenKind::RBRACE => String::from("}"),
             TokenKind::LPAREN => String::from("("),
@@ -186,11 +283,16 @@ impl std::string::ToString for TokenKind {
             TokenKind::RBRACKET => String::from("]"),
             TokenKind::SEMICOLON => String::from(";"),
             TokenKind::COMMA => String::from(","),
+            TokenKind::COLON => String::from(":"),
+            TokenKind::QUESTION => String::from("?"),
+            TokenKind::DOT => String::from("."),
             TokenKind::ID => String::from("<id>"),
             TokenKind::INTLITERAL => String::from("<int-literal>"),
             TokenKind::FLOATLITERAL => String::from("<float-literal>"),
             TokenKind::BOOLEANLITERAL => String::from("<boolean-literal>"),
+            TokenKind::CHARLITERAL => String::from("<char-literal>"),
             TokenKind::STRINGLITERAL => String::from("<string-literal>"),
+            TokenKind::INCLUDE => String::from("#include"),
             TokenKind::ERROR => String::from("<error>"),
             TokenKind::EOF => String::from("$"),
         }
@@ -5,7 +5,32 @@ use regex::Regex;
 use crate::globals::TAB_SIZE;
 use crate::token::TokenKind;
 
-pub mod unparser;
+pub mod escapes;
+
+// The radix an integer literal is written in, and the digits after any prefix: 0x is hex, 0o or a
+// leading 0 octal, and 0b binary.
+pub fn int_literal_radix(spelling: &str) -> (u32, &str) {
+    match spelling.get(..2) {
+        Some("0x") | Some("0X") => (16, &spelling[2..]),
+        Some("0o") | Some("0O") => (8, &spelling[2..]),
+        Some("0b") | Some("0B") => (2, &spelling[2..]),
+        _ if spelling.len() > 1 && spelling.starts_with('0') => (8, &spelling[1..]),
+        _ => (10, spelling),
+    }
+}
+
+// The value of an integer literal, ignoring _ separators.  None if it is malformed or does not fit
+// in 64 bits.
+pub fn int_literal_value(spelling: &str) -> Option<u64> {
+    let (radix, digits) = int_literal_radix(spelling);
+    let digits = digits.replace('_', "");
+    u64::from_str_radix(&digits, radix).ok()
+}
+
+// The value of a float literal, ignoring _ separators.  Too large a literal is infinite.
+pub fn float_literal_value(spelling: &str) -> Option<f64> {
+    spelling.replace('_', "").parse().ok()
+}
 
 pub fn generate_tabbed_string(s: &str, depth: i32) -> String {
     let node_name = s.rsplit("::").next().unwrap();
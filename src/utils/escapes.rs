@@ -0,0 +1,192 @@
+/*
+   Escapes
+   String and character literals keep their escapes as written, so decoding and encoding them
+   both live here.  A backslash may be followed by one of the letters in ESCAPES, by 0 for the
+   null character, by \xNN for an ASCII character in two hex digits, or by \u{N...} for any
+   Unicode scalar value in one to six hex digits.
+*/
+
+// The single-letter escapes: the letter after the backslash, and the character it stands for.
+pub const ESCAPES: [(char, char); 9] = [
+    ('0', '\0'),
+    ('b', '\x08'),
+    ('f', '\x0C'),
+    ('n', '\n'),
+    ('r', '\r'),
+    ('t', '\t'),
+    ('\'', '\''),
+    ('"', '"'),
+    ('\\', '\\'),
+];
+
+// Why an escape was rejected: the message, and for a malformed \x or \u, what it should hold.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EscapeError {
+    pub message: String,
+    pub reason: Option<&'static str>,
+}
+
+impl EscapeError {
+    fn illegal(spelling: &str) -> Self {
+        Self {
+            message: format!("illegal escape character: \\{}", spelling),
+            reason: None,
+        }
+    }
+
+    fn malformed(spelling: &str, reason: &'static str) -> Self {
+        Self {
+            message: format!("malformed escape: \\{}", spelling),
+            reason: Some(reason),
+        }
+    }
+}
+
+// Reads one escape from the characters after a backslash.  Gives the character it stands for, or
+// why it was rejected, along with how many of the characters the escape spans.
+pub fn decode_escape(rest: &[char]) -> (Result<char, EscapeError>, usize) {
+    let Some(&letter) = rest.first() else {
+        return (Err(EscapeError::illegal("")), 0);
+    };
+    if let Some((_, value)) = ESCAPES.iter().find(|(escape, _)| *escape == letter) {
+        return (Ok(*value), 1);
+    }
+
+    match letter {
+        'x' => {
+            let digits = hex_digits(&rest[1..], 2);
+            let spelling: String = rest[..1 + digits.len()].iter().collect();
+            let value = match u32::from_str_radix(&digits, 16) {
+                Ok(code) if digits.len() == 2 && code <= 0x7F => Ok(char::from(code as u8)),
+                Ok(_) if digits.len() == 2 => Err(EscapeError::malformed(
+                    &spelling,
+                    "\\x escapes only reach \\x7F; use \\u{...} beyond it",
+                )),
+                _ => Err(EscapeError::malformed(
+                    &spelling,
+                    "\\x takes two hex digits",
+                )),
+            };
+            (value, spelling.chars().count())
+        }
+        'u' => {
+            if rest.get(1) != Some(&'{') {
+                let reason = "\\u takes one to six hex digits in braces";
+                return (Err(EscapeError::malformed("u", reason)), 1);
+            }
+            let digits = hex_digits(&rest[2..], 6);
+            let closed = rest.get(2 + digits.len()) == Some(&'}');
+            let length = 2 + digits.len() + usize::from(closed);
+            let spelling: String = rest[..length].iter().collect();
+            let value = match u32::from_str_radix(&digits, 16).ok().map(char::from_u32) {
+                Some(Some(value)) if closed => Ok(value),
+                Some(None) => Err(EscapeError::malformed(
+                    &spelling,
+                    "not a Unicode scalar value",
+                )),
+                _ => Err(EscapeError::malformed(
+                    &spelling,
+                    "\\u takes one to six hex digits in braces",
+                )),
+            };
+            (value, length)
+        }
+        '\n' | '\r' => (Err(EscapeError::illegal("")), 0),
+        _ => (Err(EscapeError::illegal(&letter.to_string())), 1),
+    }
+}
+
+fn hex_digits(chars: &[char], limit: usize) -> String {
+    chars
+        .iter()
+        .take(limit)
+        .take_while(|c| c.is_ascii_hexdigit())
+        .collect()
+}
+
+// The characters a literal spelling stands for.  A malformed escape, which the scanner has
+// already reported, is kept as written.
+pub fn decode_escapes(spelling: &str) -> String {
+    let chars: Vec<char> = spelling.chars().collect();
+    let mut decoded = String::new();
+    let mut index = 0;
+
+    while index < chars.len() {
+        if chars[index] != '\\' {
+            decoded.push(chars[index]);
+            index += 1;
+            continue;
+        }
+        let (value, length) = decode_escape(&chars[index + 1..]);
+        match value {
+            Ok(value) => decoded.push(value),
+            Err(_) => decoded.extend(&chars[index..=index + length]),
+        }
+        index += 1 + length;
+    }
+
+    decoded
+}
+
+// Spells a value as the inside of a literal quoted by `quote`, escaping whatever could not appear
+// there as written.  Decoding the result gives back the value.
+pub fn encode_escapes(value: &str, quote: char) -> String {
+    let mut encoded = String::new();
+
+    for c in value.chars() {
+        let letter = ESCAPES
+            .iter()
+            .find(|(_, escaped)| *escaped == c)
+            .map(|(letter, _)| *letter);
+        match letter {
+            Some(letter) if c == quote || c == '\\' || !matches!(c, '\'' | '"') => {
+                encoded.push('\\');
+                encoded.push(letter);
+            }
+            _ if c.is_control() && u32::from(c) <= 0x7F => {
+                encoded.push_str(&format!("\\x{:02X}", u32::from(c)));
+            }
+            _ if c.is_control() => encoded.push_str(&format!("\\u{{{:X}}}", u32::from(c))),
+            _ => encoded.push(c),
+        }
+    }
+
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_escapes() {
+        assert_eq!(decode_escapes(r"a\tb\\c\'\0"), "a\tb\\c'\0");
+        assert_eq!(decode_escapes(r"\x41\x7e"), "A~");
+        assert_eq!(decode_escapes(r"\u{e9}\u{1F600}"), "é😀");
+        // Malformed escapes are kept as written.
+        assert_eq!(decode_escapes(r"\q\x4g\u{110000}"), r"\q\x4g\u{110000}");
+    }
+
+    #[test]
+    fn test_decode_escape_lengths() {
+        let decode = |rest: &str| decode_escape(&rest.chars().collect::<Vec<_>>());
+        assert_eq!(decode("n..."), (Ok('\n'), 1));
+        assert_eq!(decode("x41..."), (Ok('A'), 3));
+        assert_eq!(decode("u{41}..."), (Ok('A'), 5));
+        assert_eq!(decode("x4").1, 2);
+        assert_eq!(decode("u{41").1, 4);
+        assert!(decode("x80").0.is_err());
+        assert!(decode("u{D800}").0.is_err());
+    }
+
+    #[test]
+    fn test_encode_escapes() {
+        let value = "tab\there \"quoted\" it's\\ \0 \x07 é";
+        assert_eq!(
+            encode_escapes(value, '"'),
+            r#"tab\there \"quoted\" it's\\ \0 \x07 é"#
+        );
+        assert_eq!(decode_escapes(&encode_escapes(value, '"')), value);
+        assert_eq!(encode_escapes("it's \"x\"", '\''), r#"it\'s "x""#);
+    }
+}
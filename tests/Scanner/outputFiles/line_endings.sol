@@ -0,0 +1,22 @@
+Token { token_kind: INT, spelling: "int", token_position: SourcePosition { line_start: 2, line_finish: 2, char_start: 1, char_end: 3 } }
+Token { token_kind: ID, spelling: "a", token_position: SourcePosition { line_start: 2, line_finish: 2, char_start: 5, char_end: 5 } }
+Token { token_kind: LBRACKET, spelling: "[", token_position: SourcePosition { line_start: 2, line_finish: 2, char_start: 6, char_end: 6 } }
+Token { token_kind: INTLITERAL, spelling: "3", token_position: SourcePosition { line_start: 2, line_finish: 2, char_start: 7, char_end: 10 } }
+Token { token_kind: RBRACKET, spelling: "]", token_position: SourcePosition { line_start: 2, line_finish: 2, char_start: 11, char_end: 11 } }
+Token { token_kind: SEMICOLON, spelling: ";", token_position: SourcePosition { line_start: 2, line_finish: 2, char_start: 12, char_end: 12 } }
+Token { token_kind: INT, spelling: "int", token_position: SourcePosition { line_start: 3, line_finish: 3, char_start: 1, char_end: 3 } }
+Token { token_kind: ID, spelling: "b", token_position: SourcePosition { line_start: 3, line_finish: 3, char_start: 5, char_end: 5 } }
+Token { token_kind: SEMICOLON, spelling: ";", token_position: SourcePosition { line_start: 3, line_finish: 3, char_start: 6, char_end: 6 } }
+Token { token_kind: INT, spelling: "int", token_position: SourcePosition { line_start: 4, line_finish: 4, char_start: 2, char_end: 4 } }
+Token { token_kind: ID, spelling: "c", token_position: SourcePosition { line_start: 4, line_finish: 4, char_start: 6, char_end: 6 } }
+Token { token_kind: SEMICOLON, spelling: ";", token_position: SourcePosition { line_start: 4, line_finish: 4, char_start: 7, char_end: 7 } }
+Token { token_kind: INT, spelling: "int", token_position: SourcePosition { line_start: 5, line_finish: 5, char_start: 1, char_end: 3 } }
+Token { token_kind: ID, spelling: "d", token_position: SourcePosition { line_start: 5, line_finish: 5, char_start: 5, char_end: 5 } }
+Token { token_kind: SEMICOLON, spelling: ";", token_position: SourcePosition { line_start: 5, line_finish: 5, char_start: 6, char_end: 6 } }
+Token { token_kind: INT, spelling: "int", token_position: SourcePosition { line_start: 7, line_finish: 7, char_start: 19, char_end: 21 } }
+Token { token_kind: ID, spelling: "e", token_position: SourcePosition { line_start: 7, line_finish: 7, char_start: 23, char_end: 23 } }
+Token { token_kind: SEMICOLON, spelling: ";", token_position: SourcePosition { line_start: 7, line_finish: 7, char_start: 24, char_end: 24 } }
+Token { token_kind: INT, spelling: "int", token_position: SourcePosition { line_start: 9, line_finish: 9, char_start: 1, char_end: 3 } }
+Token { token_kind: ID, spelling: "f", token_position: SourcePosition { line_start: 9, line_finish: 9, char_start: 5, char_end: 5 } }
+Token { token_kind: SEMICOLON, spelling: ";", token_position: SourcePosition { line_start: 9, line_finish: 9, char_start: 6, char_end: 6 } }
+Token { token_kind: EOF, spelling: "$", token_position: SourcePosition { line_start: 9, line_finish: 9, char_start: 15, char_end: 15 } }
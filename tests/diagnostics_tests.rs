@@ -0,0 +1,374 @@
+#![allow(dead_code)]
+#![allow(unused_variables)]
+#![allow(unused_mut)]
+#![allow(unused_imports)]
+
+use std::fs::read_to_string;
+use std::process::{Command, Output};
+
+use serde_json::Value;
+
+fn run_vc(args: &[&str]) -> Output {
+    Command::new("./target/debug/vc")
+        .args(args)
+        .output()
+        .expect("Failed to execute command.")
+}
+
+fn check_stderr_matches(name: &str) {
+    let input_filepath = format!("./tests/Diagnostics/input/{}.vc", name);
+    let solution_filepath = format!("./tests/Diagnostics/output/{}.err", name);
+
+    let output = run_vc(&["--color=never", "check", &input_filepath]);
+    let expected = read_to_string(&solution_filepath)
+        .expect("File reading error.")
+        .replace("./tests/", "tests/");
+    let actual = String::from_utf8_lossy(&output.stderr).replace("./tests/", "tests/");
+
+    assert!(!output.status.success());
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_diagnostics_secondary_label() {
+    check_stderr_matches("redeclared");
+}
+
+#[test]
+fn test_diagnostics_syntax_error() {
+    check_stderr_matches("syntax");
+}
+
+#[test]
+fn test_diagnostics_scanner_errors() {
+    check_stderr_matches("lexical");
+}
+
+#[test]
+fn test_diagnostics_illegal_character() {
+    check_stderr_matches("illegal");
+}
+
+#[test]
+fn test_diagnostics_notes() {
+    check_stderr_matches("calls");
+}
+
+#[test]
+fn test_diagnostics_do_while() {
+    check_stderr_matches("do_while");
+}
+
+#[test]
+fn test_diagnostics_switch() {
+    check_stderr_matches("switch");
+}
+
+#[test]
+fn test_diagnostics_increment() {
+    check_stderr_matches("increment");
+}
+
+#[test]
+fn test_diagnostics_bitwise() {
+    check_stderr_matches("bitwise");
+}
+
+#[test]
+fn test_diagnostics_conditional() {
+    check_stderr_matches("conditional");
+}
+
+#[test]
+fn test_diagnostics_char() {
+    check_stderr_matches("char");
+}
+
+#[test]
+fn test_diagnostics_char_literals() {
+    check_stderr_matches("char_literals");
+}
+
+#[test]
+fn test_diagnostics_struct() {
+    check_stderr_matches("struct");
+}
+
+#[test]
+fn test_diagnostics_matrix() {
+    check_stderr_matches("matrix");
+}
+
+#[test]
+fn test_diagnostics_prototype() {
+    check_stderr_matches("prototype");
+}
+
+#[test]
+fn test_diagnostics_const() {
+    check_stderr_matches("const");
+}
+
+#[test]
+fn test_diagnostics_cast() {
+    check_stderr_matches("cast");
+}
+
+#[test]
+fn test_diagnostics_string() {
+    check_stderr_matches("string");
+}
+
+#[test]
+fn test_diagnostics_numerics() {
+    check_stderr_matches("numerics");
+}
+
+#[test]
+fn test_diagnostics_escapes() {
+    check_stderr_matches("escapes");
+}
+
+#[test]
+fn test_diagnostics_unsized() {
+    check_stderr_matches("unsized");
+}
+
+#[test]
+fn test_diagnostics_length() {
+    check_stderr_matches("length");
+}
+
+#[test]
+fn test_diagnostics_empty_file() {
+    check_stderr_matches("empty");
+}
+
+#[test]
+fn test_diagnostics_byte_order_mark() {
+    check_stderr_matches("bom");
+}
+
+#[test]
+fn test_diagnostics_color_always() {
+    let output = run_vc(&[
+        "--color=always",
+        "check",
+        "./tests/Diagnostics/input/calls.vc",
+    ]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(stderr.contains("\x1b[1;31merror[*26]\x1b[0m"));
+}
+
+#[test]
+fn test_diagnostics_color_never() {
+    let output = run_vc(&[
+        "--color=never",
+        "check",
+        "./tests/Diagnostics/input/calls.vc",
+    ]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(!stderr.contains('\x1b'));
+}
+
+#[test]
+fn test_checker_reports_vc_errors() {
+    let output = run_vc(&["--color=never", "check", "./tests/Checker/t1.vc"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    for expected in [
+        "error[*3]: identifier declared void: v",
+        "error[*31]: missing return statement",
+        "error[*2]: identifier redeclared: f",
+        "error[*4]: identifier declared void[]: k",
+        "error[*10]: incompatible type for this unary operator: !",
+        "error[*7]: invalid lvalue in assignment",
+        "error[*6]: incompatible type for =",
+        "error[*8]: incompatible type for return",
+        "error[*30]: statement(s) not reached",
+        "error[*20]: if conditional is not boolean (found: float)",
+        "error[*5]: identifier undeclared: g",
+        "error[*19]: attempt to reference a scalar/array as a function: i",
+        "error[*26]: too few actual parameters",
+        "error[*27]: wrong type for actual parameter: k",
+        "error[*25]: too many actual parameters",
+        "error[*23]: break must be in a loop or switch",
+        "error[*24]: continue must be in a while/for",
+        "error[*1]: return type of main is not int",
+    ] {
+        assert!(
+            stderr.contains(expected),
+            "missing \"{}\" in:\n{}",
+            expected,
+            stderr
+        );
+    }
+}
+
+#[test]
+fn test_checker_accepts_valid_programs() {
+    for input_filepath in [
+        "./tests/Checker/successfulBitwise.vc",
+        "./tests/Checker/successfulBreak.vc",
+        "./tests/Checker/successfulCast.vc",
+        "./tests/Checker/successfulChar.vc",
+        "./tests/Checker/successfulConditional.vc",
+        "./tests/Checker/successfulConst.vc",
+        "./tests/Checker/successfulDoWhile.vc",
+        "./tests/Checker/successfulIncrement.vc",
+        "./tests/Checker/successfulLength.vc",
+        "./tests/Checker/successfulMatrix.vc",
+        "./tests/Checker/successfulNumerics.vc",
+        "./tests/Checker/successfulParameterParse.vc",
+        "./tests/Checker/successfulPrototype.vc",
+        "./tests/Checker/successfulString.vc",
+        "./tests/Checker/successfulStruct.vc",
+        "./tests/Checker/successfulSwitch.vc",
+        "./tests/Checker/successfulUnsized.vc",
+        "./tests/Checker/successfulReturn.vc",
+        "./tests/Checker/testComplex.vc",
+        "./tests/Checker/testFuncCall.vc",
+    ] {
+        let output = run_vc(&["--color=never", "check", input_filepath]);
+        assert!(
+            output.status.success(),
+            "{}: {}",
+            input_filepath,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+}
+
+#[test]
+fn test_error_format_json() {
+    let output = run_vc(&[
+        "--error-format=json",
+        "check",
+        "./tests/Diagnostics/input/calls.vc",
+    ]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let diagnostics: Vec<Value> = stderr
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("Each line should be a JSON object."))
+        .collect();
+
+    assert!(!output.status.success());
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0]["severity"], "error");
+    assert_eq!(diagnostics[0]["code"], "*26");
+    assert_eq!(diagnostics[0]["message"], "too few actual parameters");
+    assert_eq!(diagnostics[0]["notes"][0], "add takes 2 parameter(s)");
+    assert_eq!(diagnostics[1]["code"], "*25");
+    assert_eq!(diagnostics[1]["span"]["line_start"], 8);
+    assert_eq!(diagnostics[1]["span"]["char_start"], 20);
+}
+
+#[test]
+fn test_error_format_json_every_subcommand() {
+    for subcommand in ["scan", "parse", "unparse", "check"] {
+        let output = run_vc(&[
+            "--error-format=json",
+            subcommand,
+            "./tests/Diagnostics/input/illegal.vc",
+        ]);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let first: Value = serde_json::from_str(stderr.lines().next().unwrap()).unwrap();
+
+        assert!(!output.status.success());
+        assert_eq!(first["code"], "S1", "{}", subcommand);
+    }
+}
+
+#[test]
+fn test_error_format_missing_input_file() {
+    let input_filepath = "./tests/Diagnostics/input/missing.vc";
+
+    let output = run_vc(&["--error-format=json", "check", input_filepath]);
+    let diagnostic: Value = serde_json::from_slice(&output.stderr).expect("Should be JSON.");
+    assert!(!output.status.success());
+    assert_eq!(diagnostic["code"], "I3");
+    assert_eq!(diagnostic["file"], input_filepath);
+
+    let output = run_vc(&["--error-format=sarif", "scan", input_filepath]);
+    let log: Value = serde_json::from_slice(&output.stderr).expect("Should be a single JSON log.");
+    assert!(!output.status.success());
+    assert_eq!(log["runs"][0]["results"][0]["ruleId"], "I3");
+}
+
+#[test]
+fn test_error_format_sarif() {
+    let output = run_vc(&[
+        "--error-format=sarif",
+        "check",
+        "./tests/Diagnostics/input/redeclared.vc",
+    ]);
+    let log: Value = serde_json::from_slice(&output.stderr).expect("Should be a single JSON log.");
+    let run = &log["runs"][0];
+    let result = &run["results"][0];
+
+    assert!(!output.status.success());
+    assert_eq!(log["version"], "2.1.0");
+    assert_eq!(run["tool"]["driver"]["name"], "vc");
+    assert_eq!(run["results"].as_array().unwrap().len(), 1);
+    assert_eq!(result["ruleId"], "*2");
+    assert_eq!(result["level"], "error");
+    assert_eq!(
+        run["tool"]["driver"]["rules"][result["ruleIndex"].as_u64().unwrap() as usize]["id"],
+        "*2"
+    );
+
+    // The declaration is "\tint count;", so code point columns 6 to 10.  An earlier line holds
+    // two characters of two bytes each, which charOffset counts once each.
+    let location = &result["locations"][0]["physicalLocation"];
+    assert_eq!(
+        location["artifactLocation"]["uri"],
+        "tests/Diagnostics/input/redeclared.vc"
+    );
+    assert_eq!(location["region"]["startLine"], 6);
+    assert_eq!(location["region"]["startColumn"], 6);
+    assert_eq!(location["region"]["endColumn"], 11);
+    assert_eq!(location["region"]["charOffset"], 71);
+    assert_eq!(location["region"]["charLength"], 5);
+    assert_eq!(
+        result["relatedLocations"][0]["message"]["text"],
+        "previously declared here"
+    );
+}
+
+// An absolute path is a file URI of its own rather than one relative to %SRCROOT%.
+#[test]
+fn test_error_format_sarif_absolute_path() {
+    let directory = std::env::temp_dir().join("vc sarif #1");
+    std::fs::create_dir_all(&directory).unwrap();
+    let input_filepath = directory.join("redeclared.vc");
+    std::fs::copy("./tests/Diagnostics/input/redeclared.vc", &input_filepath).unwrap();
+
+    let output = run_vc(&[
+        "--error-format=sarif",
+        "check",
+        input_filepath.to_str().unwrap(),
+    ]);
+    let log: Value = serde_json::from_slice(&output.stderr).expect("Should be a single JSON log.");
+    let location = &log["runs"][0]["results"][0]["locations"][0]["physicalLocation"];
+    let uri = location["artifactLocation"]["uri"].as_str().unwrap();
+
+    assert!(uri.starts_with("file:///"), "{}", uri);
+    assert!(uri.ends_with("/vc%20sarif%20%231/redeclared.vc"), "{}", uri);
+    assert!(location["artifactLocation"].get("uriBaseId").is_none());
+    assert!(log["runs"][0]["artifacts"][0]["location"].get("uriBaseId").is_none());
+}
+
+#[test]
+fn test_error_format_sarif_without_errors() {
+    let output = run_vc(&[
+        "--error-format=sarif",
+        "check",
+        "./tests/Checker/successfulReturn.vc",
+    ]);
+    let log: Value = serde_json::from_slice(&output.stderr).expect("Should be a single JSON log.");
+
+    assert!(output.status.success());
+    assert_eq!(log["runs"][0]["results"].as_array().unwrap().len(), 0);
+}
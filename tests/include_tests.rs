@@ -0,0 +1,72 @@
+#![allow(dead_code)]
+#![allow(unused_variables)]
+#![allow(unused_mut)]
+#![allow(unused_imports)]
+
+use std::fs::read_to_string;
+use std::process::{Command, Output};
+
+fn run_vc(args: &[&str]) -> Output {
+    Command::new("./target/debug/vc")
+        .args(args)
+        .output()
+        .expect("Failed to execute command.")
+}
+
+fn check_stderr_matches(name: &str) {
+    let input_filepath = format!("./tests/Include/input/{}.vc", name);
+    let solution_filepath = format!("./tests/Include/output/{}.err", name);
+
+    let output = run_vc(&["--color=never", "check", &input_filepath]);
+    let expected = read_to_string(&solution_filepath)
+        .expect("File reading error.")
+        .replace("./tests/", "tests/");
+    let actual = String::from_utf8_lossy(&output.stderr).replace("./tests/", "tests/");
+
+    assert!(!output.status.success());
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_include_merges_declarations() {
+    // gcd.vc and common.vc are each included twice, but read once.
+    let output = run_vc(&["check", "./tests/Include/input/main.vc"]);
+
+    assert_eq!(String::from_utf8_lossy(&output.stderr), "");
+    assert!(output.status.success());
+}
+
+#[test]
+fn test_include_error_in_included_file() {
+    check_stderr_matches("error_in_include");
+}
+
+#[test]
+fn test_include_redeclared_across_files() {
+    check_stderr_matches("redeclared");
+}
+
+#[test]
+fn test_include_type_error_in_main_file() {
+    check_stderr_matches("type_error");
+}
+
+#[test]
+fn test_include_missing_file() {
+    check_stderr_matches("missing");
+}
+
+#[test]
+fn test_include_cycle() {
+    check_stderr_matches("cycle_a");
+}
+
+#[test]
+fn test_include_malformed() {
+    check_stderr_matches("malformed");
+}
+
+#[test]
+fn test_include_macro_defined_in_included_file() {
+    check_stderr_matches("macro_in_include");
+}
@@ -0,0 +1,374 @@
+#![allow(dead_code)]
+#![allow(unused_variables)]
+#![allow(unused_mut)]
+#![allow(unused_imports)]
+
+use std::fs::read_to_string;
+use std::io::{Cursor, Write};
+use std::process::{Command, Stdio};
+
+use serde_json::{json, Value};
+
+use vc::lsp::protocol::{read_message, write_message};
+
+const URI: &str = "file:///gcd.vc";
+
+// Pipes a scripted session into `vc lsp`, returning everything the server wrote and whether it
+// exited successfully.
+fn run_session(messages: &[Value]) -> (Vec<Value>, bool) {
+    let mut input = Vec::new();
+    for message in messages {
+        write_message(&mut input, message).unwrap();
+    }
+
+    let mut child = Command::new("./target/debug/vc")
+        .arg("lsp")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("Failed to execute command.");
+    child.stdin.take().unwrap().write_all(&input).unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    let mut reader = Cursor::new(output.stdout);
+    let mut replies = Vec::new();
+    while let Some(body) = read_message(&mut reader).unwrap() {
+        replies.push(serde_json::from_str(&body).unwrap());
+    }
+    (replies, output.status.success())
+}
+
+fn request(id: u64, method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params })
+}
+
+fn notification(method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "method": method, "params": params })
+}
+
+fn did_open(text: &str) -> Value {
+    notification(
+        "textDocument/didOpen",
+        json!({ "textDocument": { "uri": URI, "languageId": "vc", "version": 1, "text": text } }),
+    )
+}
+
+fn text_document_position(line: u64, character: u64) -> Value {
+    json!({
+        "textDocument": { "uri": URI },
+        "position": { "line": line, "character": character },
+    })
+}
+
+// Opens tests/Lsp/gcd.vc, makes one request of it and returns the result.
+fn request_on_gcd(method: &str, params: Value) -> Value {
+    let text = read_to_string("./tests/Lsp/gcd.vc").expect("File reading error.");
+    let (replies, _) = run_session(&[
+        request(1, "initialize", json!({ "capabilities": {} })),
+        notification("initialized", json!({})),
+        did_open(&text),
+        request(2, method, params),
+        request(3, "shutdown", Value::Null),
+        notification("exit", Value::Null),
+    ]);
+    response_to(&replies, 2)["result"].clone()
+}
+
+fn response_to(replies: &[Value], id: u64) -> &Value {
+    replies
+        .iter()
+        .find(|reply| reply["id"] == json!(id))
+        .expect("No response to request.")
+}
+
+fn published_diagnostics(replies: &[Value]) -> Vec<&Value> {
+    replies
+        .iter()
+        .filter(|reply| reply["method"] == "textDocument/publishDiagnostics")
+        .map(|reply| &reply["params"]["diagnostics"])
+        .collect()
+}
+
+#[test]
+fn test_lsp_lifecycle() {
+    let (replies, success) = run_session(&[
+        request(1, "initialize", json!({ "capabilities": {} })),
+        notification("initialized", json!({})),
+        request(2, "shutdown", Value::Null),
+        notification("exit", Value::Null),
+    ]);
+
+    let capabilities = &response_to(&replies, 1)["result"]["capabilities"];
+    assert_eq!(capabilities["textDocumentSync"], 1);
+    assert_eq!(capabilities["hoverProvider"], true);
+    assert_eq!(capabilities["definitionProvider"], true);
+    assert_eq!(capabilities["documentSymbolProvider"], true);
+    assert_eq!(
+        capabilities["semanticTokensProvider"]["legend"]["tokenTypes"][0],
+        "keyword"
+    );
+    assert_eq!(response_to(&replies, 2)["result"], Value::Null);
+    assert!(success);
+}
+
+#[test]
+fn test_lsp_exit_without_shutdown() {
+    let (replies, success) = run_session(&[
+        request(1, "initialize", json!({ "capabilities": {} })),
+        notification("exit", Value::Null),
+    ]);
+
+    assert_eq!(replies.len(), 1);
+    assert!(!success);
+}
+
+#[test]
+fn test_lsp_unknown_method() {
+    let (replies, _) = run_session(&[
+        request(1, "initialize", json!({ "capabilities": {} })),
+        request(2, "textDocument/rename", text_document_position(0, 0)),
+        request(3, "shutdown", Value::Null),
+        notification("exit", Value::Null),
+    ]);
+
+    assert_eq!(response_to(&replies, 2)["error"]["code"], -32601);
+}
+
+#[test]
+fn test_lsp_publish_diagnostics() {
+    let (replies, _) = run_session(&[
+        request(1, "initialize", json!({ "capabilities": {} })),
+        did_open("int main() { return x; }"),
+        notification(
+            "textDocument/didChange",
+            json!({
+                "textDocument": { "uri": URI, "version": 2 },
+                "contentChanges": [{ "text": "int main() { return 0; }" }],
+            }),
+        ),
+        request(2, "shutdown", Value::Null),
+        notification("exit", Value::Null),
+    ]);
+
+    let published = published_diagnostics(&replies);
+    assert_eq!(published.len(), 2);
+
+    let undeclared = &published[0][0];
+    assert_eq!(undeclared["code"], "*5");
+    assert_eq!(undeclared["severity"], 1);
+    assert_eq!(undeclared["message"], "identifier undeclared: x");
+    assert_eq!(
+        undeclared["range"],
+        json!({
+            "start": { "line": 0, "character": 20 },
+            "end": { "line": 0, "character": 21 },
+        })
+    );
+    assert_eq!(*published[1], json!([]));
+}
+
+#[test]
+fn test_lsp_publish_syntax_error() {
+    let (replies, _) = run_session(&[did_open("int main() { return 0 }")]);
+
+    let published = published_diagnostics(&replies);
+    assert_eq!(published[0].as_array().unwrap().len(), 1);
+    assert_eq!(
+        published[0][0]["range"]["start"],
+        json!({ "line": 0, "character": 22 })
+    );
+}
+
+#[test]
+fn test_lsp_hover() {
+    let hover = request_on_gcd("textDocument/hover", text_document_position(11, 7));
+    assert_eq!(
+        hover["contents"]["value"],
+        "```vc\nint gcd(int a, int b)\n```"
+    );
+    assert_eq!(
+        hover["range"],
+        json!({
+            "start": { "line": 11, "character": 6 },
+            "end": { "line": 11, "character": 9 },
+        })
+    );
+
+    let hover = request_on_gcd("textDocument/hover", text_document_position(1, 8));
+    assert_eq!(hover["contents"]["value"], "```vc\nfloat scale[3]\n```");
+
+    let hover = request_on_gcd("textDocument/hover", text_document_position(12, 4));
+    let contents = hover["contents"]["value"].as_str().unwrap();
+    assert!(contents.starts_with("```vc\nvoid putIntLn(int i)\n```"));
+
+    let hover = request_on_gcd("textDocument/hover", text_document_position(13, 3));
+    assert_eq!(hover, Value::Null);
+}
+
+#[test]
+fn test_lsp_definition() {
+    let definition = request_on_gcd("textDocument/definition", text_document_position(11, 12));
+    assert_eq!(definition["uri"], URI);
+    assert_eq!(
+        definition["range"],
+        json!({
+            "start": { "line": 0, "character": 4 },
+            "end": { "line": 0, "character": 9 },
+        })
+    );
+
+    let definition = request_on_gcd("textDocument/definition", text_document_position(6, 13));
+    assert_eq!(
+        definition["range"]["start"],
+        json!({ "line": 3, "character": 19 })
+    );
+
+    let definition = request_on_gcd("textDocument/definition", text_document_position(12, 4));
+    assert_eq!(definition, Value::Null);
+}
+
+#[test]
+fn test_lsp_document_symbols() {
+    let symbols = request_on_gcd(
+        "textDocument/documentSymbol",
+        json!({ "textDocument": { "uri": URI } }),
+    );
+
+    let outline: Vec<(&str, &str, u64)> = symbols
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|symbol| {
+            (
+                symbol["name"].as_str().unwrap(),
+                symbol["detail"].as_str().unwrap(),
+                symbol["kind"].as_u64().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        outline,
+        vec![
+            ("limit", "int limit", 13),
+            ("scale", "float scale[3]", 13),
+            ("gcd", "int gcd(int a, int b)", 12),
+            ("main", "int main()", 12),
+        ]
+    );
+    assert_eq!(
+        symbols[2]["range"],
+        json!({
+            "start": { "line": 3, "character": 4 },
+            "end": { "line": 7, "character": 1 },
+        })
+    );
+}
+
+#[test]
+fn test_lsp_semantic_tokens() {
+    let tokens = request_on_gcd(
+        "textDocument/semanticTokens/full",
+        json!({ "textDocument": { "uri": URI } }),
+    );
+    let data: Vec<u64> = tokens["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|value| value.as_u64().unwrap())
+        .collect();
+
+    // int limit = 10;
+    assert_eq!(
+        data[..20],
+        [0, 0, 3, 1, 0, 0, 4, 5, 4, 0, 0, 6, 1, 7, 0, 0, 2, 2, 5, 0]
+    );
+    // float scale, on the next line.
+    assert_eq!(data[20..30], [1, 0, 5, 1, 0, 0, 6, 5, 4, 0]);
+    // int gcd(int a, ...: the function and its first parameter.
+    assert_eq!(data[40..50], [0, 4, 3, 2, 0, 0, 4, 3, 1, 0]);
+    assert_eq!(data[50..55], [0, 4, 1, 3, 0]);
+}
+
+#[test]
+fn test_lsp_semantic_tokens_for_macros() {
+    let (replies, _) = run_session(&[
+        request(1, "initialize", json!({ "capabilities": {} })),
+        did_open("#define SIZE 2 + 3\nint main() { return SIZE; }"),
+        request(
+            2,
+            "textDocument/semanticTokens/full",
+            json!({ "textDocument": { "uri": URI } }),
+        ),
+        request(3, "shutdown", Value::Null),
+        notification("exit", Value::Null),
+    ]);
+    let data: Vec<u64> = response_to(&replies, 2)["result"]["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|value| value.as_u64().unwrap())
+        .collect();
+
+    // int main() { return SIZE; }, with the three tokens of SIZE's expansion sent as one macro.
+    assert_eq!(
+        data,
+        [1, 0, 3, 1, 0, 0, 4, 4, 2, 0, 0, 9, 6, 0, 0, 0, 7, 4, 8, 0]
+    );
+}
+
+fn file_uri(path: &str) -> String {
+    let canonical = std::fs::canonicalize(path).unwrap();
+    format!("file://{}", canonical.display())
+}
+
+#[test]
+fn test_lsp_includes() {
+    let main_uri = file_uri("./tests/Include/input/error_in_include.vc");
+    let broken_uri = file_uri("./tests/Include/input/helpers/broken.vc");
+    let text = read_to_string("./tests/Include/input/error_in_include.vc").unwrap();
+
+    let (replies, _) = run_session(&[notification(
+        "textDocument/didOpen",
+        json!({ "textDocument": { "uri": main_uri, "languageId": "vc", "version": 1, "text": text } }),
+    )]);
+
+    // The error is in the included file, so it is published there.
+    let published: Vec<(&str, usize)> = replies
+        .iter()
+        .map(|reply| {
+            (
+                reply["params"]["uri"].as_str().unwrap(),
+                reply["params"]["diagnostics"].as_array().unwrap().len(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        published,
+        vec![(main_uri.as_str(), 0), (broken_uri.as_str(), 1)]
+    );
+
+    let main_uri = file_uri("./tests/Include/input/main.vc");
+    let gcd_uri = file_uri("./tests/Include/input/helpers/gcd.vc");
+    let text = read_to_string("./tests/Include/input/main.vc").unwrap();
+    let (replies, _) = run_session(&[
+        notification(
+            "textDocument/didOpen",
+            json!({ "textDocument": { "uri": main_uri, "languageId": "vc", "version": 1, "text": text } }),
+        ),
+        request(
+            1,
+            "textDocument/definition",
+            json!({
+                "textDocument": { "uri": main_uri },
+                "position": { "line": 4, "character": 12 },
+            }),
+        ),
+    ]);
+
+    let definition = &response_to(&replies, 1)["result"];
+    assert_eq!(definition["uri"], gcd_uri);
+    assert_eq!(
+        definition["range"]["start"],
+        json!({ "line": 3, "character": 4 })
+    );
+}
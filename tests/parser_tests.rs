@@ -49,3 +49,94 @@ fn test_parser_defaults() {
 
     let input_file_string = read_to_string(input_filepath).expect("File reading error.");
 }
+
+fn capture_parse_stdout(input_filepath: &str) -> String {
+    let subprocess_output = Command::new("./target/debug/vc")
+        .arg("parse")
+        .arg(input_filepath)
+        .output()
+        .expect("Failed to execute command.");
+
+    String::from_utf8_lossy(&subprocess_output.stdout).to_string()
+}
+
+// Parses tests/Parser/input/<name>.vc and compares the printed AST with output/<name>.ast.
+fn test_parse_filepath(name: &str) {
+    let input_filepath = format!("./tests/Parser/input/{}.vc", name);
+    let solution_filepath = format!("./tests/Parser/output/{}.ast", name);
+
+    let actual = capture_parse_stdout(&input_filepath);
+    let expected = read_to_string(solution_filepath).expect("File reading error.");
+
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_parser_precedence_and_associativity() {
+    test_parse_filepath("tPrecedence");
+}
+
+#[test]
+fn test_parser_do_while() {
+    test_parse_filepath("tDoWhile");
+}
+
+#[test]
+fn test_parser_switch() {
+    test_parse_filepath("tSwitch");
+}
+
+#[test]
+fn test_parser_increment() {
+    test_parse_filepath("tIncrement");
+}
+
+#[test]
+fn test_parser_bitwise() {
+    test_parse_filepath("tBitwise");
+}
+
+#[test]
+fn test_parser_conditional() {
+    test_parse_filepath("tConditional");
+}
+
+#[test]
+fn test_parser_char() {
+    test_parse_filepath("tChar");
+}
+
+#[test]
+fn test_parser_struct() {
+    test_parse_filepath("tStruct");
+}
+
+#[test]
+fn test_parser_matrix() {
+    test_parse_filepath("tMatrix");
+}
+
+#[test]
+fn test_parser_prototype() {
+    test_parse_filepath("tPrototype");
+}
+
+#[test]
+fn test_parser_const() {
+    test_parse_filepath("tConst");
+}
+
+#[test]
+fn test_parser_cast() {
+    test_parse_filepath("tCast");
+}
+
+#[test]
+fn test_parser_string() {
+    test_parse_filepath("tString");
+}
+
+#[test]
+fn test_parser_unsized_arrays() {
+    test_parse_filepath("tUnsized");
+}
@@ -0,0 +1,72 @@
+#![allow(dead_code)]
+#![allow(unused_variables)]
+#![allow(unused_mut)]
+#![allow(unused_imports)]
+
+use std::fs::read_to_string;
+use std::process::{Command, Output};
+
+fn run_vc(args: &[&str]) -> Output {
+    Command::new("./target/debug/vc")
+        .args(args)
+        .output()
+        .expect("Failed to execute command.")
+}
+
+fn check_stderr_matches(name: &str, solution: &str, defines: &[&str]) {
+    let input_filepath = format!("./tests/Preprocessor/input/{}.vc", name);
+    let solution_filepath = format!("./tests/Preprocessor/output/{}.err", solution);
+
+    let mut args = vec!["--color=never", "check"];
+    args.extend(defines);
+    args.push(&input_filepath);
+    let output = run_vc(&args);
+    let expected = read_to_string(&solution_filepath)
+        .expect("File reading error.")
+        .replace("./tests/", "tests/");
+    let actual = String::from_utf8_lossy(&output.stderr).replace("./tests/", "tests/");
+
+    assert!(!output.status.success());
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn test_preprocessor_constants() {
+    let output = run_vc(&["check", "./tests/Preprocessor/input/constants.vc"]);
+
+    assert_eq!(String::from_utf8_lossy(&output.stderr), "");
+    assert!(output.status.success());
+}
+
+#[test]
+fn test_preprocessor_conditional_compilation() {
+    let output = run_vc(&["check", "./tests/Preprocessor/input/debug.vc"]);
+    assert!(output.status.success());
+
+    check_stderr_matches("debug", "debug_defined", &["-DDEBUG"]);
+}
+
+#[test]
+fn test_preprocessor_command_line_value() {
+    let output = run_vc(&["check", "-DSIZE=8", "./tests/Preprocessor/input/debug.vc"]);
+    assert!(output.status.success());
+
+    let output = run_vc(&[
+        "check",
+        "-D",
+        "8=SIZE",
+        "./tests/Preprocessor/input/debug.vc",
+    ]);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("invalid macro name: 8"));
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_preprocessor_error_maps_to_macro_use() {
+    check_stderr_matches("type_error", "type_error", &[]);
+}
+
+#[test]
+fn test_preprocessor_directive_errors() {
+    check_stderr_matches("directive_errors", "directive_errors", &[]);
+}